@@ -1,11 +1,22 @@
 // constants.rs - UPDATED FOR DAY 19
 
+use crate::state::{ApyTier, ClaimTier};
+
+// ==================== ACCOUNT VERSIONING ====================
+// Accounts whose fields are only ever appended; older ones are grown in place with
+// the new fields' defaults. Ones written before the version byte existed are told
+// apart by size and get it inserted first.
+pub const CURRENT_ACCOUNT_VERSION: u8 = 1;
+// Bump on every StakeAccount layout change and add its step to migrate_all_stakes
+pub const STAKE_ACCOUNT_VERSION: u8 = 6;
+
 // ==================== TIERED APY CONFIGURATION ====================
 
-// Time Thresholds (in seconds)
-pub const SECONDS_IN_SEVEN_DAYS: i64 = 604_800;    // 7 days
-pub const SECONDS_IN_THIRTY_DAYS: i64 = 2_592_000;  // 30 days
-pub const SECONDS_IN_NINETY_DAYS: i64 = 7_776_000;  // 90 days
+// Time Thresholds (in seconds) - plain day counts, not calendar months,
+// so they line up with the Actual/365 year below
+pub const SECONDS_IN_SEVEN_DAYS: i64 = 7 * SECONDS_PER_DAY;    // 7 days
+pub const SECONDS_IN_THIRTY_DAYS: i64 = 30 * SECONDS_PER_DAY;  // 30 days
+pub const SECONDS_IN_NINETY_DAYS: i64 = 90 * SECONDS_PER_DAY;  // 90 days
 
 // APY Tiers
 // Tier 1: 7-29 days → 5% APY
@@ -20,51 +31,525 @@ pub const TIER_2_APY_DENOMINATOR: u64 = 100;
 pub const TIER_3_APY_NUMERATOR: u64 = 20;
 pub const TIER_3_APY_DENOMINATOR: u64 = 100;
 
+// Denominator of interpolated rates between tiers (see get_effective_apy)
+pub const APY_INTERPOLATION_PRECISION: u64 = 1_000_000_000;
+
+// ==================== TOKEN DECIMALS ====================
+pub const DEVR_DECIMALS: u8 = 9;
+pub const ONE_DEVR: u64 = 10u64.pow(DEVR_DECIMALS as u32);
+
 // ==================== TIME CONSTANTS ====================
-pub const SECONDS_PER_YEAR: u64 = 31_536_000; // 365 days in seconds
+// Day-count convention: Actual/365 Fixed. Rewards accrue per actual second of
+// eligible lock time over a fixed 365-day year (no leap days, no 360-day months),
+// so a 365-day lock earns exactly the tier APY and a 30-day lock earns 30/365 of it.
+pub const SECONDS_PER_DAY: i64 = 86_400;
+pub const DAYS_PER_YEAR: u64 = 365;
+pub const SECONDS_PER_YEAR: u64 = DAYS_PER_YEAR * SECONDS_PER_DAY as u64; // 31_536_000
 
 // ==================== LOCK DURATION LIMITS ====================
 pub const MIN_LOCK_DURATION: i64 = 604_800;      // 7 days in seconds
 pub const MAX_LOCK_DURATION: i64 = 315_360_000;  // 10 years in seconds
 
+// Longest stretch of a lock that earns rewards; longer locks still restrict
+// withdrawal for their full length (admin-configurable on TokenConfig)
+pub const DEFAULT_MAX_REWARD_ELIGIBLE_DURATION: i64 = 3 * DAYS_PER_YEAR as i64 * SECONDS_PER_DAY; // 3 years
+
+// Longest warm-up an admin may set before rewards start accruing
+pub const MAX_REWARD_ACCRUAL_DELAY: i64 = 30 * SECONDS_PER_DAY;
+// Which duration rewards are paid on (TokenConfig.reward_basis)
+pub const REWARD_BASIS_COMMITTED: u8 = 0; // The whole committed lock, however early or late the unstake
+pub const REWARD_BASIS_ELAPSED: u8 = 1;   // Time actually staked, capped at the lock
+
+// Grace before maturity for validator clock skew at the exact lock end. Kept tiny
+// so it can't meaningfully shorten a lock (30s of a 7-day minimum)
+pub const UNSTAKE_SKEW_TOLERANCE: i64 = 30;
+
 // ==================== STAKE AMOUNT LIMITS ====================
 pub const MIN_STAKE_AMOUNT: u64 = 1_000_000_000;         // 1 DEVR (9 decimals)
 pub const MAX_STAKE_AMOUNT: u64 = 100_000_000_000_000;   // 100,000 DEVR
 
+// ==================== LOYALTY HOLDING BONUS ====================
+// Snapshotted at stake time from the liquid balance left in the user's ATA
+pub const LOYALTY_MIN_HOLD: u64 = 100_000_000_000;  // 100 DEVR held after staking
+pub const LOYALTY_APY_BONUS_BPS: u64 = 100;         // +1% APY
+
+// ==================== STAKE BOOST ====================
+// Boost tokens deposited alongside a stake multiply its base APY until unstake
+pub const MAX_BOOST_MULTIPLIER_BPS: u64 = 5_000; // Boost adds at most +50% of the base APY
+
+// ==================== EPOCH BONUS ====================
+// Stakes whose lock fully covers a configured program epoch earn extra APY
+pub const EPOCH_BONUS_BPS: u64 = 200; // +2% APY
+
+// ==================== UNSTAKE CAP ====================
+// Anti-whale limit on principal + rewards withdrawn in one transaction
+pub const NO_UNSTAKE_CAP: u64 = u64::MAX; // Disables the cap
+
+// ==================== REWARD EMISSIONS CAP ====================
+// Lifetime ceiling on staking rewards paid out or compounded
+pub const NO_REWARD_CAP: u64 = u64::MAX; // Disables the cap
+
+// ==================== EARLY UNSTAKE ====================
+// Share of principal an emergency exit before maturity forfeits (on top of all rewards)
+pub const DEFAULT_EARLY_UNSTAKE_PENALTY_BPS: u64 = 1_000; // 10%
+pub const MAX_EARLY_UNSTAKE_PENALTY_BPS: u64 = 5_000;     // 50%
+
+// ==================== CLAIM STREAKS ====================
+// A claim at most this long after the previous one continues the streak
+pub const CLAIM_STREAK_WINDOW: i64 = 2 * SECONDS_PER_DAY; // 48 hours
+pub const STREAK_BONUS_BPS_PER_DAY: u64 = 100;             // +1% per consecutive day after the first
+pub const MAX_STREAK_BONUS_BPS: u64 = 10_000;              // Configured cap can't exceed +100%
+
+// ==================== INSURANCE FUND ====================
+// Share of unstake rewards routed to the fund that backs reward-vault shortfalls
+pub const MAX_INSURANCE_FEE_BPS: u64 = 1_000; // 10% of rewards
+
+// ==================== FAUCET ====================
+// Base amount each user may claim per day (admin-configurable on TokenConfig)
+pub const DEFAULT_DAILY_CLAIM_AMOUNT: u64 = 100_000_000_000;  // 100 DEVR
+pub const MIN_DAILY_CLAIM_AMOUNT: u64 = 1_000_000_000;        // 1 DEVR
+pub const MAX_DAILY_CLAIM_AMOUNT: u64 = 10_000_000_000_000;   // 10,000 DEVR
+pub const DEFAULT_CLAIM_COOLDOWN: i64 = SECONDS_PER_DAY;     // 24 hours between claims
+pub const MAX_CLAIM_COOLDOWN: i64 = 30 * SECONDS_PER_DAY;    // Anything positive up to 30 days
+pub const NO_LIFETIME_CLAIM_CAP: u64 = 0;                     // max_lifetime_claim value meaning unlimited
+
+// ==================== REFERRALS ====================
+// Minted to a registered referrer on each claim that names them
+pub const DEFAULT_REFERRAL_BONUS: u64 = 10_000_000_000;      // 10 DEVR
+pub const MAX_REFERRAL_BONUS: u64 = 1_000_000_000_000;       // 1,000 DEVR
+
+// ==================== AUTO-CLAIM ====================
+// Keepers mint opted-in users' accrued daily claims straight into a stake
+pub const MAX_ACCRUAL_DAYS: i64 = 7; // Older unclaimed days are forfeited
+pub const AUTO_CLAIM_KEEPER_FEE_BPS: u64 = 100; // 1% of the minted amount
+
+// ==================== UNBONDING ====================
+// Positions a user may have waiting in the two-phase unstake queue at once
+pub const MAX_UNBONDING_PER_USER: u64 = 20;
+pub const MAX_UNSTAKE_DELAY: i64 = 7 * SECONDS_PER_DAY; // Longest request-to-withdraw wait
+
+// ==================== OPEN POSITIONS ====================
+// Positions a user may hold open at once, bounding per-user account growth
+pub const MAX_STAKES_PER_USER: u64 = 100;
+
+// ==================== CLAIM TIERS ====================
+pub const BASIS_POINTS: u64 = 10_000;
+pub const MAX_CLAIM_MULTIPLIER_BPS: u64 = 50_000; // 5x daily amount
+
+// Default tier table: (min active stake, multiplier)
+pub const CLAIM_TIER_1_MIN_STAKED: u64 = 100_000_000_000;      // 100 DEVR
+pub const CLAIM_TIER_1_MULTIPLIER_BPS: u64 = 12_500;           // 1.25x
+pub const CLAIM_TIER_2_MIN_STAKED: u64 = 1_000_000_000_000;    // 1,000 DEVR
+pub const CLAIM_TIER_2_MULTIPLIER_BPS: u64 = 15_000;           // 1.5x
+pub const CLAIM_TIER_3_MIN_STAKED: u64 = 10_000_000_000_000;   // 10,000 DEVR
+pub const CLAIM_TIER_3_MULTIPLIER_BPS: u64 = 20_000;           // 2x
+
+// ==================== DELEGATION LIMITS ====================
+pub const DEFAULT_MAX_TOTAL_DELEGATION_BPS: u64 = 10_000; // 100% of balance (no extra cap)
+pub const MAX_DELEGATION_COOLDOWN: i64 = 7 * SECONDS_PER_DAY; // Longest revoke-to-approve wait
+pub const NO_DELEGATION_AMOUNT_CAP: u64 = 0; // max_delegation_amount value meaning unlimited
+
+// ==================== CIRCUIT BREAKER ====================
+pub const CIRCUIT_BREAKER_BUCKETS: usize = 6;                        // Rolling window granularity
+pub const DEFAULT_CIRCUIT_BREAKER_WINDOW: i64 = 3_600;               // 1 hour
+pub const DEFAULT_CIRCUIT_BREAKER_THRESHOLD: u64 = 500_000_000_000_000; // 500,000 DEVR per window
+
+// ==================== TOKEN METADATA ====================
+// Metaplex field limits, shared by create_metadata and update_metadata
+pub const MAX_METADATA_NAME_LENGTH: usize = 32;
+pub const MAX_METADATA_SYMBOL_LENGTH: usize = 10;
+pub const MAX_METADATA_URI_LENGTH: usize = 200;
+
+// ==================== TRANSFER LIMITS ====================
+// Defaults for TokenConfig.min_transfer / max_transfer (admin-configurable)
+pub const MIN_TRANSFER_AMOUNT: u64 = ONE_DEVR;          // 1 token minimum
+pub const MAX_TRANSFER_AMOUNT: u64 = 10_000 * ONE_DEVR; // 10,000 tokens maximum
+
+// ==================== BATCH TRANSFER ====================
+pub const MAX_BATCH_TRANSFER: usize = 10; // Recipients per tx (compute bound)
+
+// ==================== ALLOWLIST ====================
+pub const MAX_ALLOWLIST_BATCH: usize = 10; // Entries created per tx (compute bound)
+// Faucet gate: TokenConfig.allowlist_root is a sorted-pair SHA-256 Merkle root over
+// allowlisted wallets; the all-zero root leaves the faucet open to everyone
+pub const OPEN_ALLOWLIST_ROOT: [u8; 32] = [0; 32];
+pub const ALLOWLIST_LEAF_PREFIX: u8 = 0; // Domain-separates leaves from inner nodes
+pub const ALLOWLIST_NODE_PREFIX: u8 = 1;
+pub const MAX_ALLOWLIST_PROOF_DEPTH: usize = 20; // ~1M wallets
+
+// ==================== STAKE MIGRATION ====================
+pub const MAX_MIGRATION_BATCH: usize = 10; // Positions migrated per tx (compute bound)
+
+// ==================== APY HISTORY ====================
+pub const APY_HISTORY_LEN: usize = 16; // Rate changes kept before the oldest is overwritten
+pub const MAX_TIER_APY_BPS: u64 = 10_000; // 100% - ceiling for an admin-set tier rate
+
+// ==================== LIQUIDITY PROVIDER REWARDS ====================
+pub const LP_FEE_PRECISION: u128 = 1_000_000_000_000; // Scale for fees-per-contributed-token
+
+// ==================== FEATURE FLAGS ====================
+// Bits in FeatureFlags.flags - a set bit turns the optional behavior on
+pub const FEATURE_LOYALTY_BONUS: u64 = 1 << 0;       // Loyalty APY bonus snapshotted at stake time
+pub const FEATURE_CIRCUIT_BREAKER: u64 = 1 << 1;     // Unstake-volume circuit breaker
+pub const FEATURE_STAKE_TIERED_CLAIMS: u64 = 1 << 2; // Faucet multiplier by active stake
+pub const FEATURE_INTERPOLATED_APY: u64 = 1 << 3;    // New locks quote get_effective_apy, not the tier step
+pub const ALL_FEATURES: u64 = FEATURE_LOYALTY_BONUS
+    | FEATURE_CIRCUIT_BREAKER
+    | FEATURE_STAKE_TIERED_CLAIMS
+    | FEATURE_INTERPOLATED_APY;
+// Used until the flags PDA is created. Interpolation changes what new locks earn, so it's opt-in.
+pub const DEFAULT_FEATURE_FLAGS: u64 = ALL_FEATURES & !FEATURE_INTERPOLATED_APY;
+
+// ==================== CONFIG CHANGE FIELDS ====================
+// `field` codes in ConfigChangedEvent. Append only - monitoring keys off these numbers.
+pub const CONFIG_FIELD_CLAIM_TIER_MIN_STAKED: [u8; 3] = [0, 2, 4]; // Per tier, lowest first
+pub const CONFIG_FIELD_CLAIM_TIER_MULTIPLIER_BPS: [u8; 3] = [1, 3, 5];
+pub const CONFIG_FIELD_MAX_TOTAL_DELEGATION_BPS: u8 = 6;
+pub const CONFIG_FIELD_CIRCUIT_BREAKER_WINDOW: u8 = 7;
+pub const CONFIG_FIELD_CIRCUIT_BREAKER_THRESHOLD: u8 = 8;
+pub const CONFIG_FIELD_STAKING_PAUSED: u8 = 9;
+pub const CONFIG_FIELD_FEATURE_FLAGS: u8 = 10;
+pub const CONFIG_FIELD_EPOCH_START: u8 = 11;
+pub const CONFIG_FIELD_EPOCH_LENGTH: u8 = 12;
+pub const CONFIG_FIELD_MAX_UNSTAKE_PER_TX: u8 = 13;
+pub const CONFIG_FIELD_MAX_REWARD_ELIGIBLE_DURATION: u8 = 14;
+pub const CONFIG_FIELD_VAULT_AUTHORITY: u8 = 15; // Pubkey - see ConfigPubkeyChangedEvent
+pub const CONFIG_FIELD_REWARD_ACCRUAL_DELAY: u8 = 16;
+pub const CONFIG_FIELD_MIN_REWARD_RESERVE: u8 = 17;
+pub const CONFIG_FIELD_INSURANCE_FEE_BPS: u8 = 18;
+pub const CONFIG_FIELD_DELEGATION_COOLDOWN: u8 = 19;
+pub const CONFIG_FIELD_REWARD_BASIS: u8 = 20;
+pub const CONFIG_FIELD_DAILY_CLAIM_AMOUNT: u8 = 21;
+pub const CONFIG_FIELD_EARLY_UNSTAKE_PENALTY_BPS: u8 = 22;
+pub const CONFIG_FIELD_PAUSED: u8 = 23;
+pub const CONFIG_FIELD_MAX_STREAK_BONUS_BPS: u8 = 24;
+pub const CONFIG_FIELD_ADMIN: u8 = 25; // Pubkey - see ConfigPubkeyChangedEvent
+pub const CONFIG_FIELD_MAX_TOTAL_REWARDS: u8 = 26;
+pub const CONFIG_FIELD_CLAIM_COOLDOWN_SECONDS: u8 = 27;
+pub const CONFIG_FIELD_MAX_LIFETIME_CLAIM: u8 = 28;
+pub const CONFIG_FIELD_REFERRAL_BONUS: u8 = 29;
+pub const CONFIG_FIELD_MIN_TRANSFER: u8 = 30;
+pub const CONFIG_FIELD_MAX_TRANSFER: u8 = 31;
+pub const CONFIG_FIELD_MAX_DELEGATION_AMOUNT: u8 = 32;
+pub const CONFIG_FIELD_UNSTAKE_DELAY: u8 = 33;
+pub const CONFIG_FIELD_APY_TIER_MIN_DURATION: [u8; 3] = [34, 37, 40]; // Per tier, lowest first
+pub const CONFIG_FIELD_APY_TIER_NUMERATOR: [u8; 3] = [35, 38, 41];
+pub const CONFIG_FIELD_APY_TIER_DENOMINATOR: [u8; 3] = [36, 39, 42];
+pub const CONFIG_FIELD_REWARD_DECIMALS: u8 = 43;
+pub const CONFIG_FIELD_REWARD_MINT: u8 = 44; // Pubkey - see ConfigPubkeyChangedEvent
+pub const CONFIG_FIELD_ALLOWLIST_ROOT: u8 = 45; // 32-byte root - see ConfigPubkeyChangedEvent
+
+// ==================== GOVERNANCE ====================
+// Voting weight per APY tier (bps of staked principal), on the compiled-in tier
+// durations so admin rate changes never move voting power
+pub const VOTE_WEIGHT_BPS: [u64; 3] = [10_000, 15_000, 20_000]; // 1x / 1.5x / 2x
+
+// ==================== STAKE REVIEW FREEZE ====================
+pub const MAX_FREEZE_DURATION: i64 = 2_592_000;  // 30 days - freeze lapses after this
+
+// ==================== PARAMETER PREVIEW ====================
+pub const PREVIEW_STAKE_AMOUNT: u64 = 1_000_000_000_000; // 1,000 DEVR sample stake
+// Lock durations sampled by `apy_curve` (7d, 30d, 90d, 180d, 365d)
+pub const APY_CURVE_DURATIONS: [i64; 5] = [
+    SECONDS_IN_SEVEN_DAYS,
+    SECONDS_IN_THIRTY_DAYS,
+    SECONDS_IN_NINETY_DAYS,
+    180 * SECONDS_PER_DAY,
+    DAYS_PER_YEAR as i64 * SECONDS_PER_DAY,
+];
+
+// ==================== STAKE VALIDATION ====================
+// Bits reported by `validate_stake` (set = check failed)
+pub const STAKE_CHECK_NOT_PAUSED: u32 = 1 << 0;
+pub const STAKE_CHECK_MIN_AMOUNT: u32 = 1 << 1;
+pub const STAKE_CHECK_MAX_AMOUNT: u32 = 1 << 2;
+pub const STAKE_CHECK_MIN_DURATION: u32 = 1 << 3;
+pub const STAKE_CHECK_MAX_DURATION: u32 = 1 << 4;
+pub const STAKE_CHECK_BALANCE: u32 = 1 << 5;
+// `stake` enforces the same check as InsufficientRewardReserve
+pub const STAKE_CHECK_REWARD_LIQUIDITY: u32 = 1 << 6;
+pub const ALL_STAKE_CHECKS: u32 = STAKE_CHECK_NOT_PAUSED
+    | STAKE_CHECK_MIN_AMOUNT
+    | STAKE_CHECK_MAX_AMOUNT
+    | STAKE_CHECK_MIN_DURATION
+    | STAKE_CHECK_MAX_DURATION
+    | STAKE_CHECK_BALANCE
+    | STAKE_CHECK_REWARD_LIQUIDITY;
+
 // ==================== APY CALCULATION HELPER ====================
-/// Returns (numerator, denominator) based on lock duration
-pub fn get_apy_for_duration(lock_duration: i64) -> (u64, u64) {
-    if lock_duration >= SECONDS_IN_NINETY_DAYS {
-        (TIER_3_APY_NUMERATOR, TIER_3_APY_DENOMINATOR) // 20%
-    } else if lock_duration >= SECONDS_IN_THIRTY_DAYS {
-        (TIER_2_APY_NUMERATOR, TIER_2_APY_DENOMINATOR) // 10%
+/// Returns (numerator, denominator) of the highest tier `lock_duration` reaches;
+/// locks below every tier get the lowest one
+pub fn get_apy_for_duration(tiers: &[ApyTier; 3], lock_duration: i64) -> (u64, u64) {
+    let tier = tiers
+        .iter()
+        .rev()
+        .find(|tier| lock_duration >= tier.min_duration)
+        .unwrap_or(&tiers[0]);
+    (tier.apy_numerator, tier.apy_denominator)
+}
+
+/// Piecewise-linear APY: exactly the tier rate at each tier threshold, linear in
+/// between, so one more day of lock never jumps a whole tier. Locks below the first
+/// threshold get the first rate and locks past the last get the last. Rates between
+/// thresholds come back over APY_INTERPOLATION_PRECISION.
+pub fn get_effective_apy(tiers: &[ApyTier; 3], lock_duration: i64) -> (u64, u64) {
+    let upper = match tiers.iter().position(|tier| lock_duration < tier.min_duration) {
+        Some(upper) if upper > 0 => upper,
+        _ => return get_apy_for_duration(tiers, lock_duration),
+    };
+    let (lower, upper) = (&tiers[upper - 1], &tiers[upper]);
+    if lock_duration == lower.min_duration {
+        return (lower.apy_numerator, lower.apy_denominator);
+    }
+
+    let rate = |tier: &ApyTier| {
+        tier.apy_numerator as i128 * APY_INTERPOLATION_PRECISION as i128 / tier.apy_denominator as i128
+    };
+    let (from, to) = (rate(lower), rate(upper));
+    let progress = (lock_duration - lower.min_duration) as i128;
+    let span = (upper.min_duration - lower.min_duration) as i128;
+    let interpolated = from + (to - from) * progress / span;
+    (interpolated as u64, APY_INTERPOLATION_PRECISION)
+}
+
+/// Base APY a new lock is quoted: the tier step, or the interpolated curve while
+/// FEATURE_INTERPOLATED_APY is on
+pub fn get_apy_for_lock(tiers: &[ApyTier; 3], lock_duration: i64, interpolated: bool) -> (u64, u64) {
+    if interpolated {
+        get_effective_apy(tiers, lock_duration)
+    } else {
+        get_apy_for_duration(tiers, lock_duration)
+    }
+}
+
+/// The compiled-in schedule as ApyTier entries, lowest tier first
+pub fn default_apy_tiers() -> [ApyTier; 3] {
+    [
+        ApyTier { min_duration: SECONDS_IN_SEVEN_DAYS, apy_numerator: TIER_1_APY_NUMERATOR, apy_denominator: TIER_1_APY_DENOMINATOR },
+        ApyTier { min_duration: SECONDS_IN_THIRTY_DAYS, apy_numerator: TIER_2_APY_NUMERATOR, apy_denominator: TIER_2_APY_DENOMINATOR },
+        ApyTier { min_duration: SECONDS_IN_NINETY_DAYS, apy_numerator: TIER_3_APY_NUMERATOR, apy_denominator: TIER_3_APY_DENOMINATOR },
+    ]
+}
+
+/// Returns the APY tier (1-3) a lock duration qualifies for under `tiers`
+pub fn get_tier_for_duration(tiers: &[ApyTier; 3], lock_duration: i64) -> u8 {
+    tiers
+        .iter()
+        .rposition(|tier| lock_duration >= tier.min_duration)
+        .map_or(1, |index| index as u8 + 1)
+}
+
+/// APY bonus (bps) for the liquid balance a user keeps at stake time.
+/// Point-in-time snapshot: selling later does not remove it, buying later does not add it.
+/// Extra share of the base APY (bps) a boost deposit earns: the boost-to-principal
+/// ratio, so boosting with 10% of the principal raises the rate by 10%, capped
+pub fn get_boost_multiplier_bps(boost_amount: u64, staked_amount: u64) -> u64 {
+    if staked_amount == 0 {
+        return 0;
+    }
+
+    (boost_amount as u128 * BASIS_POINTS as u128 / staked_amount as u128)
+        .min(MAX_BOOST_MULTIPLIER_BPS as u128) as u64
+}
+
+pub fn get_loyalty_bonus_bps(liquid_balance: u64) -> u64 {
+    if liquid_balance >= LOYALTY_MIN_HOLD {
+        LOYALTY_APY_BONUS_BPS
     } else {
-        (TIER_1_APY_NUMERATOR, TIER_1_APY_DENOMINATOR) // 5%
+        0
     }
 }
 
+/// True if [lock_start, lock_end] fully contains at least one epoch, where epochs are
+/// [epoch_start + k * epoch_length, epoch_start + (k + 1) * epoch_length) for k >= 0.
+/// An epoch_length of 0 disables epochs.
+pub fn contains_full_epoch(lock_start: i64, lock_end: i64, epoch_start: i64, epoch_length: i64) -> bool {
+    if epoch_length <= 0 || lock_end <= lock_start {
+        return false;
+    }
+
+    // First epoch beginning at or after the lock starts (ceiling division)
+    let offset = lock_start.saturating_sub(epoch_start);
+    let k = if offset <= 0 {
+        0
+    } else {
+        (offset + epoch_length - 1) / epoch_length
+    };
+    let first_epoch_start = match k
+        .checked_mul(epoch_length)
+        .and_then(|span| epoch_start.checked_add(span))
+    {
+        Some(start) => start,
+        None => return false,
+    };
+
+    first_epoch_start
+        .checked_add(epoch_length)
+        .is_some_and(|first_epoch_end| first_epoch_end <= lock_end)
+}
+
+// ==================== DECIMAL CONVERSION ====================
+/// Rescales a raw amount between tokens with different decimals (rounds down)
+pub fn convert_decimals(amount: u64, from_decimals: u8, to_decimals: u8) -> Option<u64> {
+    if to_decimals >= from_decimals {
+        let factor = 10u64.checked_pow((to_decimals - from_decimals) as u32)?;
+        amount.checked_mul(factor)
+    } else {
+        let factor = 10u64.checked_pow((from_decimals - to_decimals) as u32)?;
+        Some(amount / factor)
+    }
+}
+
+/// Whole tokens in a raw amount, for logs (rounds down)
+pub fn to_ui_amount(raw: u64, decimals: u8) -> u64 {
+    10u64.checked_pow(decimals as u32).map_or(0, |unit| raw / unit)
+}
+
+// ==================== DELEGATION HELPER ====================
+/// Largest allowance an account may grant under a bps-of-balance cap
+pub fn get_max_delegation(balance: u64, max_total_delegation_bps: u64) -> u64 {
+    ((balance as u128 * max_total_delegation_bps as u128) / BASIS_POINTS as u128) as u64
+}
+
+/// Whether a single allowance stays under the platform-wide per-delegate ceiling
+pub fn within_delegation_amount_cap(amount: u64, max_delegation_amount: u64) -> bool {
+    max_delegation_amount == NO_DELEGATION_AMOUNT_CAP || amount <= max_delegation_amount
+}
+
+/// Whether a re-approval is still blocked after the account's last revoke.
+/// A zero cooldown, or an account that was never revoked, never blocks.
+pub fn is_delegation_cooling_down(last_revoked_at: i64, cooldown: i64, now: i64) -> bool {
+    cooldown > 0 && last_revoked_at > 0 && now < last_revoked_at.saturating_add(cooldown)
+}
+
+// ==================== MATURITY HELPER ====================
+/// Canonical unlock time, computed once and stored on the StakeAccount
+pub fn get_maturity_timestamp(staked_at: i64, lock_duration: i64) -> Option<i64> {
+    staked_at.checked_add(lock_duration)
+}
+
+/// Seconds until the lock ends, clamped at zero once matured. The unstake gate
+/// already opens UNSTAKE_SKEW_TOLERANCE seconds before this reaches zero.
+pub fn get_seconds_to_maturity(maturity_timestamp: i64, now: i64) -> i64 {
+    maturity_timestamp.saturating_sub(now).max(0)
+}
+
+/// The unstake gate: a stake is withdrawable once its lock ends, give or take
+/// UNSTAKE_SKEW_TOLERANCE seconds of clock skew
+pub fn is_stake_matured(maturity_timestamp: i64, now: i64) -> bool {
+    now >= maturity_timestamp.saturating_sub(UNSTAKE_SKEW_TOLERANCE)
+}
+
+/// Seconds until `is_stake_matured` passes, so this hits zero exactly when
+/// unstake stops failing with StillLocked
+pub fn get_seconds_to_unlock(maturity_timestamp: i64, now: i64) -> i64 {
+    get_seconds_to_maturity(maturity_timestamp.saturating_sub(UNSTAKE_SKEW_TOLERANCE), now)
+}
+
+// ==================== CLAIM TIER HELPERS ====================
+pub fn default_claim_tiers() -> [ClaimTier; 3] {
+    [
+        ClaimTier { min_staked: CLAIM_TIER_1_MIN_STAKED, multiplier_bps: CLAIM_TIER_1_MULTIPLIER_BPS },
+        ClaimTier { min_staked: CLAIM_TIER_2_MIN_STAKED, multiplier_bps: CLAIM_TIER_2_MULTIPLIER_BPS },
+        ClaimTier { min_staked: CLAIM_TIER_3_MIN_STAKED, multiplier_bps: CLAIM_TIER_3_MULTIPLIER_BPS },
+    ]
+}
+
+/// Returns (tier, multiplier_bps) for a user's active stake. Tier 0 = base amount.
+pub fn get_claim_tier(tiers: &[ClaimTier], total_active_staked: u64) -> (u8, u64) {
+    tiers
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, tier)| total_active_staked >= tier.min_staked)
+        .map(|(i, tier)| (i as u8 + 1, tier.multiplier_bps))
+        .unwrap_or((0, BASIS_POINTS))
+}
+
 // ==================== TESTS ====================
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EPOCH_START: i64 = 1_700_000_000;
+    const EPOCH_LENGTH: i64 = SECONDS_IN_SEVEN_DAYS;
+
+    #[test]
+    fn test_lock_fully_containing_an_epoch() {
+        // Starts a day before epoch 1 and ends a day after it
+        let lock_start = EPOCH_START + EPOCH_LENGTH - 86_400;
+        let lock_end = EPOCH_START + 2 * EPOCH_LENGTH + 86_400;
+        assert!(contains_full_epoch(lock_start, lock_end, EPOCH_START, EPOCH_LENGTH));
+
+        // Exactly one epoch, edge to edge
+        assert!(contains_full_epoch(EPOCH_START, EPOCH_START + EPOCH_LENGTH, EPOCH_START, EPOCH_LENGTH));
+    }
+
+    #[test]
+    fn test_lock_partially_overlapping_epochs() {
+        // Starts mid-epoch 0, ends mid-epoch 1 - neither is fully covered
+        let lock_start = EPOCH_START + EPOCH_LENGTH / 2;
+        let lock_end = lock_start + EPOCH_LENGTH;
+        assert!(!contains_full_epoch(lock_start, lock_end, EPOCH_START, EPOCH_LENGTH));
+    }
+
+    #[test]
+    fn test_lock_missing_epochs() {
+        // Ends before the first epoch starts
+        assert!(!contains_full_epoch(EPOCH_START - 2 * EPOCH_LENGTH, EPOCH_START, EPOCH_START, EPOCH_LENGTH));
+        // Epochs disabled
+        assert!(!contains_full_epoch(EPOCH_START, EPOCH_START + 10 * EPOCH_LENGTH, EPOCH_START, 0));
+    }
+
+    #[test]
+    fn test_loyalty_bonus_threshold() {
+        assert_eq!(get_loyalty_bonus_bps(0), 0);
+        assert_eq!(get_loyalty_bonus_bps(LOYALTY_MIN_HOLD - 1), 0);
+        assert_eq!(get_loyalty_bonus_bps(LOYALTY_MIN_HOLD), LOYALTY_APY_BONUS_BPS);
+        assert_eq!(get_loyalty_bonus_bps(u64::MAX), LOYALTY_APY_BONUS_BPS);
+    }
+
+    #[test]
+    fn test_boost_multiplier_scales_then_caps() {
+        let staked = 100_000_000_000; // 100 DEVR
+        assert_eq!(get_boost_multiplier_bps(0, staked), 0);
+        // 10 DEVR boost on 100 DEVR -> rate x1.1
+        assert_eq!(get_boost_multiplier_bps(10_000_000_000, staked), 1_000);
+        assert_eq!(get_boost_multiplier_bps(staked / 2, staked), MAX_BOOST_MULTIPLIER_BPS);
+        assert_eq!(get_boost_multiplier_bps(u64::MAX, staked), MAX_BOOST_MULTIPLIER_BPS);
+        assert_eq!(get_boost_multiplier_bps(10_000_000_000, 0), 0);
+    }
+
+    #[test]
+    fn test_day_count_convention() {
+        assert_eq!(SECONDS_PER_YEAR, 31_536_000);
+        assert_eq!(SECONDS_IN_SEVEN_DAYS, 604_800);
+        assert_eq!(SECONDS_IN_THIRTY_DAYS, 2_592_000);
+        assert_eq!(SECONDS_IN_NINETY_DAYS, 7_776_000);
+
+        // Tier thresholds are whole days of the same day that makes up the year
+        for threshold in [SECONDS_IN_SEVEN_DAYS, SECONDS_IN_THIRTY_DAYS, SECONDS_IN_NINETY_DAYS] {
+            assert_eq!(threshold % SECONDS_PER_DAY, 0);
+        }
+    }
+
     #[test]
     fn test_apy_tier_1() {
-        let (num, denom) = get_apy_for_duration(SECONDS_IN_SEVEN_DAYS);
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_SEVEN_DAYS);
         assert_eq!(num, 5);
         assert_eq!(denom, 100);
     }
 
     #[test]
     fn test_apy_tier_2() {
-        let (num, denom) = get_apy_for_duration(SECONDS_IN_THIRTY_DAYS);
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS);
         assert_eq!(num, 10);
         assert_eq!(denom, 100);
     }
 
     #[test]
     fn test_apy_tier_3() {
-        let (num, denom) = get_apy_for_duration(SECONDS_IN_NINETY_DAYS);
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS);
         assert_eq!(num, 20);
         assert_eq!(denom, 100);
     }
@@ -72,11 +557,219 @@ mod tests {
     #[test]
     fn test_boundary_cases() {
         // Just below 30 days → 5%
-        let (num, _) = get_apy_for_duration(SECONDS_IN_THIRTY_DAYS - 1);
+        let (num, _) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS - 1);
         assert_eq!(num, 5);
 
         // Just below 90 days → 10%
-        let (num, _) = get_apy_for_duration(SECONDS_IN_NINETY_DAYS - 1);
+        let (num, _) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS - 1);
         assert_eq!(num, 10);
     }
+
+    #[test]
+    fn test_effective_apy_matches_tiers_at_thresholds() {
+        let tiers = default_apy_tiers();
+        for tier in tiers.iter() {
+            assert_eq!(
+                get_effective_apy(&tiers, tier.min_duration),
+                (tier.apy_numerator, tier.apy_denominator)
+            );
+        }
+        // Flat outside the tier range
+        assert_eq!(get_effective_apy(&tiers, 0), (5, 100));
+        assert_eq!(get_effective_apy(&tiers, MAX_LOCK_DURATION), (20, 100));
+    }
+
+    #[test]
+    fn test_effective_apy_interpolates_between_tiers() {
+        let tiers = default_apy_tiers();
+        // Halfway from 30 days (10%) to 90 days (20%)
+        assert_eq!(get_effective_apy(&tiers, 60 * SECONDS_PER_DAY), (150_000_000, APY_INTERPOLATION_PRECISION));
+        // Rates never decrease as the lock grows
+        let rate = |lock_duration: i64| {
+            let (num, den) = get_effective_apy(&tiers, lock_duration);
+            num as u128 * APY_INTERPOLATION_PRECISION as u128 / den as u128
+        };
+        for day in 7..=120 {
+            assert!(rate(day * SECONDS_PER_DAY) <= rate((day + 1) * SECONDS_PER_DAY));
+        }
+    }
+
+    #[test]
+    fn test_effective_apy_has_no_cliff_at_tier_marks() {
+        let tiers = default_apy_tiers();
+        let rate = |(num, den): (u64, u64)| num as f64 / den as f64;
+
+        for mark in [SECONDS_IN_THIRTY_DAYS, SECONDS_IN_NINETY_DAYS] {
+            let day_before = mark - SECONDS_PER_DAY;
+            // The step jumps a whole tier (5 / 10 percentage points) overnight...
+            let step_jump = rate(get_apy_for_duration(&tiers, mark)) - rate(get_apy_for_duration(&tiers, day_before));
+            assert!(step_jump >= 0.05);
+            // ...the curve moves by one day's slope (under half a point)
+            let curve_jump = rate(get_effective_apy(&tiers, mark)) - rate(get_effective_apy(&tiers, day_before));
+            assert!(curve_jump > 0.0 && curve_jump < 0.005);
+        }
+
+        // 89 vs 90 days: 19.83% vs 20%, instead of 10% vs 20%
+        let (num, den) = get_effective_apy(&tiers, SECONDS_IN_NINETY_DAYS - SECONDS_PER_DAY);
+        assert_eq!(num * 100 / den, 19);
+    }
+
+    #[test]
+    fn test_apy_for_lock_keeps_the_step_unless_interpolated() {
+        let tiers = default_apy_tiers();
+        let lock = SECONDS_IN_NINETY_DAYS - 1;
+        assert_eq!(get_apy_for_lock(&tiers, lock, false), get_apy_for_duration(&tiers, lock));
+        assert_eq!(get_apy_for_lock(&tiers, lock, true), get_effective_apy(&tiers, lock));
+    }
+
+    #[test]
+    fn test_apy_follows_the_given_schedule() {
+        let mut tiers = default_apy_tiers();
+        tiers[1] = ApyTier { min_duration: 60 * SECONDS_PER_DAY, apy_numerator: 12, apy_denominator: 100 };
+        tiers[2].apy_numerator = 30;
+
+        // Raised threshold: 30 days now stays on tier 1
+        assert_eq!(get_apy_for_duration(&tiers, SECONDS_IN_THIRTY_DAYS), (5, 100));
+        assert_eq!(get_apy_for_duration(&tiers, 60 * SECONDS_PER_DAY), (12, 100));
+        assert_eq!(get_apy_for_duration(&tiers, SECONDS_IN_NINETY_DAYS), (30, 100));
+        // Below every tier falls back to the lowest
+        assert_eq!(get_apy_for_duration(&tiers, 0), (5, 100));
+    }
+
+    #[test]
+    fn test_tier_for_duration_matches_apy() {
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), SECONDS_IN_SEVEN_DAYS), 1);
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS - 1), 1);
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS), 2);
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS - 1), 2);
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS), 3);
+    }
+
+    #[test]
+    fn test_delayed_inclusion_drops_tier() {
+        // User targets an end exactly 30 days out, but the tx lands 5 seconds late
+        let signed_at = 1_700_000_000;
+        let end_timestamp = signed_at + SECONDS_IN_THIRTY_DAYS;
+
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), end_timestamp - signed_at), 2);
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), end_timestamp - (signed_at + 5)), 1);
+    }
+
+    #[test]
+    fn test_convert_decimals_same() {
+        assert_eq!(convert_decimals(493_150_684_931, 9, 9), Some(493_150_684_931));
+    }
+
+    #[test]
+    fn test_convert_decimals_usdc_like() {
+        // 493.150684931 DEVR-denominated reward → 493.150684 in a 6-decimal token
+        assert_eq!(convert_decimals(493_150_684_931, 9, 6), Some(493_150_684));
+        // Dust below the reward token's precision rounds down to zero
+        assert_eq!(convert_decimals(999, 9, 6), Some(0));
+    }
+
+    #[test]
+    fn test_convert_decimals_scale_up() {
+        assert_eq!(convert_decimals(1_000_000, 6, 9), Some(1_000_000_000));
+        assert_eq!(convert_decimals(u64::MAX, 6, 9), None);
+    }
+
+    #[test]
+    fn test_to_ui_amount() {
+        assert_eq!(to_ui_amount(1_500_000_000, DEVR_DECIMALS), 1);
+        assert_eq!(to_ui_amount(1_500_000, 6), 1);
+        assert_eq!(to_ui_amount(42, 0), 42);
+        assert_eq!(to_ui_amount(999_999_999, 9), 0);
+        // u64 can't hold 10^20, so nothing is a whole token
+        assert_eq!(to_ui_amount(u64::MAX, 20), 0);
+    }
+
+    #[test]
+    fn test_max_delegation() {
+        assert_eq!(get_max_delegation(100_000_000_000, BASIS_POINTS), 100_000_000_000);
+        assert_eq!(get_max_delegation(100_000_000_000, 5_000), 50_000_000_000);
+        assert_eq!(get_max_delegation(0, 5_000), 0);
+        // No overflow on the largest balances
+        assert_eq!(get_max_delegation(u64::MAX, BASIS_POINTS), u64::MAX);
+    }
+
+    #[test]
+    fn test_delegation_amount_cap() {
+        let cap = 50_000_000_000;
+        assert!(within_delegation_amount_cap(cap - 1, cap));
+        assert!(within_delegation_amount_cap(cap, cap));
+        assert!(!within_delegation_amount_cap(cap + 1, cap));
+
+        // 0 disables the cap
+        assert!(within_delegation_amount_cap(u64::MAX, NO_DELEGATION_AMOUNT_CAP));
+    }
+
+    #[test]
+    fn test_delegation_cooldown_window() {
+        let revoked_at = 1_700_000_000;
+        let cooldown = SECONDS_PER_DAY;
+
+        // Blocked within the window, allowed from its end
+        assert!(is_delegation_cooling_down(revoked_at, cooldown, revoked_at));
+        assert!(is_delegation_cooling_down(revoked_at, cooldown, revoked_at + cooldown - 1));
+        assert!(!is_delegation_cooling_down(revoked_at, cooldown, revoked_at + cooldown));
+
+        // Disabled, or never revoked
+        assert!(!is_delegation_cooling_down(revoked_at, 0, revoked_at));
+        assert!(!is_delegation_cooling_down(0, cooldown, revoked_at));
+    }
+
+    #[test]
+    fn test_seconds_to_maturity_fresh_stake() {
+        let staked_at = 1_700_000_000;
+        let remaining = get_seconds_to_maturity(staked_at + SECONDS_IN_SEVEN_DAYS, staked_at);
+        assert_eq!(remaining, SECONDS_IN_SEVEN_DAYS);
+    }
+
+    #[test]
+    fn test_maturity_timestamp() {
+        assert_eq!(get_maturity_timestamp(1_700_000_000, SECONDS_IN_SEVEN_DAYS), Some(1_700_604_800));
+        assert_eq!(get_maturity_timestamp(i64::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_seconds_to_maturity_matured() {
+        let staked_at = 1_700_000_000;
+        let maturity = staked_at + SECONDS_IN_SEVEN_DAYS;
+
+        assert_eq!(get_seconds_to_maturity(maturity, maturity - 1), 1);
+        assert_eq!(get_seconds_to_maturity(maturity, maturity), 0);
+        // Long past maturity clamps to zero instead of going negative
+        assert_eq!(get_seconds_to_maturity(maturity, maturity + 86_400), 0);
+    }
+
+    #[test]
+    fn test_seconds_to_unlock_matches_gate() {
+        let maturity = 1_700_000_000 + SECONDS_IN_SEVEN_DAYS;
+        let gate_opens_at = maturity - UNSTAKE_SKEW_TOLERANCE;
+
+        assert_eq!(get_seconds_to_unlock(maturity, gate_opens_at - 1), 1);
+        assert!(!is_stake_matured(maturity, gate_opens_at - 1));
+        assert_eq!(get_seconds_to_unlock(maturity, gate_opens_at), 0);
+        assert!(is_stake_matured(maturity, gate_opens_at));
+        assert_eq!(get_seconds_to_unlock(maturity, maturity + 86_400), 0);
+    }
+
+    #[test]
+    fn test_claim_tier_non_staker() {
+        let (tier, multiplier) = get_claim_tier(&default_claim_tiers(), 0);
+        assert_eq!(tier, 0);
+        assert_eq!(multiplier, BASIS_POINTS);
+    }
+
+    #[test]
+    fn test_claim_tiers() {
+        let tiers = default_claim_tiers();
+
+        assert_eq!(get_claim_tier(&tiers, CLAIM_TIER_1_MIN_STAKED - 1), (0, BASIS_POINTS));
+        assert_eq!(get_claim_tier(&tiers, CLAIM_TIER_1_MIN_STAKED), (1, 12_500));
+        assert_eq!(get_claim_tier(&tiers, CLAIM_TIER_2_MIN_STAKED), (2, 15_000));
+        assert_eq!(get_claim_tier(&tiers, CLAIM_TIER_3_MIN_STAKED), (3, 20_000));
+        assert_eq!(get_claim_tier(&tiers, MAX_STAKE_AMOUNT), (3, 20_000));
+    }
 }
@@ -53,4 +53,241 @@ pub enum ErrorCode {
 
     #[msg("Invalid URI format! Must start with https:// or ipfs://")]
     InvalidUriFormat = 16,
+
+    #[msg("Token account still holds tokens! Transfer out the full balance before closing.")]
+    TokenAccountNotEmpty = 17,
+
+    #[msg("Only the program admin can perform this action!")]
+    Unauthorized = 18,
+
+    #[msg("This stake is frozen for review and cannot be unstaked until it is cleared.")]
+    StakeFrozen = 19,
+
+    #[msg("This stake is not frozen!")]
+    StakeNotFrozen = 20,
+
+    #[msg("This stake has already been frozen for review once and cannot be frozen again.")]
+    StakeAlreadyFrozen = 21,
+
+    #[msg("Transfer would leave less than the requested reserve in your account!")]
+    ReserveViolation = 22,
+
+    #[msg("Invalid claim tiers! Thresholds must increase and multipliers must be between 1x and 5x.")]
+    InvalidClaimTiers = 23,
+
+    #[msg("Lock duration no longer qualifies for the requested minimum APY tier!")]
+    TierNotMet = 24,
+
+    #[msg("Delegated allowance exceeds the maximum share of balance allowed!")]
+    TotalDelegationExceeded = 25,
+
+    #[msg("Delegation cap must be between 1 and 10000 basis points!")]
+    InvalidDelegationCap = 26,
+
+    #[msg("This stake has already been unstaked and closed!")]
+    StakeAlreadyClosed = 27,
+
+    #[msg("No stake exists at this index!")]
+    StakeNotFound = 28,
+
+    #[msg("Staking is paused by the circuit breaker! An admin must reset it.")]
+    StakingPaused = 29,
+
+    #[msg("Circuit breaker window must be at least one bucket long!")]
+    InvalidCircuitBreakerWindow = 30,
+
+    #[msg("Allowlist batch must be 1-10 users with one entry account per user!")]
+    InvalidAllowlistBatch = 31,
+
+    #[msg("Allowlist entry account does not match the user's PDA!")]
+    AllowlistAccountMismatch = 32,
+
+    #[msg("Batch transfer must have 1-10 recipients with one amount per recipient!")]
+    InvalidBatchTransfer = 33,
+
+    #[msg("The same recipient appears more than once in the batch!")]
+    DuplicateRecipient = 34,
+
+    #[msg("Feature flags contain unknown bits!")]
+    InvalidFeatureFlags = 35,
+
+    #[msg("Minting is disabled: the mint authority has been renounced.")]
+    MintingDisabled = 36,
+
+    #[msg("No liquidity providers have funded the reward vault yet!")]
+    NoLiquidityProviders = 37,
+
+    #[msg("No LP fees to claim!")]
+    NoLpFeesToClaim = 38,
+
+    #[msg("Withdraw amount must leave something to restake!")]
+    InvalidWithdrawAmount = 39,
+
+    #[msg("Rewards must be paid in the staking token to be restaked!")]
+    RewardTokenMismatch = 40,
+
+    #[msg("Stake is consistent with its counter and cannot be reclaimed as an orphan!")]
+    StakeNotOrphaned = 41,
+
+    #[msg("Epoch length must be zero (disabled) or positive, with a non-negative start!")]
+    InvalidEpochConfig = 42,
+
+    #[msg("Withdrawal exceeds the per-transaction unstake cap!")]
+    UnstakeTooLarge = 43,
+
+    #[msg("User has not opted in to auto-claim!")]
+    AutoClaimNotEnabled = 44,
+
+    #[msg("Stake has already matured!")]
+    StakeMatured = 45,
+
+    #[msg("Too many positions are already unbonding!")]
+    TooManyUnbonding = 46,
+
+    #[msg("Reward duration cap must be between the min and max lock durations!")]
+    InvalidRewardDurationCap = 47,
+
+    #[msg("New vault authority must differ from the current one!")]
+    InvalidVaultAuthority = 48,
+
+    #[msg("Reward accrual delay must be between 0 and 30 days!")]
+    InvalidAccrualDelay = 49,
+
+    #[msg("Claim nonce doesn't match the current claim state!")]
+    NonceMismatch = 50,
+
+    #[msg("Sweep would take the reward vault below its minimum reserve!")]
+    ReserveBreach = 51,
+
+    #[msg("Recipient is on the blocklist!")]
+    RecipientBlocked = 52,
+
+    #[msg("Insurance fee must be at most 1000 bps (10% of rewards)!")]
+    InvalidInsuranceFee = 53,
+
+    #[msg("Migration batch must be 1-10 stake accounts!")]
+    InvalidMigrationBatch = 54,
+
+    #[msg("Account is not one of the user's stake accounts in a known layout!")]
+    InvalidStakeMigration = 55,
+
+    #[msg("Delegate can't be re-approved this soon after a revoke!")]
+    DelegationCooldown = 56,
+
+    #[msg("Delegation cooldown must be between 0 and 7 days!")]
+    InvalidDelegationCooldown = 57,
+
+    #[msg("Payout destination must be a DEVR token account!")]
+    InvalidPayoutDestination = 58,
+
+    #[msg("Unstake must pay into the user's payout destination!")]
+    PayoutDestinationMismatch = 59,
+
+    #[msg("Boost vault has not been initialized!")]
+    BoostVaultNotInitialized = 60,
+
+    #[msg("Boosted positions must be withdrawn with unstake!")]
+    BoostedStakeNotRestakable = 61,
+
+    #[msg("Reward basis must be 0 (committed) or 1 (elapsed)!")]
+    InvalidRewardBasis = 62,
+
+    #[msg("Daily claim amount must be between 1 and 10,000 DEVR!")]
+    InvalidDailyClaimAmount = 63,
+
+    #[msg("Unstake amount exceeds the staked amount!")]
+    UnstakeAmountExceedsStake = 64,
+
+    #[msg("Early unstake penalty cannot exceed 50%!")]
+    PenaltyTooHigh = 65,
+
+    #[msg("Program is paused by the admin!")]
+    ProgramPaused = 66,
+
+    #[msg("APY tiers must have ascending thresholds and rates between 0% and 100%!")]
+    InvalidApyTiers = 67,
+
+    #[msg("Vault can't cover this stake's rewards on top of existing obligations!")]
+    InsufficientRewardReserve = 68,
+
+    #[msg("Extension must lengthen the lock!")]
+    InvalidLockExtension = 69,
+
+    #[msg("Streak bonus cap cannot exceed 100%!")]
+    StreakBonusTooHigh = 70,
+
+    #[msg("Only the pending admin can accept the handover!")]
+    NotPendingAdmin = 71,
+
+    #[msg("Lifetime staking reward cap reached!")]
+    RewardCapReached = 72,
+
+    #[msg("Claim cooldown must be between 1 second and 30 days!")]
+    InvalidClaimCooldown = 73,
+
+    #[msg("Lifetime claim cap reached for this wallet!")]
+    ClaimCapReached = 74,
+
+    #[msg("Too many open stake positions!")]
+    TooManyStakes = 75,
+
+    #[msg("Cannot refer yourself!")]
+    SelfReferral = 76,
+
+    #[msg("Referral and referrer token account must be passed together and match!")]
+    ReferralAccountMismatch = 77,
+
+    #[msg("Referral bonus cannot exceed 1,000 DEVR!")]
+    InvalidReferralBonus = 78,
+
+    #[msg("Slash amount must be between 1 and the staked amount!")]
+    InvalidSlashAmount = 79,
+
+    #[msg("The same stake account was passed more than once!")]
+    DuplicateStakeAccount = 80,
+
+    #[msg("Claim history cannot be closed while a lifetime claim cap is set!")]
+    ClaimHistoryLocked = 81,
+
+    #[msg("Emergency withdrawals require the program to be paused!")]
+    ProgramNotPaused = 82,
+
+    #[msg("A position already exists at this stake index - re-fetch the stake counter and retry!")]
+    StakeIndexCollision = 83,
+
+    #[msg("A stake's lock can be extended but never shortened!")]
+    LockCannotShorten = 84,
+
+    #[msg("Reward vault and reward token account must match the reward config!")]
+    RewardAccountMismatch = 85,
+
+    #[msg("Transfer limits must satisfy 0 < min_transfer <= max_transfer!")]
+    InvalidTransferLimits = 86,
+
+    #[msg("Wallet is not on the faucet allowlist!")]
+    NotAllowlisted = 87,
+
+    #[msg("This stake has already been withdrawn!")]
+    AlreadyWithdrawn = 88,
+
+    #[msg("Source and destination token accounts must differ!")]
+    SelfTransfer = 89,
+
+    #[msg("Approval exceeds the platform's maximum delegation amount!")]
+    DelegationTooLarge = 90,
+
+    #[msg("Call request_unstake and wait out the unstake delay first!")]
+    UnstakeNotRequested = 91,
+
+    #[msg("The unstake delay since request_unstake has not passed yet!")]
+    UnstakeDelayActive = 92,
+
+    #[msg("Unstake has already been requested for this stake!")]
+    UnstakeAlreadyRequested = 93,
+
+    #[msg("Unstake delay must be between 0 and 7 days!")]
+    InvalidUnstakeDelay = 94,
+
+    #[msg("Account is not in a known older layout!")]
+    InvalidAccountMigration = 95,
 }
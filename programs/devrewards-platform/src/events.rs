@@ -1,3 +1,4 @@
+use crate::state::{ApyTier, ClaimTier};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -8,6 +9,8 @@ pub struct StakeEvent {
     pub lock_duration: i64,
     pub apy_numerator: u64,
     pub apy_denominator: u64,
+    pub loyalty_bonus_bps: u64,
+    pub epoch_bonus_bps: u64,
     pub timestamp: i64,
 }
 
@@ -16,10 +19,704 @@ pub struct UnstakeEvent {
     pub user: Pubkey,
     pub stake_index: u64,
     pub principal: u64,
-    pub rewards: u64,
+    pub rewards: u64, // In DEVR; split-mint payouts transfer the reward-token equivalent
     pub total_withdrawn: u64,
     pub lock_duration: i64,
+    pub reward_duration: i64, // Seconds actually rewarded (after freeze and reward cap)
     pub apy_numerator: u64,
     pub apy_denominator: u64,
+    pub destination: Pubkey, // Token account the withdrawal was paid into
+    pub boost_returned: u64, // Boost deposit handed back alongside principal and rewards
+    pub remaining_principal: u64, // Still staked after a partial unstake (0 once closed)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParameterPreviewEvent {
+    pub sample_amount: u64,
+    pub tier_1_duration: i64,
+    pub tier_1_rewards: u64,
+    pub tier_2_duration: i64,
+    pub tier_2_rewards: u64,
+    pub tier_3_duration: i64,
+    pub tier_3_rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeFrozenEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub admin: Pubkey,
+    pub frozen_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeUnfrozenEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub claim_tier: u8,
+    pub current_streak: u32,
+    pub streak_bonus_bps: u64, // Extra share of the claim earned by the streak
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimTiersUpdatedEvent {
+    pub admin: Pubkey,
+    pub claim_tiers: [ClaimTier; 3],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaturityEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub seconds_remaining: i64,
+    pub is_matured: bool,
+}
+
+#[event]
+pub struct DelegationCapUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_total_delegation_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeCapUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_unstake_per_tx: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTrippedEvent {
+    pub window_volume: u64,
+    pub threshold: u64,
+    pub window: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerResetEvent {
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerUpdatedEvent {
+    pub admin: Pubkey,
+    pub window: i64,
+    pub threshold: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchAllowlistEvent {
+    pub admin: Pubkey,
+    pub requested: u64,
+    pub added: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeatureFlagsUpdatedEvent {
+    pub admin: Pubkey,
+    pub old_flags: u64,
+    pub new_flags: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpContributionEvent {
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub total_contributed: u64,
+    pub pool_total_contributed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpFeesDepositedEvent {
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub acc_fees_per_share: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LpRewardsClaimedEvent {
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ApyHistoryEvent {
+    pub index: u8,
+    pub total_recorded: u64,
+    pub tiers: [ApyTier; 3],
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RestakeEvent {
+    pub user: Pubkey,
+    pub old_stake_index: u64,
+    pub new_stake_index: u64,
+    pub restaked_principal: u64,
+    pub restaked_rewards: u64,
+    pub lock_duration: i64,
+    pub apy_numerator: u64,
+    pub apy_denominator: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrphanReclaimedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub recorded_index: u64,
+    pub counter_stake_count: u64,
+    pub principal_returned: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EpochConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub epoch_start: i64,
+    pub epoch_length: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsCompoundedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub new_stake_index: u64,
+    pub amount: u64,
+    pub previous_compound_at: i64,
+    pub total_compounded: u64,
+    pub lock_duration: i64,
+    pub apy_numerator: u64,
+    pub apy_denominator: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoClaimToggledEvent {
+    pub user: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutoClaimProcessedEvent {
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub stake_index: u64,
+    pub days: u64,
+    pub amount_staked: u64,
+    pub keeper_fee: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeValidationEvent {
+    pub amount: u64,
+    pub lock_duration: i64,
+    pub checks_run: u32,
+    pub failed_checks: u32, // STAKE_CHECK_* bits that failed
+    pub is_valid: bool,     // No blocking check failed (liquidity is advisory)
+    pub projected_rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardDurationCapUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_reward_eligible_duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultAuthorityTransferredEvent {
+    pub admin: Pubkey,
+    pub vault: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardAccrualDelayUpdatedEvent {
+    pub admin: Pubkey,
+    pub reward_accrual_delay: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MinRewardReserveUpdatedEvent {
+    pub admin: Pubkey,
+    pub min_reward_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExcessRewardsSweptEvent {
+    pub admin: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub min_reward_reserve: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddToStakeEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub amount: u64,
+    pub staked_amount: u64,
+    pub remaining_lock: i64,
+    pub reward_principal: u64, // Principal earning the full lock after blending tranches
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExtendStakeEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub previous_lock_duration: i64,
+    pub lock_duration: i64,
+    pub maturity_timestamp: i64,
+    pub apy_numerator: u64,
+    pub apy_denominator: u64,
+    pub reward_liability: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressBlockedEvent {
+    pub admin: Pubkey,
+    pub address: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AddressUnblockedEvent {
+    pub admin: Pubkey,
+    pub address: Pubkey,
+    pub blocked_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFeeUpdatedEvent {
+    pub admin: Pubkey,
+    pub insurance_fee_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultFundedEvent {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,        // After the deposit
+    pub total_rewards_funded: u64, // Lifetime, across every funder
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFundDrawnEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub shortfall: u64, // Part of the payout the reward vault couldn't cover
+    pub fund_balance: u64, // Fund token balance after the draw
+    pub timestamp: i64,
+}
+
+/// One event type for every admin config change, so monitoring can alert on all of
+/// them. `field` is a CONFIG_FIELD_* code; i64 values are their two's-complement
+/// bits (`as u64`), bools are 0/1. Setters still emit their own detailed event too.
+#[event]
+pub struct ConfigChangedEvent {
+    pub field: u8,
+    pub old_value: u64,
+    pub new_value: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// ConfigChangedEvent variant for Pubkey-valued settings
+#[event]
+pub struct ConfigPubkeyChangedEvent {
+    pub field: u8,
+    pub old_value: Pubkey,
+    pub new_value: Pubkey,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+pub fn emit_config_changed(field: u8, old_value: u64, new_value: u64, admin: Pubkey, timestamp: i64) {
+    emit!(ConfigChangedEvent {
+        field,
+        old_value,
+        new_value,
+        admin,
+        timestamp,
+    });
+}
+
+#[event]
+pub struct StakesMigratedEvent {
+    pub user: Pubkey,
+    pub requested: u64,
+    pub migrated: u64, // Legacy positions rewritten; the rest were already current
+    pub counter_grown: bool, // StakeCounter was grown to its current layout
+    pub user_claim_grown: bool,
+    pub user_stake_stats_grown: bool,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ApyCurvePoint {
+    pub lock_duration: i64,
+    pub apy_numerator: u64,
+    pub apy_denominator: u64,
+    pub projected_rewards: u64,
+}
+
+#[event]
+pub struct ApyCurveEvent {
+    pub amount: u64,
+    pub points: Vec<ApyCurvePoint>, // One per APY_CURVE_DURATIONS entry, shortest first
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationCooldownUpdatedEvent {
+    pub admin: Pubkey,
+    pub delegation_cooldown: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutDestinationSetEvent {
+    pub user: Pubkey,
+    pub old_destination: Pubkey, // Default pubkey if none was set
+    pub new_destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeBoostedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub boost_amount: u64,
+    pub boost_multiplier_bps: u64, // Extra share of the base APY, e.g. 1_000 = rate x1.1
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardBasisUpdatedEvent {
+    pub admin: Pubkey,
+    pub reward_basis: u8,
+    pub timestamp: i64,
+}
+
+/// Every TokenConfig field, in account order, plus values derived at emit time
+#[event]
+pub struct ConfigDumpEvent {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub mint_authority: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub vault: Pubkey,
+    pub vault_authority: Pubkey,
+    pub daily_claim_amount: u64,
+    pub config_bump: u8,
+    pub mint_authority_bump: u8,
+    pub mint_bump: u8,
+    pub vault_bump: u8,
+    pub vault_authority_bump: u8,
+    pub global_stats_bump: u8,
+    pub claim_tiers: [ClaimTier; 3],
+    pub max_total_delegation_bps: u64,
+    pub reward_decimals: u8,
+    pub circuit_breaker_window: i64,
+    pub circuit_breaker_threshold: u64,
+    pub epoch_start: i64,
+    pub epoch_length: i64,
+    pub max_unstake_per_tx: u64,
+    pub max_total_rewards: u64,
+    pub max_reward_eligible_duration: i64,
+    pub reward_accrual_delay: i64,
+    pub min_reward_reserve: u64,
+    pub insurance_fee_bps: u64,
+    pub delegation_cooldown: i64,
+    pub reward_basis: u8,
+    pub early_unstake_penalty_bps: u64,
+    pub paused: bool,
+    pub max_streak_bonus_bps: u64,
+    pub claim_cooldown_seconds: i64,
+    pub max_lifetime_claim: u64,
+    pub referral_bonus: u64,
+    pub min_transfer: u64,
+    pub max_transfer: u64,
+    pub allowlist_root: [u8; 32],
+    pub max_delegation_amount: u64,
+    pub unstake_delay: i64,
+    pub vault_balance: u64, // Current vault token balance
+    pub feature_flags: u64, // Effective flags (defaults if the PDA doesn't exist)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub old_daily_claim_amount: u64,
+    pub new_daily_claim_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarlyUnstakePenaltyUpdatedEvent {
+    pub admin: Pubkey,
+    pub early_unstake_penalty_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyUnstakeEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub principal: u64,
+    pub penalty: u64, // Principal kept in the vault
+    pub returned: u64, // Principal paid back (rewards are forfeited)
+    pub penalty_bps: u64,
+    pub seconds_early: i64, // Time left on the lock at exit
+    pub boost_returned: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProgramPauseUpdatedEvent {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferEvent {
+    pub from: Pubkey, // Source token account
+    pub to: Pubkey,   // Destination token account
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegatedTransferEvent {
+    pub from: Pubkey, // Source token account
+    pub to: Pubkey,   // Destination token account
+    pub delegate: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateApprovedEvent {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64, // Replaces any earlier allowance on the account
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegateRevokedEvent {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxStreakBonusUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_streak_bonus_bps: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposedEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey, // Default pubkey = proposal cancelled
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferredEvent {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardCapUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_total_rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimCooldownUpdatedEvent {
+    pub admin: Pubkey,
+    pub claim_cooldown_seconds: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LifetimeClaimCapUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_lifetime_claim: u64, // 0 = unlimited
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MetadataUpdatedEvent {
+    pub admin: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralEvent {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub bonus: u64,
+    pub total_referred: u64,
+    pub referral_rewards: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralBonusUpdatedEvent {
+    pub admin: Pubkey,
+    pub referral_bonus: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SlashEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub slashed_amount: u64,
+    pub remaining_principal: u64,
+    pub reason_code: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserClaimClosedEvent {
+    pub user: Pubkey,
+    pub total_claimed: u64, // Lifetime total dropped with the account
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawEvent {
+    pub admin: Pubkey,
+    pub recipient: Pubkey, // Token account that received the funds
+    pub amount: u64,
+    pub vault_balance: u64, // Left in the vault afterwards
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardConfigUpdatedEvent {
+    pub admin: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub reward_decimals: u8,
+    pub split: bool, // Rewards paid in a token other than the staked one
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TransferLimitsUpdatedEvent {
+    pub admin: Pubkey,
+    pub min_transfer: u64,
+    pub max_transfer: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowlistRootUpdatedEvent {
+    pub admin: Pubkey,
+    pub previous_root: [u8; 32],
+    pub allowlist_root: [u8; 32], // All zero = faucet open to everyone
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MaxDelegationAmountUpdatedEvent {
+    pub admin: Pubkey,
+    pub max_delegation_amount: u64, // 0 = no cap
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StatsSnapshotEvent {
+    pub total_staked: u64,
+    pub total_stakes: u64,
+    pub total_rewards_paid: u64,
+    pub vault_balance: u64, // Vault token balance at the snapshot
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequestedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub unstake_delay: i64,
+    pub withdrawable_at: i64, // Earliest time unstake succeeds
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeDelayUpdatedEvent {
+    pub admin: Pubkey,
+    pub unstake_delay: i64, // 0 = one-step unstake
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GlobalAccountsMigratedEvent {
+    pub admin: Pubkey,
+    pub config_len: u64, // Size before the migration
+    pub global_stats_len: u64,
+    pub config_grown: bool,
+    pub global_stats_grown: bool,
     pub timestamp: i64,
 }
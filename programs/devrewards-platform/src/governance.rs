@@ -0,0 +1,59 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::state::StakeAccount;
+use anchor_lang::prelude::*;
+
+/// Governance weight of staked DEVR: principal scaled by how long it is locked
+pub struct VotePower;
+
+impl VotePower {
+    /// Weight (bps) for a lock duration, by the APY tier the lock reaches
+    pub fn duration_weight_bps(lock_duration: i64) -> u64 {
+        let tier = get_tier_for_duration(&default_apy_tiers(), lock_duration);
+        VOTE_WEIGHT_BPS[tier as usize - 1]
+    }
+
+    /// Voting power of a single position
+    pub fn of(stake_account: &StakeAccount) -> Result<u64> {
+        let power = stake_account.staked_amount as u128
+            * Self::duration_weight_bps(stake_account.lock_duration) as u128
+            / BASIS_POINTS as u128;
+        u64::try_from(power).map_err(|_| error!(ErrorCode::ArithmeticOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_stake_account;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+
+    fn position(lock_duration: i64) -> StakeAccount {
+        test_stake_account(1_700_000_000, lock_duration)
+    }
+
+    #[test]
+    fn test_weight_follows_apy_tier_boundaries() {
+        assert_eq!(VotePower::duration_weight_bps(SECONDS_IN_SEVEN_DAYS), 10_000);
+        assert_eq!(VotePower::duration_weight_bps(SECONDS_IN_THIRTY_DAYS - 1), 10_000);
+        assert_eq!(VotePower::duration_weight_bps(SECONDS_IN_THIRTY_DAYS), 15_000);
+        assert_eq!(VotePower::duration_weight_bps(SECONDS_IN_NINETY_DAYS - 1), 15_000);
+        assert_eq!(VotePower::duration_weight_bps(SECONDS_IN_NINETY_DAYS), 20_000);
+        assert_eq!(VotePower::duration_weight_bps(MAX_LOCK_DURATION), 20_000);
+    }
+
+    #[test]
+    fn test_power_scales_principal() {
+        assert_eq!(VotePower::of(&position(SECONDS_IN_SEVEN_DAYS)).unwrap(), PRINCIPAL);
+        assert_eq!(VotePower::of(&position(SECONDS_IN_THIRTY_DAYS)).unwrap(), PRINCIPAL * 3 / 2);
+        assert_eq!(VotePower::of(&position(SECONDS_IN_NINETY_DAYS)).unwrap(), PRINCIPAL * 2);
+    }
+
+    #[test]
+    fn test_power_overflow_is_an_error() {
+        let mut whale = position(SECONDS_IN_NINETY_DAYS);
+        whale.staked_amount = u64::MAX;
+        assert!(VotePower::of(&whale).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use super::unstake::{reserve_position_rewards, RewardWindow};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::AddToStakeEvent;
+use crate::state::{GlobalStats, StakeAccount, TokenConfig, UserStakeStats, UserStats};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct AddToStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        constraint = stake_account.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, token::mint = config.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds a tranche to a still-locked position and books it in the global and
+/// per-user totals. Shared with the auto-claim keeper path; both then re-reserve
+/// the position's rewards for the larger principal.
+pub fn top_up_position(
+    stake_account: &mut StakeAccount,
+    global_stats: &mut GlobalStats,
+    user_stake_stats: &mut UserStakeStats,
+    user_stats: &mut UserStats,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    stake_account.add_tranche(amount, now)?;
+
+    global_stats.total_staked = global_stats
+        .total_staked
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    global_stats.update_peak();
+
+    user_stake_stats.total_active_staked = user_stake_stats
+        .total_active_staked
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    user_stats.record_deposit(amount)
+}
+
+/// Top up a locked position. Maturity and tier stay the same; the new tranche
+/// earns only over the lock left (see `StakeAccount::reward_principal`).
+pub fn handler(ctx: Context<AddToStake>, stake_count: u64, amount: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+    require!(amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let stake_account = &ctx.accounts.stake_account;
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+    require!(
+        !is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StakeMatured
+    );
+    let new_staked_amount = stake_account
+        .staked_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        new_staked_amount <= MAX_STAKE_AMOUNT,
+        ErrorCode::AmountTooLarge
+    );
+    let remaining_lock = stake_account.maturity_timestamp - current_time;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    ctx.accounts.vault.reload()?;
+    let stake_account = &mut ctx.accounts.stake_account;
+    top_up_position(
+        stake_account,
+        &mut ctx.accounts.global_stats,
+        &mut ctx.accounts.user_stake_stats,
+        &mut ctx.accounts.user_stats,
+        amount,
+        current_time,
+    )?;
+    // The tranche earns too, so the vault must back its rewards as well
+    reserve_position_rewards(
+        stake_account,
+        &mut ctx.accounts.global_stats,
+        RewardWindow::from_config(&ctx.accounts.config),
+        ctx.accounts.vault.amount,
+    )?;
+
+    emit!(AddToStakeEvent {
+        user,
+        stake_index: stake_count,
+        amount,
+        staked_amount: stake_account.staked_amount,
+        remaining_lock,
+        reward_principal: stake_account.reward_principal(),
+        timestamp: current_time,
+    });
+
+    msg!("✅ Added to stake!");
+    msg!("Stake index: #{}", stake_count);
+    msg!("Added: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Total staked: {} DEVR", to_ui_amount(stake_account.staked_amount, DEVR_DECIMALS));
+    msg!("Lock left: {} days", remaining_lock / 86400);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::unstake::{max_position_rewards, position_rewards};
+    use crate::state::{test_global_stats, test_stake_account};
+
+    const DEVR: u64 = 1_000_000_000;
+    const START: i64 = 1_700_000_000;
+    const NO_CAP: RewardWindow = RewardWindow {
+        accrual_delay: 0,
+        max_duration: MAX_LOCK_DURATION,
+        basis: REWARD_BASIS_COMMITTED,
+    };
+
+    fn position(staked_amount: u64, lock_duration: i64) -> StakeAccount {
+        StakeAccount { staked_amount, ..test_stake_account(START, lock_duration) }
+    }
+
+    fn matured_rewards(stake_account: &StakeAccount) -> u64 {
+        position_rewards(stake_account, stake_account.maturity_timestamp, NO_CAP).unwrap().0
+    }
+
+    #[test]
+    fn test_tranches_earn_less_than_single_stake() {
+        let single = position(1_000 * DEVR, SECONDS_IN_NINETY_DAYS);
+
+        // Same 1,000 DEVR, but half of it added halfway through the lock
+        let mut tranched = position(500 * DEVR, SECONDS_IN_NINETY_DAYS);
+        tranched
+            .add_tranche(500 * DEVR, START + SECONDS_IN_NINETY_DAYS / 2)
+            .unwrap();
+        assert_eq!(tranched.staked_amount, single.staked_amount);
+        assert_eq!(tranched.maturity_timestamp, single.maturity_timestamp);
+
+        // 500 over the whole lock + 500 over half of it = 750 earning the full lock
+        assert_eq!(tranched.reward_principal(), 750 * DEVR);
+        let single_rewards = matured_rewards(&single);
+        let tranched_rewards = matured_rewards(&tranched);
+        assert!(tranched_rewards < single_rewards);
+        assert!(tranched_rewards.abs_diff(single_rewards * 3 / 4) <= 1);
+    }
+
+    #[test]
+    fn test_tranche_at_stake_time_matches_single_stake() {
+        let single = position(1_000 * DEVR, SECONDS_IN_THIRTY_DAYS);
+
+        let mut tranched = position(400 * DEVR, SECONDS_IN_THIRTY_DAYS);
+        tranched.add_tranche(600 * DEVR, START).unwrap();
+
+        assert_eq!(tranched.reward_principal(), single.reward_principal());
+        assert_eq!(matured_rewards(&tranched), matured_rewards(&single));
+    }
+
+    #[test]
+    fn test_cannot_add_to_matured_position() {
+        let mut stake_account = position(100 * DEVR, SECONDS_IN_THIRTY_DAYS);
+        let maturity = stake_account.maturity_timestamp;
+
+        assert!(stake_account.add_tranche(DEVR, maturity).is_err());
+        assert_eq!(stake_account.staked_amount, 100 * DEVR);
+        assert_eq!(stake_account.weighted_staked_seconds, 0);
+    }
+
+    #[test]
+    fn test_top_up_reserves_only_the_tranche_rewards() {
+        let mut stake_account = position(500 * DEVR, SECONDS_IN_NINETY_DAYS);
+        let reserved = max_position_rewards(&stake_account, NO_CAP).unwrap();
+        stake_account.reward_liability = reserved;
+        let mut global_stats = GlobalStats {
+            total_reward_liability: reserved,
+            ..test_global_stats(500 * DEVR)
+        };
+        let user = Pubkey::new_unique();
+        let mut user_stake_stats = UserStakeStats {
+            version: CURRENT_ACCOUNT_VERSION,
+            user,
+            total_active_staked: 500 * DEVR,
+            bump: 255,
+        };
+        let mut user_stats = UserStats {
+            version: CURRENT_ACCOUNT_VERSION,
+            user,
+            total_ever_staked: 500 * DEVR,
+            total_rewards_earned: 0,
+            stakes_opened: 1,
+            stakes_closed: 0,
+            bump: 255,
+        };
+
+        let now = START + SECONDS_IN_NINETY_DAYS / 2;
+        top_up_position(
+            &mut stake_account,
+            &mut global_stats,
+            &mut user_stake_stats,
+            &mut user_stats,
+            500 * DEVR,
+            now,
+        )
+        .unwrap();
+        assert_eq!(global_stats.total_staked, 1_000 * DEVR);
+        assert_eq!(user_stake_stats.total_active_staked, 1_000 * DEVR);
+        assert_eq!(user_stats.total_ever_staked, 1_000 * DEVR);
+        assert_eq!(user_stats.stakes_opened, 1);
+
+        // A vault holding only the principal and the old reservation can't back the tranche
+        let mut short = global_stats.clone();
+        let mut unbacked = stake_account.clone();
+        assert_eq!(
+            reserve_position_rewards(&mut unbacked, &mut short, NO_CAP, 1_000 * DEVR + reserved)
+                .unwrap_err(),
+            error!(ErrorCode::InsufficientRewardReserve)
+        );
+
+        // With enough in the vault only the tranche's share is added: it earns over half the lock
+        let liability =
+            reserve_position_rewards(&mut stake_account, &mut global_stats, NO_CAP, u64::MAX).unwrap();
+        assert_eq!(liability, matured_rewards(&stake_account));
+        assert_eq!(stake_account.reward_liability, liability);
+        assert_eq!(global_stats.total_reward_liability, liability);
+        assert!((liability - reserved).abs_diff(reserved / 2) <= 1);
+    }
+}
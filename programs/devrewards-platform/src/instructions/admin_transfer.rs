@@ -0,0 +1,84 @@
+use crate::constants::CONFIG_FIELD_ADMIN;
+use crate::error::ErrorCode;
+use crate::events::{AdminTransferProposedEvent, AdminTransferredEvent, ConfigPubkeyChangedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        constraint = config.pending_admin == new_admin.key() @ ErrorCode::NotPendingAdmin
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Signing proves the proposed key is actually controlled by someone
+    pub new_admin: Signer<'info>,
+}
+
+/// Step one of an admin handover: proposes `new_admin`, who must then call
+/// `accept_admin`. The current admin stays in charge until then. Proposing the
+/// default pubkey cancels a pending handover.
+pub fn transfer_handler(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = new_admin;
+
+    emit!(AdminTransferProposedEvent {
+        admin: ctx.accounts.admin.key(),
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    if new_admin == Pubkey::default() {
+        msg!("✅ Admin handover cancelled");
+    } else {
+        msg!("✅ Admin handover proposed!");
+        msg!("Pending admin: {}", new_admin);
+    }
+
+    Ok(())
+}
+
+/// Step two: the pending admin signs to take over
+pub fn accept_handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_admin = config.admin;
+    let new_admin = ctx.accounts.new_admin.key();
+    config.admin = new_admin;
+    config.pending_admin = Pubkey::default();
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(AdminTransferredEvent {
+        old_admin,
+        new_admin,
+        timestamp,
+    });
+    emit!(ConfigPubkeyChangedEvent {
+        field: CONFIG_FIELD_ADMIN,
+        old_value: old_admin,
+        new_value: new_admin,
+        admin: new_admin,
+        timestamp,
+    });
+
+    msg!("✅ Admin transferred!");
+    msg!("Old admin: {}", old_admin);
+    msg!("New admin: {}", new_admin);
+
+    Ok(())
+}
@@ -1,15 +1,35 @@
+use crate::constants::{
+    get_max_delegation, is_delegation_cooling_down, to_ui_amount, within_delegation_amount_cap,
+    DEVR_DECIMALS,
+};
 use crate::error::ErrorCode;
+use crate::events::DelegateApprovedEvent;
+use crate::state::{DelegationRecord, TokenConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Approve, Token, TokenAccount};
 
 #[derive(Accounts)]
 pub struct ApproveDelegate<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
     #[account(mut)]
     pub token_account: Account<'info, TokenAccount>,
 
     /// CHECK: Unchecked
     pub delegate: UncheckedAccount<'info>,
 
+    /// CHECK: Delegation record PDA, validated by seeds. Read manually since an
+    /// account that was never revoked has no record.
+    #[account(
+        seeds = [b"delegation-record", token_account.key().as_ref()],
+        bump
+    )]
+    pub delegation_record: UncheckedAccount<'info>,
+
     pub owner: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -23,6 +43,28 @@ pub fn handler(ctx: Context<ApproveDelegate>, amount: u64) -> Result<()> {
         ErrorCode::InsufficientBalance
     );
 
+    // SPL accounts hold a single delegate and approve replaces the previous
+    // allowance, so this amount is the account's total outstanding delegation
+    let max_delegation = get_max_delegation(
+        ctx.accounts.token_account.amount,
+        ctx.accounts.config.max_total_delegation_bps,
+    );
+    require!(amount <= max_delegation, ErrorCode::TotalDelegationExceeded);
+
+    // Flat ceiling on top of the bps cap, bounding what a compromised delegate can move
+    require!(
+        within_delegation_amount_cap(amount, ctx.accounts.config.max_delegation_amount),
+        ErrorCode::DelegationTooLarge
+    );
+
+    // Guards against revoke-then-approve phishing flows
+    let now = Clock::get()?.unix_timestamp;
+    let last_revoked_at = DelegationRecord::load_last_revoked_at(&ctx.accounts.delegation_record)?;
+    require!(
+        !is_delegation_cooling_down(last_revoked_at, ctx.accounts.config.delegation_cooldown, now),
+        ErrorCode::DelegationCooldown
+    );
+
     let cpi_accounts = Approve {
         to: ctx.accounts.token_account.to_account_info(),
         delegate: ctx.accounts.delegate.to_account_info(),
@@ -34,9 +76,17 @@ pub fn handler(ctx: Context<ApproveDelegate>, amount: u64) -> Result<()> {
 
     token::approve(cpi_ctx, amount)?;
 
+    emit!(DelegateApprovedEvent {
+        owner: ctx.accounts.owner.key(),
+        delegate: ctx.accounts.delegate.key(),
+        token_account: ctx.accounts.token_account.key(),
+        amount,
+        timestamp: now,
+    });
+
     msg!("✅ Delegation approved!");
     msg!("Delegate: {}", ctx.accounts.delegate.key());
-    msg!("Approved amount: {} tokens", amount / 1_000_000_000);
+    msg!("Approved amount: {} tokens", to_ui_amount(amount, DEVR_DECIMALS));
     msg!("Token account: {}", ctx.accounts.token_account.key());
     Ok(())
 }
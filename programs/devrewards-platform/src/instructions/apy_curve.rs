@@ -0,0 +1,103 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{ApyCurveEvent, ApyCurvePoint};
+use crate::instructions::unstake::compute_rewards;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApyCurve<'info> {
+    // Read-only: only used to make sure the program has been initialized
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+}
+
+/// APY and committed-lock rewards for `amount` at each sampled duration
+pub fn apy_curve_points(amount: u64) -> Result<Vec<ApyCurvePoint>> {
+    APY_CURVE_DURATIONS
+        .iter()
+        .map(|&lock_duration| {
+            let (apy_numerator, apy_denominator) = get_apy_for_duration(&default_apy_tiers(), lock_duration);
+            Ok(ApyCurvePoint {
+                lock_duration,
+                apy_numerator,
+                apy_denominator,
+                projected_rewards: compute_rewards(
+                    amount,
+                    apy_numerator,
+                    apy_denominator,
+                    lock_duration,
+                )?,
+            })
+        })
+        .collect()
+}
+
+pub fn handler(_ctx: Context<ApyCurve>, amount: u64) -> Result<()> {
+    // Same bounds a real stake would have to meet
+    require!(amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
+    require!(amount <= MAX_STAKE_AMOUNT, ErrorCode::AmountTooLarge);
+
+    let points = apy_curve_points(amount)?;
+
+    msg!("📈 APY curve for {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    for point in points.iter() {
+        msg!(
+            "{} days: {}/{} APY, {} DEVR",
+            point.lock_duration / SECONDS_PER_DAY,
+            point.apy_numerator,
+            point.apy_denominator,
+            to_ui_amount(point.projected_rewards, DEVR_DECIMALS)
+        );
+    }
+
+    emit!(ApyCurveEvent {
+        amount,
+        points,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMOUNT: u64 = 1_000_000_000_000; // 1,000 DEVR
+
+    #[test]
+    fn test_curve_matches_tiers_and_reward_formula() {
+        let points = apy_curve_points(AMOUNT).unwrap();
+        assert_eq!(points.len(), APY_CURVE_DURATIONS.len());
+
+        for (point, &lock_duration) in points.iter().zip(APY_CURVE_DURATIONS.iter()) {
+            let (apy_numerator, apy_denominator) = get_apy_for_duration(&default_apy_tiers(), lock_duration);
+            assert_eq!(point.lock_duration, lock_duration);
+            assert_eq!(point.apy_numerator, apy_numerator);
+            assert_eq!(point.apy_denominator, apy_denominator);
+
+            let expected =
+                (AMOUNT * apy_numerator / apy_denominator) * lock_duration as u64 / SECONDS_PER_YEAR;
+            assert_eq!(point.projected_rewards, expected);
+        }
+    }
+
+    #[test]
+    fn test_curve_is_monotonic() {
+        let points = apy_curve_points(AMOUNT).unwrap();
+
+        // Longer locks never earn a lower rate or less in total
+        for pair in points.windows(2) {
+            assert!(pair[1].lock_duration > pair[0].lock_duration);
+            assert!(pair[1].apy_numerator >= pair[0].apy_numerator);
+            assert!(pair[1].projected_rewards > pair[0].projected_rewards);
+        }
+
+        // 1,000 DEVR for a full year at 20% earns 200 DEVR
+        assert_eq!(points[4].projected_rewards, 200_000_000_000);
+    }
+}
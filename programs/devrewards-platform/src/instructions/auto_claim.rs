@@ -0,0 +1,381 @@
+use super::add_to_stake::top_up_position;
+use super::claim_tokens::{check_allowlisted, is_minting_enabled};
+use super::unstake::{reserve_position_rewards, RewardWindow};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{AutoClaimProcessedEvent, AutoClaimToggledEvent};
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, StakeAccount, TokenConfig, UserClaim,
+    UserStakeStats, UserStats,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetAutoClaim<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserClaim::LEN,
+        seeds = [b"user-claim", user.key().as_ref()],
+        bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, stake_count: u64)]
+pub struct ProcessAutoClaims<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user-claim", user.as_ref()],
+        bump = user_claim.bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    // Position the accrued claims are added to
+    #[account(
+        mut,
+        seeds = [b"stake", user.as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        constraint = stake_account.user == user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA used as the mint authority, validated by seeds and bump
+    #[account(
+        seeds = [b"mint-authority"],
+        bump = config.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    // Staked tokens are minted straight into the vault
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Keeper's fee destination
+    #[account(mut, token::mint = config.mint)]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    // Anyone can process opted-in users' claims
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Whole days of unclaimed faucet entitlement, capped at MAX_ACCRUAL_DAYS.
+/// Returns (days, amount, new_last_claim_time) or None if a full day hasn't passed.
+/// Uncapped accrual keeps the partial day; capped accrual forfeits the excess.
+pub fn accrued_claims(
+    last_claim_time: i64,
+    now: i64,
+    daily_amount: u64,
+) -> Option<(u64, u64, i64)> {
+    let elapsed_days = now.checked_sub(last_claim_time)? / SECONDS_PER_DAY;
+    if elapsed_days < 1 {
+        return None;
+    }
+
+    let days = elapsed_days.min(MAX_ACCRUAL_DAYS);
+    let new_last_claim_time = if elapsed_days > MAX_ACCRUAL_DAYS {
+        now
+    } else {
+        last_claim_time.checked_add(days.checked_mul(SECONDS_PER_DAY)?)?
+    };
+    let amount = daily_amount.checked_mul(days as u64)?;
+
+    Some((days as u64, amount, new_last_claim_time))
+}
+
+/// Keeper's cut of an auto-claim; the rest is staked
+pub fn keeper_fee(amount: u64) -> u64 {
+    ((amount as u128 * AUTO_CLAIM_KEEPER_FEE_BPS as u128) / BASIS_POINTS as u128) as u64
+}
+
+pub fn set_handler(ctx: Context<SetAutoClaim>, enabled: bool) -> Result<()> {
+    let user_claim = &mut ctx.accounts.user_claim;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // First-time users start accruing from opt-in, not from the epoch
+    if user_claim.user == Pubkey::default() {
+        user_claim.version = CURRENT_ACCOUNT_VERSION;
+        user_claim.user = ctx.accounts.user.key();
+        user_claim.bump = ctx.bumps.user_claim;
+        user_claim.last_claim_time = current_time;
+    }
+    user_claim.auto_claim_to_stake = enabled;
+
+    emit!(AutoClaimToggledEvent {
+        user: user_claim.user,
+        enabled,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Auto-claim {}", if enabled { "enabled" } else { "disabled" });
+
+    Ok(())
+}
+
+pub fn process_handler(
+    ctx: Context<ProcessAutoClaims>,
+    user: Pubkey,
+    stake_count: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+    require!(
+        is_minting_enabled(
+            &ctx.accounts.mint.mint_authority,
+            &ctx.accounts.mint_authority.key()
+        ),
+        ErrorCode::MintingDisabled
+    );
+    require!(
+        ctx.accounts.user_claim.auto_claim_to_stake,
+        ErrorCode::AutoClaimNotEnabled
+    );
+    // The keeper supplies the user's proof; the tree is public
+    check_allowlisted(&ctx.accounts.config.allowlist_root, &user, &proof)?;
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // Only add to a position that is still locked and not under review
+    let stake_account = &ctx.accounts.stake_account;
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+    require!(
+        !is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StakeMatured
+    );
+
+    // Same daily amount (and stake tier) a manual claim would get
+    let config = &ctx.accounts.config;
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    let (_, multiplier_bps) = if is_feature_enabled(flags, FEATURE_STAKE_TIERED_CLAIMS) {
+        get_claim_tier(
+            &config.claim_tiers,
+            ctx.accounts.user_stake_stats.total_active_staked,
+        )
+    } else {
+        (0, BASIS_POINTS)
+    };
+    let daily_amount = ((config.daily_claim_amount as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BASIS_POINTS as u128) as u64;
+
+    let (days, amount, new_last_claim_time) = match accrued_claims(
+        ctx.accounts.user_claim.last_claim_time,
+        current_time,
+        daily_amount,
+    ) {
+        Some(accrual) => accrual,
+        None => return err!(ErrorCode::ClaimTooSoon),
+    };
+    // Keeper mints count toward the same per-wallet ceiling as manual claims
+    ctx.accounts
+        .user_claim
+        .check_lifetime_cap(amount, config.max_lifetime_claim)?;
+    let fee = keeper_fee(amount);
+    let amount_staked = amount - fee;
+
+    let new_staked_amount = stake_account
+        .staked_amount
+        .checked_add(amount_staked)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        new_staked_amount <= MAX_STAKE_AMOUNT,
+        ErrorCode::AmountTooLarge
+    );
+
+    let seeds = &[b"mint-authority".as_ref(), &[config.mint_authority_bump]];
+    let signer = &[&seeds[..]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer,
+        ),
+        amount_staked,
+    )?;
+
+    if fee > 0 {
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.keeper_token_account.to_account_info(),
+                    authority: ctx.accounts.mint_authority.to_account_info(),
+                },
+                signer,
+            ),
+            fee,
+        )?;
+    }
+
+    // A mid-lock deposit, so it earns only on the lock left
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    ctx.accounts.vault.reload()?;
+    top_up_position(
+        &mut ctx.accounts.stake_account,
+        &mut ctx.accounts.global_stats,
+        &mut ctx.accounts.user_stake_stats,
+        &mut ctx.accounts.user_stats,
+        amount_staked,
+        current_time,
+    )?;
+    // Same re-reservation as `add_to_stake`
+    reserve_position_rewards(
+        &mut ctx.accounts.stake_account,
+        &mut ctx.accounts.global_stats,
+        RewardWindow::from_config(config),
+        ctx.accounts.vault.amount,
+    )?;
+
+    let user_claim = &mut ctx.accounts.user_claim;
+    user_claim.last_claim_time = new_last_claim_time;
+    // Counts as a claim, so manual claims signed against the old state go stale
+    user_claim.claim_nonce = user_claim
+        .claim_nonce
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    user_claim.total_claimed = user_claim
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    // Keeper fee included: it's minted by the faucet too
+    ctx.accounts.global_stats.record_minted(amount)?;
+
+    emit!(AutoClaimProcessedEvent {
+        user,
+        keeper: ctx.accounts.keeper.key(),
+        stake_index: stake_count,
+        days,
+        amount_staked,
+        keeper_fee: fee,
+        total_claimed: user_claim.total_claimed,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Auto-claim processed!");
+    msg!("Days: {}", days);
+    msg!("Staked: {} DEVR", to_ui_amount(amount_staked, ctx.accounts.mint.decimals));
+    msg!("Keeper fee: {} DEVR", to_ui_amount(fee, ctx.accounts.mint.decimals));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAILY: u64 = 100_000_000_000; // 100 DEVR
+    const LAST: i64 = 1_700_000_000;
+
+    #[test]
+    fn test_multi_day_accrual_into_stake() {
+        // 3 days and 5 hours later: 3 days are processed, the 5 hours carry over
+        let now = LAST + 3 * SECONDS_PER_DAY + 5 * 3_600;
+        let (days, amount, new_last) = accrued_claims(LAST, now, DAILY).unwrap();
+        assert_eq!(days, 3);
+        assert_eq!(amount, 300_000_000_000);
+        assert_eq!(new_last, LAST + 3 * SECONDS_PER_DAY);
+
+        // 1% to the keeper, the rest is staked
+        let fee = keeper_fee(amount);
+        assert_eq!(fee, 3_000_000_000);
+        assert_eq!(amount - fee, 297_000_000_000);
+
+        // The carried-over hours count toward the next day
+        assert!(accrued_claims(new_last, now, DAILY).is_none());
+        let (days, _, _) = accrued_claims(new_last, new_last + SECONDS_PER_DAY, DAILY).unwrap();
+        assert_eq!(days, 1);
+    }
+
+    #[test]
+    fn test_accrual_is_capped() {
+        let now = LAST + 30 * SECONDS_PER_DAY;
+        let (days, amount, new_last) = accrued_claims(LAST, now, DAILY).unwrap();
+        assert_eq!(days, MAX_ACCRUAL_DAYS as u64);
+        assert_eq!(amount, DAILY * MAX_ACCRUAL_DAYS as u64);
+        // Days beyond the cap are forfeited, not banked
+        assert_eq!(new_last, now);
+    }
+
+    #[test]
+    fn test_no_accrual_within_a_day() {
+        assert!(accrued_claims(LAST, LAST + SECONDS_PER_DAY - 1, DAILY).is_none());
+        assert!(accrued_claims(LAST, LAST - 1, DAILY).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::constants::{CURRENT_ACCOUNT_VERSION, MAX_ALLOWLIST_BATCH};
+use crate::error::ErrorCode;
+use crate::events::BatchAllowlistEvent;
+use crate::state::{AllowlistEntry, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct BatchAddToAllowlist<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: one writable AllowlistEntry PDA per user, same order
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchAddToAllowlist<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        !users.is_empty()
+            && users.len() <= MAX_ALLOWLIST_BATCH
+            && users.len() == ctx.remaining_accounts.len(),
+        ErrorCode::InvalidAllowlistBatch
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let rent = Rent::get()?.minimum_balance(AllowlistEntry::LEN);
+    let mut added: u64 = 0;
+
+    for (user, entry_info) in users.iter().zip(ctx.remaining_accounts.iter()) {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[b"allowlist", user.as_ref()], ctx.program_id);
+        require_keys_eq!(
+            entry_info.key(),
+            expected,
+            ErrorCode::AllowlistAccountMismatch
+        );
+
+        // Already allowlisted - skip instead of failing the batch
+        if entry_info.owner == ctx.program_id && !entry_info.data_is_empty() {
+            continue;
+        }
+
+        let seeds: &[&[u8]] = &[b"allowlist", user.as_ref(), &[bump]];
+        create_entry_account(&ctx, entry_info, seeds, rent)?;
+
+        let entry = AllowlistEntry {
+            version: CURRENT_ACCOUNT_VERSION,
+            user: *user,
+            added_at: now,
+            bump,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+
+        added += 1;
+    }
+
+    emit!(BatchAllowlistEvent {
+        admin: ctx.accounts.admin.key(),
+        requested: users.len() as u64,
+        added,
+        timestamp: now,
+    });
+
+    msg!("✅ Allowlist batch processed!");
+    msg!("Added: {} / {}", added, users.len());
+
+    Ok(())
+}
+
+/// Creates the entry PDA, tolerating lamports someone sent to the address beforehand
+fn create_entry_account<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, BatchAddToAllowlist<'info>>,
+    entry_info: &'info AccountInfo<'info>,
+    seeds: &[&[u8]],
+    rent: u64,
+) -> Result<()> {
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let admin = ctx.accounts.admin.to_account_info();
+    let signer = &[seeds];
+
+    let current_lamports = entry_info.lamports();
+    if current_lamports == 0 {
+        let cpi_accounts = CreateAccount {
+            from: admin,
+            to: entry_info.clone(),
+        };
+        return system_program::create_account(
+            CpiContext::new_with_signer(system_program, cpi_accounts, signer),
+            rent,
+            AllowlistEntry::LEN as u64,
+            ctx.program_id,
+        );
+    }
+
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        let cpi_accounts = Transfer {
+            from: admin,
+            to: entry_info.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program.clone(), cpi_accounts), top_up)?;
+    }
+
+    let cpi_accounts = Allocate {
+        account_to_allocate: entry_info.clone(),
+    };
+    system_program::allocate(
+        CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer),
+        AllowlistEntry::LEN as u64,
+    )?;
+
+    let cpi_accounts = Assign {
+        account_to_assign: entry_info.clone(),
+    };
+    system_program::assign(
+        CpiContext::new_with_signer(system_program, cpi_accounts, signer),
+        ctx.program_id,
+    )
+}
@@ -0,0 +1,111 @@
+use super::transfer_tokens::check_transfer_amount;
+use crate::constants::{to_ui_amount, DEVR_DECIMALS, MAX_BATCH_TRANSFER};
+use crate::error::ErrorCode;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct BatchTransfer<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(mut, token::authority = authority)]
+    pub from_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: one writable recipient token account per amount, same order
+}
+
+/// True if any key appears twice. Batches are capped at MAX_BATCH_TRANSFER,
+/// so sorting a copy stays well inside the compute budget.
+pub fn has_duplicate_keys(keys: &[Pubkey]) -> bool {
+    let mut sorted = keys.to_vec();
+    sorted.sort_unstable();
+    sorted.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+    amounts: Vec<u64>,
+    reject_duplicates: bool,
+) -> Result<()> {
+    let recipients = ctx.remaining_accounts;
+    require!(
+        !amounts.is_empty()
+            && amounts.len() <= MAX_BATCH_TRANSFER
+            && amounts.len() == recipients.len(),
+        ErrorCode::InvalidBatchTransfer
+    );
+
+    // Off by default: sending to the same account twice can be intentional
+    if reject_duplicates {
+        let keys: Vec<Pubkey> = recipients.iter().map(|info| info.key()).collect();
+        require!(!has_duplicate_keys(&keys), ErrorCode::DuplicateRecipient);
+    }
+
+    let config = &ctx.accounts.config;
+    let mut total: u64 = 0;
+    for amount in amounts.iter() {
+        check_transfer_amount(*amount, config.min_transfer, config.max_transfer)?;
+        total = total
+            .checked_add(*amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    require!(
+        total <= ctx.accounts.from_token_account.amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let from_mint = ctx.accounts.from_token_account.mint;
+
+    for (recipient, amount) in recipients.iter().zip(amounts.iter()) {
+        let to_token_account = Account::<TokenAccount>::try_from(recipient)?;
+        require!(to_token_account.mint == from_mint, ErrorCode::MintMismatch);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.from_token_account.to_account_info(),
+            to: recipient.clone(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), *amount)?;
+    }
+
+    msg!("✅ Batch transfer successful!");
+    msg!("From: {}", ctx.accounts.from_token_account.key());
+    msg!("Recipients: {}", recipients.len());
+    msg!("Total: {} tokens", to_ui_amount(total, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_duplicates() {
+        let keys: Vec<Pubkey> = (0..MAX_BATCH_TRANSFER).map(|_| Pubkey::new_unique()).collect();
+        assert!(!has_duplicate_keys(&keys));
+    }
+
+    #[test]
+    fn test_duplicate_detected_anywhere_in_batch() {
+        let mut keys: Vec<Pubkey> = (0..MAX_BATCH_TRANSFER).map(|_| Pubkey::new_unique()).collect();
+        keys[MAX_BATCH_TRANSFER - 1] = keys[0];
+        assert!(has_duplicate_keys(&keys));
+    }
+
+    #[test]
+    fn test_single_recipient() {
+        assert!(!has_duplicate_keys(&[Pubkey::new_unique()]));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
+use crate::error::ErrorCode;
+use crate::events::{AddressBlockedEvent, AddressUnblockedEvent};
+use crate::state::{Blocklist, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct BlockAddress<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Blocklist::LEN,
+        seeds = [b"blocklist", address.as_ref()],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct UnblockAddress<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Closing the entry is what unblocks the address
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"blocklist", address.as_ref()],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Block program-mediated transfers (`transfer`, `delegated_transfer`) to token
+/// accounts owned by `address`. Plain SPL transfers outside this program can't be stopped.
+pub fn block_handler(ctx: Context<BlockAddress>, address: Pubkey) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.version = CURRENT_ACCOUNT_VERSION;
+    blocklist.address = address;
+    blocklist.blocked_at = now;
+    blocklist.bump = ctx.bumps.blocklist;
+
+    emit!(AddressBlockedEvent {
+        admin: ctx.accounts.admin.key(),
+        address,
+        timestamp: now,
+    });
+
+    msg!("⛔ Address blocked: {}", address);
+
+    Ok(())
+}
+
+pub fn unblock_handler(ctx: Context<UnblockAddress>, address: Pubkey) -> Result<()> {
+    emit!(AddressUnblockedEvent {
+        admin: ctx.accounts.admin.key(),
+        address,
+        blocked_at: ctx.accounts.blocklist.blocked_at,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Address unblocked: {}", address);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::error::ErrorCode;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitBoostVault<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Holds boost deposits until their positions unstake, controlled by the vault authority
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"boost-vault"],
+        bump
+    )]
+    pub boost_vault: Account<'info, TokenAccount>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA derived from seeds, only used as the boost vault's authority
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Reads the boost vault if an admin has created it. Unboosted stakes never touch it.
+pub fn load_boost_vault(info: &AccountInfo) -> Result<Option<TokenAccount>> {
+    if info.owner != &anchor_spl::token::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = info.try_borrow_data()?;
+    Ok(Some(TokenAccount::try_deserialize(&mut &data[..])?))
+}
+
+pub fn handler(ctx: Context<InitBoostVault>) -> Result<()> {
+    msg!("✅ Boost vault created!");
+    msg!("Vault: {}", ctx.accounts.boost_vault.key());
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::constants::{
+    to_ui_amount, CIRCUIT_BREAKER_BUCKETS, CONFIG_FIELD_CIRCUIT_BREAKER_THRESHOLD,
+    CONFIG_FIELD_CIRCUIT_BREAKER_WINDOW, CONFIG_FIELD_STAKING_PAUSED, DEVR_DECIMALS,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, CircuitBreakerResetEvent, CircuitBreakerUpdatedEvent};
+use crate::state::{GlobalStats, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCircuitBreaker<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResetCircuitBreaker<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Configure the unstake volume limit. A threshold of 0 disables the breaker.
+pub fn set_handler(ctx: Context<SetCircuitBreaker>, window: i64, threshold: u64) -> Result<()> {
+    require!(
+        window >= CIRCUIT_BREAKER_BUCKETS as i64,
+        ErrorCode::InvalidCircuitBreakerWindow
+    );
+
+    let config = &mut ctx.accounts.config;
+    let (old_window, old_threshold) = (config.circuit_breaker_window, config.circuit_breaker_threshold);
+    config.circuit_breaker_window = window;
+    config.circuit_breaker_threshold = threshold;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(CircuitBreakerUpdatedEvent {
+        admin,
+        window,
+        threshold,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_CIRCUIT_BREAKER_WINDOW, old_window as u64, window as u64, admin, timestamp);
+    emit_config_changed(CONFIG_FIELD_CIRCUIT_BREAKER_THRESHOLD, old_threshold, threshold, admin, timestamp);
+
+    msg!("✅ Circuit breaker updated!");
+    msg!("Window: {} seconds", window);
+    msg!("Threshold: {} DEVR", to_ui_amount(threshold, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+/// Resume staking after a trip and start a fresh volume window
+pub fn reset_handler(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+    let global_stats = &mut ctx.accounts.global_stats;
+    let was_paused = global_stats.staking_paused;
+    global_stats.staking_paused = false;
+    global_stats.unstake_buckets = [0; CIRCUIT_BREAKER_BUCKETS];
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(CircuitBreakerResetEvent { admin, timestamp });
+    emit_config_changed(CONFIG_FIELD_STAKING_PAUSED, was_paused as u64, 0, admin, timestamp);
+
+    msg!("✅ Circuit breaker reset - staking resumed");
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use super::claim_tokens::{check_allowlisted, is_minting_enabled, price_claim, record_claim};
+use super::stake::{open_position, PositionTerms};
+use super::unstake::PositionBooks;
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{ClaimEvent, StakeEvent};
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, StakeAccount, StakeCounter,
+    TokenConfig, UserClaim, UserStakeStats, UserStats,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimAndStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserClaim::LEN,
+        seeds = [b"user-claim", user.key().as_ref()],
+        bump
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    #[account(mut, address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: This is a PDA used as the mint authority, validated by seeds and bump
+    #[account(
+        seeds = [b"mint-authority"],
+        bump = config.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeCounter::LEN,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump
+    )]
+    pub counter: Account<'info, StakeCounter>,
+
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake", user.key().as_ref(), &counter.stake_count.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    // The claim is minted straight into the vault as the new position's principal
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump,
+        address = config.vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim the faucet and stake the whole claim for `lock_duration` in one transaction.
+/// The claim follows every `claim_tokens` rule (allowlist, nonce, cooldown, tier, streak,
+/// cap); the position is a plain stake of exactly the claimed amount.
+pub fn handler(
+    ctx: Context<ClaimAndStake>,
+    expected_nonce: u64,
+    lock_duration: i64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+    require!(
+        is_minting_enabled(
+            &ctx.accounts.mint.mint_authority,
+            &ctx.accounts.mint_authority.key()
+        ),
+        ErrorCode::MintingDisabled
+    );
+    check_allowlisted(&ctx.accounts.config.allowlist_root, &ctx.accounts.user.key(), &proof)?;
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+    require!(
+        lock_duration >= MIN_LOCK_DURATION,
+        ErrorCode::DurationTooShort
+    );
+    require!(
+        lock_duration <= MAX_LOCK_DURATION,
+        ErrorCode::DurationTooLong
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let config = &ctx.accounts.config;
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    let user_claim = &mut ctx.accounts.user_claim;
+    let (amount, claim_tier, streak_bonus_bps) = price_claim(
+        user_claim,
+        config,
+        flags,
+        ctx.accounts.user_stake_stats.total_active_staked,
+        expected_nonce,
+        current_time,
+    )?;
+    require!(amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
+    require!(amount <= MAX_STAKE_AMOUNT, ErrorCode::AmountTooLarge);
+
+    let seeds = &[b"mint-authority".as_ref(), &[config.mint_authority_bump]];
+    let signer = &[&seeds[..]];
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+
+    let user = ctx.accounts.user.key();
+    record_claim(user_claim, user, ctx.bumps.user_claim, amount, current_time)?;
+
+    let tiers = ctx.accounts.config.apy_tiers;
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    ctx.accounts.global_stats.record_minted(amount)?;
+    // Same reservation as `stake`: the vault must already back the position's rewards
+    ctx.accounts.vault.reload()?;
+    let stake_account = &mut ctx.accounts.stake_account;
+    // Nothing liquid to snapshot a loyalty bonus from, and no boost deposit
+    open_position(
+        stake_account,
+        user,
+        ctx.bumps.stake_account,
+        &PositionTerms {
+            epoch_bonus: true,
+            ..PositionTerms::plain(
+                amount,
+                lock_duration,
+                tiers,
+                is_feature_enabled(flags, FEATURE_INTERPOLATED_APY),
+                current_time,
+            )
+        },
+        config,
+        &mut PositionBooks {
+            counter: &mut ctx.accounts.counter,
+            global_stats: &mut ctx.accounts.global_stats,
+            user_stake_stats: &mut ctx.accounts.user_stake_stats,
+            user_stats: &mut ctx.accounts.user_stats,
+        },
+        ctx.accounts.vault.amount,
+    )?;
+    let epoch_bonus_bps = stake_account.epoch_bonus_bps;
+
+    let counter = &mut ctx.accounts.counter;
+    if counter.bump == 0 {
+        counter.version = CURRENT_ACCOUNT_VERSION;
+        counter.bump = ctx.bumps.counter;
+        counter.unbonding_count = 0;
+    }
+
+    emit!(ClaimEvent {
+        user,
+        amount,
+        total_claimed: user_claim.total_claimed,
+        claim_tier,
+        current_streak: user_claim.current_streak,
+        streak_bonus_bps,
+        timestamp: current_time,
+    });
+    emit!(StakeEvent {
+        user,
+        stake_index: stake_account.stake_index,
+        staked_amount: amount,
+        lock_duration,
+        apy_numerator: stake_account.apy_numerator,
+        apy_denominator: stake_account.apy_denominator,
+        loyalty_bonus_bps: 0,
+        epoch_bonus_bps,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Claimed and staked!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, ctx.accounts.mint.decimals));
+    msg!("Stake index: {}", stake_account.stake_index);
+    msg!("Duration: {} days", lock_duration / 86400);
+    msg!("Next claim available in {} seconds", config.claim_cooldown_seconds);
+
+    Ok(())
+}
@@ -1,6 +1,13 @@
+use crate::constants::*;
 use crate::error::ErrorCode;
-use crate::state::{TokenConfig, UserClaim};
+use crate::events::{ClaimEvent, ReferralEvent};
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, Referral, TokenConfig, UserClaim,
+    UserStakeStats,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{mint_to, Mint, MintTo, Token, TokenAccount},
@@ -41,6 +48,41 @@ pub struct ClaimTokens<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
+    // Only total_minted is touched; claims never read the vault or staking reserves
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: User's stake stats PDA, validated by seeds. May not exist yet for non-stakers,
+    /// so it is deserialized manually in the handler.
+    #[account(
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    // Optional: the referrer's registered Referral, passed with their token account
+    #[account(
+        mut,
+        seeds = [b"referral", referral.referrer.as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
+    #[account(mut, token::mint = config.mint)]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -49,21 +91,196 @@ pub struct ClaimTokens<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<ClaimTokens>) -> Result<()> {
-    let user_claim = &mut ctx.accounts.user_claim;
-    let config = &ctx.accounts.config;
-    let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+/// Reads the user's active stake, treating a missing stats account as a non-staker
+fn active_staked(user_stake_stats: &AccountInfo) -> Result<u64> {
+    if user_stake_stats.owner != &crate::ID || user_stake_stats.data_is_empty() {
+        return Ok(0);
+    }
 
-    // Check cooldown (skip check for first-time claimers)
-    if user_claim.last_claim_time != 0 {
-        let time_elapsed = current_time - user_claim.last_claim_time;
+    let data = user_stake_stats.try_borrow_data()?;
+    let stats = UserStakeStats::try_deserialize(&mut &data[..])?;
+    Ok(stats.total_active_staked)
+}
+
+/// Extra claim share for a streak: nothing on day one, then STREAK_BONUS_BPS_PER_DAY
+/// per consecutive day, up to `max_bonus_bps`
+pub fn streak_bonus_bps(current_streak: u32, max_bonus_bps: u64) -> u64 {
+    (current_streak.saturating_sub(1) as u64)
+        .saturating_mul(STREAK_BONUS_BPS_PER_DAY)
+        .min(max_bonus_bps)
+}
+
+/// Seconds left before the next claim is allowed; 0 once `cooldown` has passed.
+/// First-time claimers (no previous claim) never wait.
+pub fn claim_cooldown_remaining(last_claim_time: i64, now: i64, cooldown: i64) -> i64 {
+    if last_claim_time == 0 {
+        return 0;
+    }
+    cooldown.saturating_sub(now.saturating_sub(last_claim_time)).max(0)
+}
 
-        if time_elapsed < 86400 {
-            msg!("Time remaining: {} seconds", 86400 - time_elapsed);
-            return Err(error!(ErrorCode::ClaimTooSoon));
+/// Checks a referral passed with a claim: both accounts or neither, the token account
+/// must belong to the referrer, and nobody can refer themselves
+pub fn validate_referral(
+    referrer: Option<Pubkey>,
+    referrer_token_owner: Option<Pubkey>,
+    user: &Pubkey,
+) -> Result<()> {
+    match (referrer, referrer_token_owner) {
+        (None, None) => Ok(()),
+        (Some(referrer), Some(owner)) => {
+            require!(referrer != *user, ErrorCode::SelfReferral);
+            require!(owner == referrer, ErrorCode::ReferralAccountMismatch);
+            Ok(())
         }
+        _ => err!(ErrorCode::ReferralAccountMismatch),
     }
+}
+
+/// Minting only works while the mint authority is still our PDA
+pub fn is_minting_enabled(mint_authority: &COption<Pubkey>, expected: &Pubkey) -> bool {
+    *mint_authority == COption::Some(*expected)
+}
+
+/// Leaf the allowlist root commits to for `user`
+pub fn allowlist_leaf(user: &Pubkey) -> [u8; 32] {
+    hashv(&[&[ALLOWLIST_LEAF_PREFIX], user.as_ref()]).to_bytes()
+}
+
+/// Folds a sorted-pair Merkle proof from `user`'s leaf up to a root
+pub fn allowlist_proof_root(user: &Pubkey, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(allowlist_leaf(user), |node, sibling| {
+        let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+        hashv(&[&[ALLOWLIST_NODE_PREFIX], &left, &right]).to_bytes()
+    })
+}
+
+/// Faucet gate shared by every claim path. The open (all-zero) root skips the proof.
+pub fn check_allowlisted(allowlist_root: &[u8; 32], user: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+    if *allowlist_root == OPEN_ALLOWLIST_ROOT {
+        return Ok(());
+    }
+    require!(
+        proof.len() <= MAX_ALLOWLIST_PROOF_DEPTH
+            && allowlist_proof_root(user, proof) == *allowlist_root,
+        ErrorCode::NotAllowlisted
+    );
+    Ok(())
+}
+
+/// Everything a claim checks and prices before minting: replay nonce, cooldown,
+/// stake tier, streak bonus and the lifetime cap. Shared with `claim_and_stake`.
+/// Returns (claim_amount, claim_tier, streak_bonus_bps).
+pub fn price_claim(
+    user_claim: &mut UserClaim,
+    config: &TokenConfig,
+    flags: u64,
+    total_active_staked: u64,
+    expected_nonce: u64,
+    current_time: i64,
+) -> Result<(u64, u8, u64)> {
+    // Replay guard: a claim signed against an older state (e.g. on a fork) is rejected
+    user_claim.consume_nonce(expected_nonce)?;
+
+    let time_remaining = claim_cooldown_remaining(
+        user_claim.last_claim_time,
+        current_time,
+        config.claim_cooldown_seconds,
+    );
+    if time_remaining > 0 {
+        msg!("Time remaining: {} seconds", time_remaining);
+        return Err(error!(ErrorCode::ClaimTooSoon));
+    }
+
+    // Bigger stakers get a bigger faucet
+    let (claim_tier, multiplier_bps) = if is_feature_enabled(flags, FEATURE_STAKE_TIERED_CLAIMS) {
+        get_claim_tier(&config.claim_tiers, total_active_staked)
+    } else {
+        (0, BASIS_POINTS)
+    };
+    let tier_amount = ((config.daily_claim_amount as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BASIS_POINTS as u128) as u64;
+
+    // Consecutive days scale the claim on top of the tier
+    user_claim.record_streak(current_time);
+    let streak_bonus_bps = streak_bonus_bps(user_claim.current_streak, config.max_streak_bonus_bps);
+    let claim_amount = ((tier_amount as u128)
+        .checked_mul((BASIS_POINTS + streak_bonus_bps) as u128)
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        / BASIS_POINTS as u128) as u64;
+
+    // Per-wallet ceiling so one address can't drain the faucet over time
+    user_claim.check_lifetime_cap(claim_amount, config.max_lifetime_claim)?;
+
+    Ok((claim_amount, claim_tier, streak_bonus_bps))
+}
+
+/// Stamps a minted claim onto the user's claim record, filling in a new one
+pub fn record_claim(
+    user_claim: &mut UserClaim,
+    user: Pubkey,
+    bump: u8,
+    claim_amount: u64,
+    current_time: i64,
+) -> Result<()> {
+    if user_claim.user == Pubkey::default() {
+        user_claim.version = CURRENT_ACCOUNT_VERSION;
+        user_claim.user = user;
+        user_claim.bump = bump;
+    }
+
+    user_claim.last_claim_time = current_time;
+    user_claim.total_claimed = user_claim
+        .total_claimed
+        .checked_add(claim_amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+pub fn handler(ctx: Context<ClaimTokens>, expected_nonce: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    // Fail clearly instead of with an opaque SPL authority error after a renounce
+    require!(
+        is_minting_enabled(
+            &ctx.accounts.mint.mint_authority,
+            &ctx.accounts.mint_authority.key()
+        ),
+        ErrorCode::MintingDisabled
+    );
+    check_allowlisted(&ctx.accounts.config.allowlist_root, &ctx.accounts.user.key(), &proof)?;
+
+    validate_referral(
+        ctx.accounts.referral.as_ref().map(|referral| referral.referrer),
+        ctx.accounts.referrer_token_account.as_ref().map(|account| account.owner),
+        &ctx.accounts.user.key(),
+    )?;
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    // Only read the stats account when tiers can actually use it
+    let total_active_staked = if is_feature_enabled(flags, FEATURE_STAKE_TIERED_CLAIMS) {
+        active_staked(&ctx.accounts.user_stake_stats)?
+    } else {
+        0
+    };
+    let user_claim = &mut ctx.accounts.user_claim;
+    let config = &ctx.accounts.config;
+    let (claim_amount, claim_tier, streak_bonus_bps) = price_claim(
+        user_claim,
+        config,
+        flags,
+        total_active_staked,
+        expected_nonce,
+        current_time,
+    )?;
 
     // Setup CPI accounts for minting tokens
     let cpi_accounts = MintTo {
@@ -85,22 +302,209 @@ pub fn handler(ctx: Context<ClaimTokens>) -> Result<()> {
     // Mint tokens to user's token account
     mint_to(
         cpi_ctx.with_signer(signer),
-        config.daily_claim_amount
+        claim_amount
     )?;
 
     // Update user claim state
-    if user_claim.user == Pubkey::default() {
-        user_claim.user = ctx.accounts.user.key();
-        user_claim.bump = ctx.bumps.user_claim;
+    record_claim(
+        user_claim,
+        ctx.accounts.user.key(),
+        ctx.bumps.user_claim,
+        claim_amount,
+        current_time,
+    )?;
+    ctx.accounts.global_stats.record_minted(claim_amount)?;
+
+    // Growth loop: the referrer gets a bonus on top of, not out of, the claim
+    if let (Some(referral), Some(referrer_token_account)) = (
+        ctx.accounts.referral.as_mut(),
+        ctx.accounts.referrer_token_account.as_ref(),
+    ) {
+        let bonus = config.referral_bonus;
+        if bonus > 0 {
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: referrer_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer,
+                ),
+                bonus,
+            )?;
+            ctx.accounts.global_stats.record_minted(bonus)?;
+        }
+        referral.record_referral(bonus)?;
+
+        emit!(ReferralEvent {
+            referrer: referral.referrer,
+            user: ctx.accounts.user.key(),
+            bonus,
+            total_referred: referral.total_referred,
+            referral_rewards: referral.referral_rewards,
+            timestamp: current_time,
+        });
+        msg!("Referral bonus: {} to {}", bonus, referral.referrer);
     }
 
-    user_claim.last_claim_time = current_time;
-    user_claim.total_claimed += config.daily_claim_amount;
+    emit!(ClaimEvent {
+        user: ctx.accounts.user.key(),
+        amount: claim_amount,
+        total_claimed: user_claim.total_claimed,
+        claim_tier,
+        current_streak: user_claim.current_streak,
+        streak_bonus_bps,
+        timestamp: current_time,
+    });
 
     msg!("Tokens claimed successfully!");
-    msg!("Amount claimed: {}", config.daily_claim_amount);
+    msg!("Amount claimed: {}", claim_amount);
+    msg!("Claim tier: {}", claim_tier);
+    msg!("Streak: {} days", user_claim.current_streak);
     msg!("Total claimed: {}", user_claim.total_claimed);
-    msg!("Next claim available in 24 hours");
+    msg!("Next claim available in {} seconds", config.claim_cooldown_seconds);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minting_enabled_with_pda_authority() {
+        let pda = Pubkey::new_unique();
+        assert!(is_minting_enabled(&COption::Some(pda), &pda));
+    }
+
+    #[test]
+    fn test_minting_disabled_when_renounced() {
+        let pda = Pubkey::new_unique();
+        assert!(!is_minting_enabled(&COption::None, &pda));
+    }
+
+    #[test]
+    fn test_streak_bonus_grows_daily_up_to_the_cap() {
+        let cap = 500; // +5%
+        assert_eq!(streak_bonus_bps(1, cap), 0);
+        assert_eq!(streak_bonus_bps(2, cap), STREAK_BONUS_BPS_PER_DAY);
+        assert_eq!(streak_bonus_bps(6, cap), cap);
+        assert_eq!(streak_bonus_bps(30, cap), cap);
+
+        // No cap configured, no bonus
+        assert_eq!(streak_bonus_bps(30, 0), 0);
+        assert_eq!(streak_bonus_bps(u32::MAX, MAX_STREAK_BONUS_BPS), MAX_STREAK_BONUS_BPS);
+    }
+
+    #[test]
+    fn test_cooldown_boundary_follows_config() {
+        const LAST: i64 = 1_700_000_000;
+        let hour = 3_600;
+
+        // Testnet-style 1-hour cooldown
+        assert_eq!(claim_cooldown_remaining(LAST, LAST + 1, hour), hour - 1);
+        assert_eq!(claim_cooldown_remaining(LAST, LAST + hour - 1, hour), 1);
+        assert_eq!(claim_cooldown_remaining(LAST, LAST + hour, hour), 0);
+
+        // The default still waits a full day
+        assert_eq!(claim_cooldown_remaining(LAST, LAST + hour, DEFAULT_CLAIM_COOLDOWN), 86_400 - hour);
+        assert_eq!(claim_cooldown_remaining(LAST, LAST + 86_400, DEFAULT_CLAIM_COOLDOWN), 0);
+
+        // First claim never waits
+        assert_eq!(claim_cooldown_remaining(0, LAST, DEFAULT_CLAIM_COOLDOWN), 0);
+    }
+
+    #[test]
+    fn test_referral_validation() {
+        let user = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+
+        validate_referral(None, None, &user).unwrap();
+        validate_referral(Some(referrer), Some(referrer), &user).unwrap();
+
+        // Naming yourself earns nothing
+        assert_eq!(
+            validate_referral(Some(user), Some(user), &user).unwrap_err(),
+            error!(ErrorCode::SelfReferral)
+        );
+
+        // The bonus can't be routed to someone else's account, or sent without a referral
+        assert_eq!(
+            validate_referral(Some(referrer), Some(user), &user).unwrap_err(),
+            error!(ErrorCode::ReferralAccountMismatch)
+        );
+        assert!(validate_referral(Some(referrer), None, &user).is_err());
+        assert!(validate_referral(None, Some(referrer), &user).is_err());
+    }
+
+    #[test]
+    fn test_minting_disabled_when_authority_moved() {
+        let pda = Pubkey::new_unique();
+        assert!(!is_minting_enabled(&COption::Some(Pubkey::new_unique()), &pda));
+    }
+
+    /// Sorted-pair root over the given wallets, padding odd levels by promoting the last node
+    fn root_of(users: &[Pubkey]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let mut levels = vec![users.iter().map(allowlist_leaf).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => {
+                        let (left, right) = if a <= b { (a, b) } else { (b, a) };
+                        hashv(&[&[ALLOWLIST_NODE_PREFIX], left, right]).to_bytes()
+                    }
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        (levels.last().unwrap()[0], levels)
+    }
+
+    fn proof_for(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        for level in &levels[..levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+
+    #[test]
+    fn test_allowlist_accepts_valid_proofs() {
+        let users: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+        let (root, levels) = root_of(&users);
+
+        for (index, user) in users.iter().enumerate() {
+            assert!(check_allowlisted(&root, user, &proof_for(&levels, index)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_allowlist_rejects_outsiders_and_bad_proofs() {
+        let users: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (root, levels) = root_of(&users);
+
+        // Someone else's proof doesn't carry an outsider to the root
+        assert_eq!(
+            check_allowlisted(&root, &Pubkey::new_unique(), &proof_for(&levels, 0)).unwrap_err(),
+            error!(ErrorCode::NotAllowlisted)
+        );
+        // Nor does another member's proof, or no proof at all
+        assert!(check_allowlisted(&root, &users[1], &proof_for(&levels, 2)).is_err());
+        assert!(check_allowlisted(&root, &users[1], &[]).is_err());
+    }
+
+    #[test]
+    fn test_open_allowlist_needs_no_proof() {
+        assert!(check_allowlisted(&OPEN_ALLOWLIST_ROOT, &Pubkey::new_unique(), &[]).is_ok());
+    }
+}
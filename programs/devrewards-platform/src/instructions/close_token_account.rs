@@ -0,0 +1,51 @@
+use crate::error::ErrorCode;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CloseTokenAccount<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Owner's DEVR token account - must be empty before closing
+    #[account(mut, token::authority = owner)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    // Owner receives the rent lamports back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CloseTokenAccount>) -> Result<()> {
+    require!(
+        ctx.accounts.token_account.mint == ctx.accounts.config.mint,
+        ErrorCode::MintMismatch
+    );
+    require!(
+        ctx.accounts.token_account.amount == 0,
+        ErrorCode::TokenAccountNotEmpty
+    );
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.token_account.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+    token::close_account(cpi_ctx)?;
+
+    msg!("✅ Token account closed!");
+    msg!("Token account: {}", ctx.accounts.token_account.key());
+    msg!("Rent returned to: {}", ctx.accounts.owner.key());
+
+    Ok(())
+}
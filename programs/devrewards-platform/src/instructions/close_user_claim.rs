@@ -0,0 +1,93 @@
+use super::claim_tokens::claim_cooldown_remaining;
+use crate::constants::{to_ui_amount, DEVR_DECIMALS, NO_LIFETIME_CLAIM_CAP};
+use crate::error::ErrorCode;
+use crate::events::UserClaimClosedEvent;
+use crate::state::{TokenConfig, UserClaim};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseUserClaim<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // User receives the rent lamports back
+    #[account(
+        mut,
+        seeds = [b"user-claim", user.key().as_ref()],
+        bump = user_claim.bump,
+        has_one = user @ ErrorCode::Unauthorized,
+        close = user
+    )]
+    pub user_claim: Account<'info, UserClaim>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Closing wipes the claim history, so it must not skip a cooldown or reset a
+/// lifetime cap - the next `claim_tokens` starts over as a first-time claimer
+pub fn check_closable(
+    last_claim_time: i64,
+    now: i64,
+    claim_cooldown_seconds: i64,
+    max_lifetime_claim: u64,
+) -> Result<()> {
+    require!(
+        max_lifetime_claim == NO_LIFETIME_CLAIM_CAP,
+        ErrorCode::ClaimHistoryLocked
+    );
+    require!(
+        claim_cooldown_remaining(last_claim_time, now, claim_cooldown_seconds) == 0,
+        ErrorCode::ClaimTooSoon
+    );
+    Ok(())
+}
+
+pub fn handler(ctx: Context<CloseUserClaim>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let user_claim = &ctx.accounts.user_claim;
+    let clock = Clock::get()?;
+
+    check_closable(
+        user_claim.last_claim_time,
+        clock.unix_timestamp,
+        config.claim_cooldown_seconds,
+        config.max_lifetime_claim,
+    )?;
+
+    emit!(UserClaimClosedEvent {
+        user: user_claim.user,
+        total_claimed: user_claim.total_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Claim account closed!");
+    msg!("User: {}", user_claim.user);
+    msg!("Total claimed: {} DEVR", to_ui_amount(user_claim.total_claimed, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAST_CLAIM: i64 = 1_700_000_000;
+    const COOLDOWN: i64 = 86_400;
+
+    #[test]
+    fn test_close_waits_out_the_cooldown() {
+        assert!(check_closable(LAST_CLAIM, LAST_CLAIM + COOLDOWN - 1, COOLDOWN, NO_LIFETIME_CLAIM_CAP).is_err());
+        assert!(check_closable(LAST_CLAIM, LAST_CLAIM + COOLDOWN, COOLDOWN, NO_LIFETIME_CLAIM_CAP).is_ok());
+        // Never claimed
+        assert!(check_closable(0, LAST_CLAIM, COOLDOWN, NO_LIFETIME_CLAIM_CAP).is_ok());
+    }
+
+    #[test]
+    fn test_close_blocked_by_lifetime_cap() {
+        assert!(check_closable(LAST_CLAIM, LAST_CLAIM + COOLDOWN, COOLDOWN, 1_000_000_000_000).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+use super::stake::{open_position, PositionTerms};
+use super::unstake::{reserve_position_rewards, uncompounded_rewards, PositionBooks, RewardWindow};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::RewardsCompoundedEvent;
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, RewardConfig, StakeAccount,
+    StakeCounter, TokenConfig, UserStakeStats, UserStats,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct CompoundRewardsToNewStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump = counter.bump
+    )]
+    pub counter: Account<'info, StakeCounter>,
+
+    // Position whose accrued rewards are compounded - principal and clock stay as they are
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        constraint = stake_account.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    // Position opened for the compounded rewards
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake", user.key().as_ref(), &counter.stake_count.to_le_bytes()],
+        bump
+    )]
+    pub new_stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    /// CHECK: Reward config PDA, validated by seeds. Read manually; rewards can only
+    /// become principal while they are paid in the stake mint.
+    #[account(
+        seeds = [b"reward-config"],
+        bump
+    )]
+    pub reward_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CompoundRewardsToNewStake>,
+    stake_count: u64,
+    lock_duration: i64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+    require!(
+        lock_duration >= MIN_LOCK_DURATION,
+        ErrorCode::DurationTooShort
+    );
+    require!(
+        lock_duration <= MAX_LOCK_DURATION,
+        ErrorCode::DurationTooLong
+    );
+
+    // Rewards become principal of the new position, so they must be DEVR
+    require!(
+        ctx.accounts.config.reward_decimals == DEVR_DECIMALS
+            && RewardConfig::load_split(&ctx.accounts.reward_config, &ctx.accounts.config.mint)?
+                .is_none(),
+        ErrorCode::RewardTokenMismatch
+    );
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+
+    let amount = uncompounded_rewards(
+        stake_account,
+        current_time,
+        RewardWindow::from_config(&ctx.accounts.config),
+    )?;
+    require!(amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
+    require!(amount <= MAX_STAKE_AMOUNT, ErrorCode::AmountTooLarge);
+
+    // The rewards stay in the vault as the new principal, so it must already cover them
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.check_reward_cap(amount, ctx.accounts.config.max_total_rewards)?;
+    let backed_total = global_stats
+        .total_staked
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        ctx.accounts.vault.amount >= backed_total,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    // Checkpoint the original position; final unstake deducts what was compounded
+    let previous_compound_at = stake_account.last_compound_at;
+    stake_account.last_compound_at = current_time;
+    stake_account.compounded_rewards = stake_account
+        .compounded_rewards
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    // What was compounded is no longer owed by the original position; its share of
+    // the reservation moves over to the new one
+    reserve_position_rewards(
+        stake_account,
+        global_stats,
+        RewardWindow::from_config(&ctx.accounts.config),
+        ctx.accounts.vault.amount,
+    )?;
+
+    // Compounded rewards count as paid now; they're excluded from the final unstake
+    global_stats.total_rewards_paid = global_stats
+        .total_rewards_paid
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let user = ctx.accounts.user.key();
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    // No bonus snapshots on a compound
+    let new_stake_account = &mut ctx.accounts.new_stake_account;
+    open_position(
+        new_stake_account,
+        user,
+        ctx.bumps.new_stake_account,
+        &PositionTerms::plain(
+            amount,
+            lock_duration,
+            ctx.accounts.config.apy_tiers,
+            is_feature_enabled(flags, FEATURE_INTERPOLATED_APY),
+            current_time,
+        ),
+        &ctx.accounts.config,
+        &mut PositionBooks {
+            counter: &mut ctx.accounts.counter,
+            global_stats: &mut ctx.accounts.global_stats,
+            user_stake_stats: &mut ctx.accounts.user_stake_stats,
+            user_stats: &mut ctx.accounts.user_stats,
+        },
+        ctx.accounts.vault.amount,
+    )?;
+
+    emit!(RewardsCompoundedEvent {
+        user,
+        stake_index: stake_count,
+        new_stake_index: new_stake_account.stake_index,
+        amount,
+        previous_compound_at,
+        total_compounded: stake_account.compounded_rewards,
+        lock_duration,
+        apy_numerator: new_stake_account.apy_numerator,
+        apy_denominator: new_stake_account.apy_denominator,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Rewards compounded!");
+    msg!("Compounded: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("New stake index: #{}", new_stake_account.stake_index);
+    msg!("Duration: {} days", lock_duration / 86400);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::unstake::position_rewards;
+    use crate::state::test_stake_account;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // The fixture's 10,000 DEVR
+    const START: i64 = 1_700_000_000;
+    const NO_CAP: RewardWindow = RewardWindow {
+        accrual_delay: 0,
+        max_duration: MAX_LOCK_DURATION,
+        basis: REWARD_BASIS_COMMITTED,
+    };
+
+    #[test]
+    fn test_mid_lock_compound_then_both_positions_mature() {
+        let mut original = test_stake_account(START, SECONDS_IN_NINETY_DAYS);
+        let maturity = original.maturity_timestamp;
+        let (full_rewards, _, _) = position_rewards(&original, maturity, NO_CAP).unwrap();
+
+        // Halfway through the lock, half of the rewards have accrued
+        let mid = START + SECONDS_IN_NINETY_DAYS / 2;
+        let compounded = uncompounded_rewards(&original, mid, NO_CAP).unwrap();
+        assert!(compounded.abs_diff(full_rewards / 2) <= 1);
+
+        original.last_compound_at = mid;
+        original.compounded_rewards = compounded;
+        assert_eq!(uncompounded_rewards(&original, mid, NO_CAP).unwrap(), 0);
+
+        // The compounded rewards lock on their own clock
+        let new_position = StakeAccount {
+            staked_amount: compounded,
+            ..test_stake_account(mid, SECONDS_IN_THIRTY_DAYS)
+        };
+        let (new_rewards, _, _) =
+            position_rewards(&new_position, new_position.maturity_timestamp, NO_CAP).unwrap();
+        assert!(new_rewards > 0);
+
+        // Original pays only what wasn't compounded - nothing is counted twice
+        let (remaining, _, _) = position_rewards(&original, maturity, NO_CAP).unwrap();
+        assert_eq!(remaining + compounded, full_rewards);
+        assert_eq!(original.staked_amount, PRINCIPAL);
+        assert_eq!(original.staked_at, START);
+    }
+
+    #[test]
+    fn test_accrual_stops_at_maturity() {
+        let original = test_stake_account(START, SECONDS_IN_THIRTY_DAYS);
+        let maturity = original.maturity_timestamp;
+        let (full_rewards, _, _) = position_rewards(&original, maturity, NO_CAP).unwrap();
+
+        assert_eq!(uncompounded_rewards(&original, START, NO_CAP).unwrap(), 0);
+        assert_eq!(uncompounded_rewards(&original, maturity, NO_CAP).unwrap(), full_rewards);
+        assert_eq!(uncompounded_rewards(&original, maturity + 10 * 86_400, NO_CAP).unwrap(), full_rewards);
+    }
+
+    #[test]
+    fn test_repeated_compounds_only_take_new_accrual() {
+        let mut original = test_stake_account(START, SECONDS_IN_NINETY_DAYS);
+        let maturity = original.maturity_timestamp;
+        let (full_rewards, _, _) = position_rewards(&original, maturity, NO_CAP).unwrap();
+
+        let mut total = 0;
+        for day in [20, 45, 70] {
+            let now = START + day * 86_400;
+            let amount = uncompounded_rewards(&original, now, NO_CAP).unwrap();
+            original.last_compound_at = now;
+            original.compounded_rewards += amount;
+            total += amount;
+        }
+
+        let (remaining, _, _) = position_rewards(&original, maturity, NO_CAP).unwrap();
+        assert_eq!(total + remaining, full_rewards);
+    }
+}
@@ -1,7 +1,9 @@
+use crate::constants::{
+    MAX_METADATA_NAME_LENGTH, MAX_METADATA_SYMBOL_LENGTH, MAX_METADATA_URI_LENGTH,
+};
 use crate::error::ErrorCode;
 use crate::state::TokenConfig;
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token::Mint;
 use mpl_token_metadata::instructions::{
     CreateMetadataAccountV3Cpi, CreateMetadataAccountV3CpiAccounts,
@@ -57,23 +59,20 @@ pub struct CreateMetadata<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handler(
-    ctx: Context<CreateMetadata>,
-    name: String,
-    symbol: String,
-    uri: String,
-) -> Result<()> {
+/// Field checks shared by create and update, so metadata can't be edited into a
+/// state create would have rejected
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
     // Validate name
     require!(!name.is_empty(), ErrorCode::NameEmpty);
-    require!(name.len() <= 32, ErrorCode::NameTooLong);
+    require!(name.len() <= MAX_METADATA_NAME_LENGTH, ErrorCode::NameTooLong);
 
     // Validate symbol
     require!(!symbol.is_empty(), ErrorCode::SymbolEmpty);
-    require!(symbol.len() <= 10, ErrorCode::SymbolTooLong);
+    require!(symbol.len() <= MAX_METADATA_SYMBOL_LENGTH, ErrorCode::SymbolTooLong);
 
     // Validate URI
     require!(!uri.is_empty(), ErrorCode::UriEmpty);
-    require!(uri.len() <= 200, ErrorCode::UriTooLong);
+    require!(uri.len() <= MAX_METADATA_URI_LENGTH, ErrorCode::UriTooLong);
 
     // Validate URI format (must start with https:// or ipfs://)
     let uri_lower = uri.to_lowercase();
@@ -81,6 +80,16 @@ pub fn handler(
         uri_lower.starts_with("https://") || uri_lower.starts_with("ipfs://"),
         ErrorCode::InvalidUriFormat
     );
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<CreateMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    validate_metadata(&name, &symbol, &uri)?;
 
     msg!("Creating metadata for token: {}", ctx.accounts.mint.key());
     msg!("Name: {}, Symbol: {}, URI: {}", name, symbol, uri);
@@ -126,3 +135,41 @@ pub fn handler(
     msg!("✅ Metadata successfully created!");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "https://example.com/devr.json";
+
+    #[test]
+    fn test_limits_are_inclusive() {
+        let name = "A".repeat(MAX_METADATA_NAME_LENGTH);
+        let symbol = "B".repeat(MAX_METADATA_SYMBOL_LENGTH);
+        let uri = format!("ipfs://{}", "c".repeat(MAX_METADATA_URI_LENGTH - 7));
+        validate_metadata(&name, &symbol, &uri).unwrap();
+
+        assert_eq!(
+            validate_metadata(&format!("{}A", name), &symbol, URI).unwrap_err(),
+            error!(ErrorCode::NameTooLong)
+        );
+        assert_eq!(
+            validate_metadata(&name, &format!("{}B", symbol), URI).unwrap_err(),
+            error!(ErrorCode::SymbolTooLong)
+        );
+        assert_eq!(
+            validate_metadata(&name, &symbol, &format!("{}c", uri)).unwrap_err(),
+            error!(ErrorCode::UriTooLong)
+        );
+    }
+
+    #[test]
+    fn test_uri_scheme_is_case_insensitive() {
+        validate_metadata("DevRewards", "DEVR", "IPFS://bafybeigdyrzt").unwrap();
+        validate_metadata("DevRewards", "DEVR", "HTTPS://example.com/devr.json").unwrap();
+        assert_eq!(
+            validate_metadata("DevRewards", "DEVR", "http://example.com/devr.json").unwrap_err(),
+            error!(ErrorCode::InvalidUriFormat)
+        );
+    }
+}
@@ -1,29 +1,54 @@
+use super::transfer_tokens::check_transfer_amount;
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
 use crate::error::ErrorCode;
+use crate::events::DelegatedTransferEvent;
+use crate::state::{Blocklist, TokenConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-const MIN_TRANSFER: u64 = 1_000_000_000;      // 1 token minimum
-const MAX_TRANSFER: u64 = 10_000_000_000_000; // 10,000 tokens maximum
-
 #[derive(Accounts)]
 pub struct DelegatedTransfer<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
     #[account(mut)]
     pub from_token_account: Account<'info, TokenAccount>,
     #[account(mut)]
     pub to_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Recipient owner's blocklist PDA, validated by seeds. Only whether it
+    /// exists is read.
+    #[account(
+        seeds = [b"blocklist", to_token_account.owner.as_ref()],
+        bump
+    )]
+    pub recipient_blocklist: UncheckedAccount<'info>,
     pub delegate: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<DelegatedTransfer>, amount: u64) -> Result<()> {
-    require!(amount >= MIN_TRANSFER, ErrorCode::AmountTooSmall);
-    require!(amount <= MAX_TRANSFER, ErrorCode::AmountTooLarge);
+    let config = &ctx.accounts.config;
+    check_transfer_amount(amount, config.min_transfer, config.max_transfer)?;
+
+    require_keys_neq!(
+        ctx.accounts.from_token_account.key(),
+        ctx.accounts.to_token_account.key(),
+        ErrorCode::SelfTransfer
+    );
     
     require!(
         ctx.accounts.from_token_account.mint == ctx.accounts.to_token_account.mint,
         ErrorCode::MintMismatch
     );
+
+    require!(
+        !Blocklist::is_blocked(&ctx.accounts.recipient_blocklist),
+        ErrorCode::RecipientBlocked
+    );
     
     let cpi_accounts = Transfer {
         from: ctx.accounts.from_token_account.to_account_info(),
@@ -36,12 +61,20 @@ pub fn handler(ctx: Context<DelegatedTransfer>, amount: u64) -> Result<()> {
     
     // Transfer call (SPL Token Program validates delegation)
     token::transfer(cpi_ctx, amount)?;
+
+    emit!(DelegatedTransferEvent {
+        from: ctx.accounts.from_token_account.key(),
+        to: ctx.accounts.to_token_account.key(),
+        delegate: ctx.accounts.delegate.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
     msg!("✅ Delegated transfer successful!");
     msg!("Delegate: {}", ctx.accounts.delegate.key());
     msg!("From: {}", ctx.accounts.from_token_account.key());
     msg!("To: {}", ctx.accounts.to_token_account.key());
-    msg!("Amount: {} tokens", amount / 1_000_000_000);
+    msg!("Amount: {} tokens", to_ui_amount(amount, DEVR_DECIMALS));
     
     Ok(())
 }
\ No newline at end of file
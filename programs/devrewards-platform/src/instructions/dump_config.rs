@@ -0,0 +1,87 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::events::ConfigDumpEvent;
+use crate::state::{FeatureFlags, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct DumpConfig<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so the dump reports
+    /// the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<DumpConfig>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+    let feature_flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+
+    emit!(ConfigDumpEvent {
+        version: config.version,
+        mint: config.mint,
+        mint_authority: config.mint_authority,
+        admin: config.admin,
+        pending_admin: config.pending_admin,
+        vault: config.vault,
+        vault_authority: config.vault_authority,
+        daily_claim_amount: config.daily_claim_amount,
+        config_bump: config.config_bump,
+        mint_authority_bump: config.mint_authority_bump,
+        mint_bump: config.mint_bump,
+        vault_bump: config.vault_bump,
+        vault_authority_bump: config.vault_authority_bump,
+        global_stats_bump: config.global_stats_bump,
+        claim_tiers: config.claim_tiers,
+        max_total_delegation_bps: config.max_total_delegation_bps,
+        reward_decimals: config.reward_decimals,
+        circuit_breaker_window: config.circuit_breaker_window,
+        circuit_breaker_threshold: config.circuit_breaker_threshold,
+        epoch_start: config.epoch_start,
+        epoch_length: config.epoch_length,
+        max_unstake_per_tx: config.max_unstake_per_tx,
+        max_total_rewards: config.max_total_rewards,
+        max_reward_eligible_duration: config.max_reward_eligible_duration,
+        reward_accrual_delay: config.reward_accrual_delay,
+        min_reward_reserve: config.min_reward_reserve,
+        insurance_fee_bps: config.insurance_fee_bps,
+        delegation_cooldown: config.delegation_cooldown,
+        reward_basis: config.reward_basis,
+        early_unstake_penalty_bps: config.early_unstake_penalty_bps,
+        paused: config.paused,
+        max_streak_bonus_bps: config.max_streak_bonus_bps,
+        claim_cooldown_seconds: config.claim_cooldown_seconds,
+        max_lifetime_claim: config.max_lifetime_claim,
+        referral_bonus: config.referral_bonus,
+        min_transfer: config.min_transfer,
+        max_transfer: config.max_transfer,
+        allowlist_root: config.allowlist_root,
+        max_delegation_amount: config.max_delegation_amount,
+        unstake_delay: config.unstake_delay,
+        vault_balance: ctx.accounts.vault.amount,
+        feature_flags,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 Config dump");
+    msg!("Admin: {}", config.admin);
+    msg!("Vault balance: {} DEVR", to_ui_amount(ctx.accounts.vault.amount, DEVR_DECIMALS));
+    msg!("Feature flags: {:#x}", feature_flags);
+
+    Ok(())
+}
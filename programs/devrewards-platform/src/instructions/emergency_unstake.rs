@@ -0,0 +1,306 @@
+use super::unstake::{
+    is_payout_destination, record_withdrawal, settle_unstake, within_unstake_cap, PositionBooks,
+};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::EmergencyUnstakeEvent;
+use crate::state::{
+    FeatureFlags, GlobalStats, StakeAccount, StakeCounter, TokenConfig,
+    UserPreferences, UserStakeStats, UserStats,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct EmergencyUnstake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump = counter.bump
+    )]
+    pub counter: Account<'info, StakeCounter>,
+
+    /// CHECK: PDA derived from seeds. Loaded and closed manually, same as `unstake`.
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    // Destination for the principal left after the penalty (checked in the handler)
+    #[account(mut, token::mint = config.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    /// CHECK: Boost vault token account PDA, validated by seeds. Only debited when
+    /// the position carries a boost deposit.
+    #[account(
+        mut,
+        seeds = [b"boost-vault"],
+        bump
+    )]
+    pub boost_vault: UncheckedAccount<'info>,
+
+    /// CHECK: User preferences PDA, validated by seeds. Read manually since most users
+    /// never set a payout destination.
+    #[account(
+        seeds = [b"user-preferences", user.key().as_ref()],
+        bump
+    )]
+    pub user_preferences: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Principal an early exit forfeits; the rest is paid back
+pub fn early_unstake_penalty(staked_amount: u64, penalty_bps: u64) -> u64 {
+    ((staked_amount as u128 * penalty_bps as u128) / BASIS_POINTS as u128) as u64
+}
+
+/// Exits a position before maturity: all rewards and a share of principal are
+/// forfeited, and the penalty stays in the vault
+pub fn handler(ctx: Context<EmergencyUnstake>, stake_count: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let mut stake_account = StakeAccount::load_open(
+        &ctx.accounts.stake_account,
+        stake_count,
+        ctx.accounts.counter.stake_count,
+    )?;
+    require_keys_eq!(
+        stake_account.user,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+
+    require!(
+        is_payout_destination(
+            UserPreferences::load_payout_destination(&ctx.accounts.user_preferences)?,
+            ctx.accounts.user_token_account.key(),
+            ctx.accounts.user_token_account.owner,
+            ctx.accounts.user.key(),
+        ),
+        ErrorCode::PayoutDestinationMismatch
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+    // Matured positions unstake normally, with their rewards
+    require!(
+        !is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StakeMatured
+    );
+
+    let config = &ctx.accounts.config;
+    let staked_amount = stake_account.staked_amount;
+    let penalty_bps = config.early_unstake_penalty_bps;
+    let penalty = early_unstake_penalty(staked_amount, penalty_bps);
+    let returned = staked_amount - penalty;
+
+    require!(
+        within_unstake_cap(returned, config.max_unstake_per_tx),
+        ErrorCode::UnstakeTooLarge
+    );
+    require!(
+        ctx.accounts.vault.amount >= returned,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+
+    // Settled before any tokens move, like a normal unstake. The whole position
+    // leaves the staked total; no rewards are paid.
+    let boost_returned = stake_account.boost_amount;
+    settle_unstake(
+        &mut stake_account,
+        &mut PositionBooks {
+            counter: &mut ctx.accounts.counter,
+            global_stats: &mut ctx.accounts.global_stats,
+            user_stake_stats: &mut ctx.accounts.user_stake_stats,
+            user_stats: &mut ctx.accounts.user_stats,
+        },
+        staked_amount,
+        0,
+    )?;
+    stake_account.try_serialize(
+        &mut &mut ctx.accounts.stake_account.try_borrow_mut_data()?[..],
+    )?;
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.record_penalty(penalty)?;
+
+    // Early exits count toward the circuit breaker like any other withdrawal
+    record_withdrawal(
+        global_stats,
+        config,
+        FeatureFlags::load_flags(&ctx.accounts.feature_flags)?,
+        returned,
+        current_time,
+    )?;
+
+    let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+    let signer = &[&seeds[..]];
+
+    // The penalty simply isn't transferred out, so it stays in the vault
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer,
+        ),
+        returned,
+    )?;
+
+    // A boost deposit is the user's own, not a reward, so it comes back in full
+    if boost_returned > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.boost_vault.to_account_info(),
+                    to: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                signer,
+            ),
+            boost_returned,
+        )?;
+    }
+
+    StakeAccount::close(
+        ctx.accounts.stake_account.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+    )?;
+    ctx.accounts.counter.close_position();
+
+    let seconds_early = get_seconds_to_maturity(stake_account.maturity_timestamp, current_time);
+    emit!(EmergencyUnstakeEvent {
+        user: stake_account.user,
+        stake_index: stake_count,
+        principal: staked_amount,
+        penalty,
+        returned,
+        penalty_bps,
+        seconds_early,
+        boost_returned,
+        timestamp: current_time,
+    });
+
+    msg!("⚠️ Emergency unstake!");
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Principal: {} DEVR", to_ui_amount(staked_amount, DEVR_DECIMALS));
+    msg!("Penalty: {} DEVR", to_ui_amount(penalty, DEVR_DECIMALS));
+    msg!("Returned: {} DEVR", to_ui_amount(returned, DEVR_DECIMALS));
+    msg!("Days early: {}", seconds_early / 86400);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_global_stats;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+
+    #[test]
+    fn test_exit_halfway_through_the_lock() {
+        let staked_at = 1_700_000_000;
+        let maturity = get_maturity_timestamp(staked_at, SECONDS_IN_NINETY_DAYS).unwrap();
+        let halfway = staked_at + SECONDS_IN_NINETY_DAYS / 2;
+        assert!(!is_stake_matured(maturity, halfway));
+        assert_eq!(get_seconds_to_maturity(maturity, halfway), SECONDS_IN_NINETY_DAYS / 2);
+
+        // Default 10% of principal is kept; the other 90% goes back
+        let penalty = early_unstake_penalty(PRINCIPAL, DEFAULT_EARLY_UNSTAKE_PENALTY_BPS);
+        assert_eq!(penalty, 1_000_000_000_000);
+        assert_eq!(PRINCIPAL - penalty, 9_000_000_000_000);
+
+        // The whole principal leaves the staked total, the penalty is counted
+        let mut stats = test_global_stats(PRINCIPAL);
+        stats.record_unstake(PRINCIPAL, 0).unwrap();
+        stats.record_penalty(penalty).unwrap();
+        assert_eq!(stats.total_staked, 0);
+        assert_eq!(stats.total_rewards_paid, 0);
+        assert_eq!(stats.total_penalties_collected, penalty);
+    }
+
+    #[test]
+    fn test_penalty_bounds() {
+        assert_eq!(early_unstake_penalty(PRINCIPAL, 0), 0);
+        assert_eq!(early_unstake_penalty(PRINCIPAL, MAX_EARLY_UNSTAKE_PENALTY_BPS), PRINCIPAL / 2);
+        // Rounds down in the user's favour
+        assert_eq!(early_unstake_penalty(3, 5_000), 1);
+    }
+}
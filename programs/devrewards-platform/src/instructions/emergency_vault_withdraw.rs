@@ -0,0 +1,85 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::EmergencyWithdrawEvent;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct EmergencyVaultWithdraw<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // Recovery account - must be one the admin controls
+    #[account(mut, token::mint = config.mint, token::authority = admin)]
+    pub recipient: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Last-resort recovery while the program is paused for a critical bug. Unlike
+/// `sweep_excess_rewards` it may take staked principal and reserved rewards, so the
+/// stats are left as they are - they still record what is owed once funds return.
+pub fn handler(ctx: Context<EmergencyVaultWithdraw>, amount: u64) -> Result<()> {
+    require!(ctx.accounts.config.paused, ErrorCode::ProgramNotPaused);
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+
+    let vault_balance = ctx.accounts.vault.amount;
+    require!(
+        amount <= vault_balance,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.recipient.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let seeds = &[
+        b"vault-authority".as_ref(),
+        &[ctx.accounts.config.vault_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        amount,
+    )?;
+
+    let remaining = vault_balance - amount;
+    emit!(EmergencyWithdrawEvent {
+        admin: ctx.accounts.admin.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        vault_balance: remaining,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🚨 Emergency vault withdrawal!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Recipient: {}", ctx.accounts.recipient.key());
+    msg!("Vault balance: {} DEVR", to_ui_amount(remaining, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::constants::*;
+use crate::events::ParameterPreviewEvent;
+use crate::instructions::unstake::compute_rewards;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct EmitParameterPreview<'info> {
+    // Read-only: only used to make sure the program has been initialized
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+}
+
+/// Rewards a sample stake would earn when locked for exactly `lock_duration`
+fn preview_rewards(lock_duration: i64) -> Result<u64> {
+    let (apy_numerator, apy_denominator) = get_apy_for_duration(&default_apy_tiers(), lock_duration);
+    compute_rewards(PREVIEW_STAKE_AMOUNT, apy_numerator, apy_denominator, lock_duration)
+}
+
+pub fn handler(_ctx: Context<EmitParameterPreview>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Each tier is previewed at its minimum qualifying duration
+    let tier_1_rewards = preview_rewards(SECONDS_IN_SEVEN_DAYS)?;
+    let tier_2_rewards = preview_rewards(SECONDS_IN_THIRTY_DAYS)?;
+    let tier_3_rewards = preview_rewards(SECONDS_IN_NINETY_DAYS)?;
+
+    emit!(ParameterPreviewEvent {
+        sample_amount: PREVIEW_STAKE_AMOUNT,
+        tier_1_duration: SECONDS_IN_SEVEN_DAYS,
+        tier_1_rewards,
+        tier_2_duration: SECONDS_IN_THIRTY_DAYS,
+        tier_2_rewards,
+        tier_3_duration: SECONDS_IN_NINETY_DAYS,
+        tier_3_rewards,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 Reward parameter preview");
+    msg!("Sample stake: {} DEVR", to_ui_amount(PREVIEW_STAKE_AMOUNT, DEVR_DECIMALS));
+    msg!("Tier 1 (7 days): {} DEVR", to_ui_amount(tier_1_rewards, DEVR_DECIMALS));
+    msg!("Tier 2 (30 days): {} DEVR", to_ui_amount(tier_2_rewards, DEVR_DECIMALS));
+    msg!("Tier 3 (90 days): {} DEVR", to_ui_amount(tier_3_rewards, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+use super::unstake::{reserve_position_rewards, RewardWindow};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::ExtendStakeEvent;
+use crate::state::{is_feature_enabled, FeatureFlags, GlobalStats, StakeAccount, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct ExtendStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        constraint = stake_account.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    // Read-only: a longer lock must still be backed by spare reward liquidity
+    #[account(
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    pub user: Signer<'info>,
+}
+
+/// Lengthens a still-locked position in place, keeping its stake time and principal.
+/// The rate is re-derived for the new lock from the current schedule.
+pub fn handler(ctx: Context<ExtendStake>, stake_count: u64, additional_duration: i64) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+    require!(
+        !is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StakeMatured
+    );
+    // A zero extension is a no-op; a negative one fails the lock invariant (LockCannotShorten)
+    require!(additional_duration != 0, ErrorCode::InvalidLockExtension);
+
+    let previous_lock_duration = stake_account.lock_duration;
+    let lock_duration = previous_lock_duration
+        .checked_add(additional_duration)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    require!(
+        lock_duration <= MAX_LOCK_DURATION,
+        ErrorCode::DurationTooLong
+    );
+
+    stake_account.extend_lock(additional_duration)?;
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    stake_account.lock_in_apy(
+        &ctx.accounts.config.apy_tiers,
+        is_feature_enabled(flags, FEATURE_INTERPOLATED_APY),
+    );
+
+    // Re-reserve for the longer lock; only the difference moves
+    let reward_liability = reserve_position_rewards(
+        stake_account,
+        &mut ctx.accounts.global_stats,
+        RewardWindow::from_config(&ctx.accounts.config),
+        ctx.accounts.vault.amount,
+    )?;
+
+    emit!(ExtendStakeEvent {
+        user: ctx.accounts.user.key(),
+        stake_index: stake_count,
+        previous_lock_duration,
+        lock_duration,
+        maturity_timestamp: stake_account.maturity_timestamp,
+        apy_numerator: stake_account.apy_numerator,
+        apy_denominator: stake_account.apy_denominator,
+        reward_liability,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Stake extended!");
+    msg!("Stake index: #{}", stake_count);
+    msg!("Duration: {} -> {} days", previous_lock_duration / 86400, lock_duration / 86400);
+    msg!("APY: {}/{}", stake_account.apy_numerator, stake_account.apy_denominator);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::unstake::{max_position_rewards, position_rewards};
+    use crate::state::test_stake_account;
+
+    const START: i64 = 1_700_000_000;
+    const NO_CAP: RewardWindow = RewardWindow {
+        accrual_delay: 0,
+        max_duration: MAX_LOCK_DURATION,
+        basis: REWARD_BASIS_COMMITTED,
+    };
+
+    fn position(lock_duration: i64) -> StakeAccount {
+        test_stake_account(START, lock_duration)
+    }
+
+    #[test]
+    fn test_extension_reaches_the_higher_tier() {
+        let mut extended = position(MIN_LOCK_DURATION);
+        assert_eq!(
+            (extended.apy_numerator, extended.apy_denominator),
+            (TIER_1_APY_NUMERATOR, TIER_1_APY_DENOMINATOR)
+        );
+
+        extended
+            .extend_lock(SECONDS_IN_NINETY_DAYS - MIN_LOCK_DURATION)
+            .unwrap();
+        extended.lock_in_apy(&default_apy_tiers(), false);
+
+        // Same payout as having staked for 90 days in the first place
+        let direct = position(SECONDS_IN_NINETY_DAYS);
+        assert_eq!(extended.maturity_timestamp, direct.maturity_timestamp);
+        assert_eq!(
+            (extended.apy_numerator, extended.apy_denominator),
+            (TIER_3_APY_NUMERATOR, TIER_3_APY_DENOMINATOR)
+        );
+        let (extended_rewards, _, _) =
+            position_rewards(&extended, extended.maturity_timestamp, NO_CAP).unwrap();
+        let (direct_rewards, _, _) =
+            position_rewards(&direct, direct.maturity_timestamp, NO_CAP).unwrap();
+        assert_eq!(extended_rewards, direct_rewards);
+    }
+
+    #[test]
+    fn test_extension_grows_the_reservation() {
+        let mut stake_account = position(SECONDS_IN_THIRTY_DAYS);
+        let before = max_position_rewards(&stake_account, NO_CAP).unwrap();
+
+        stake_account.extend_lock(SECONDS_IN_THIRTY_DAYS).unwrap();
+        stake_account.lock_in_apy(&default_apy_tiers(), false);
+        assert!(max_position_rewards(&stake_account, NO_CAP).unwrap() > before);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{StakeFrozenEvent, StakeUnfrozenEvent};
+use crate::state::{StakeAccount, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, stake_count: u64)]
+pub struct FreezeStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Position under review
+    #[account(
+        mut,
+        seeds = [b"stake", user.as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn freeze_handler(ctx: Context<FreezeStake>, user: Pubkey, stake_count: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+
+    // One review window per position, so a stake can never be held indefinitely
+    require!(stake_account.frozen_at == 0, ErrorCode::StakeAlreadyFrozen);
+
+    stake_account.frozen = true;
+    stake_account.frozen_at = clock.unix_timestamp;
+
+    let frozen_until = clock.unix_timestamp + MAX_FREEZE_DURATION;
+
+    emit!(StakeFrozenEvent {
+        user,
+        stake_index: stake_count,
+        admin: ctx.accounts.admin.key(),
+        frozen_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🧊 Stake frozen for review!");
+    msg!("User: {}", user);
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Frozen until: {}", frozen_until);
+
+    Ok(())
+}
+
+pub fn unfreeze_handler(ctx: Context<FreezeStake>, user: Pubkey, stake_count: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+
+    require!(stake_account.frozen, ErrorCode::StakeNotFrozen);
+
+    stake_account.frozen = false;
+    // Ends the reward-excluded interval (a lapsed hold is capped when rewards are computed)
+    stake_account.unfrozen_at = clock.unix_timestamp;
+
+    emit!(StakeUnfrozenEvent {
+        user,
+        stake_index: stake_count,
+        admin: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Stake cleared from review!");
+    msg!("User: {}", user);
+    msg!("Stake Index: #{}", stake_count);
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::VaultFundedEvent;
+use crate::state::{GlobalStats, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct FundVault<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut, token::mint = config.mint, token::authority = funder)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    // Anyone may fund - the deposit only ever adds reward liquidity
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposit DEVR into the vault so unstakes can be paid principal plus rewards.
+/// Unlike `fund_reward_vault`, this registers no LP share.
+pub fn handler(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    require!(
+        ctx.accounts.funder_token_account.amount >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.record_rewards_funded(amount)?;
+
+    ctx.accounts.vault.reload()?;
+    emit!(VaultFundedEvent {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        vault_balance: ctx.accounts.vault.amount,
+        total_rewards_funded: global_stats.total_rewards_funded,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Vault funded!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Total funded: {} DEVR", to_ui_amount(global_stats.total_rewards_funded, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::*;
+    use crate::instructions::insurance_fund::cover_shortfall;
+    use crate::instructions::unstake::compute_rewards;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+
+    #[test]
+    fn test_funding_lets_a_short_vault_pay_rewards() {
+        // Tier 3 for 90 days: the vault holds only the principal it was given
+        let rewards = compute_rewards(
+            PRINCIPAL,
+            TIER_3_APY_NUMERATOR,
+            TIER_3_APY_DENOMINATOR,
+            SECONDS_IN_NINETY_DAYS,
+        )
+        .unwrap();
+        let owed = PRINCIPAL + rewards;
+        let (_, _, uncovered) = cover_shortfall(owed, PRINCIPAL, 0);
+        assert_eq!(uncovered, rewards);
+
+        // Funding the rewards up front makes the same unstake payable in full
+        let (from_vault, from_fund, uncovered) = cover_shortfall(owed, PRINCIPAL + rewards, 0);
+        assert_eq!((from_vault, from_fund, uncovered), (owed, 0, 0));
+    }
+}
@@ -0,0 +1,122 @@
+use super::unstake::{position_rewards, RewardWindow};
+use crate::constants::{convert_decimals, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::state::{StakeAccount, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct GetPendingRewards<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        has_one = user
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: Stake owner, only used to derive the stake PDA
+    pub user: UncheckedAccount<'info>,
+}
+
+/// Rewards a full unstake would pay at `now`, in reward-token units and before any
+/// insurance fee. Same math as `unstake`: the committed basis quotes the whole lock,
+/// the elapsed basis only what has accrued so far.
+pub fn pending_rewards(
+    stake_account: &StakeAccount,
+    now: i64,
+    window: RewardWindow,
+    reward_decimals: u8,
+) -> Result<u64> {
+    let (principal_rewards, _, _) = position_rewards(stake_account, now, window)?;
+    convert_decimals(principal_rewards, DEVR_DECIMALS, reward_decimals)
+        .ok_or(error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Read-only quote for clients - call it with `.view()` / `simulate`
+pub fn handler(ctx: Context<GetPendingRewards>, stake_count: u64) -> Result<u64> {
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+
+    let rewards = pending_rewards(
+        &ctx.accounts.stake_account,
+        clock.unix_timestamp,
+        RewardWindow::from_config(config),
+        config.reward_decimals,
+    )?;
+
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Pending rewards: {}", rewards);
+
+    Ok(rewards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+    use crate::instructions::unstake::compute_rewards;
+    use crate::state::test_stake_account;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+    const START: i64 = 1_700_000_000;
+
+    fn position() -> StakeAccount {
+        test_stake_account(START, SECONDS_IN_THIRTY_DAYS)
+    }
+
+    fn window(basis: u8) -> RewardWindow {
+        RewardWindow {
+            accrual_delay: 0,
+            max_duration: DEFAULT_MAX_REWARD_ELIGIBLE_DURATION,
+            basis,
+        }
+    }
+
+    #[test]
+    fn test_committed_basis_quotes_the_whole_lock() {
+        let full = compute_rewards(
+            PRINCIPAL,
+            TIER_2_APY_NUMERATOR,
+            TIER_2_APY_DENOMINATOR,
+            SECONDS_IN_THIRTY_DAYS,
+        )
+        .unwrap();
+
+        // Same quote on day one as at maturity
+        let committed = window(REWARD_BASIS_COMMITTED);
+        assert_eq!(pending_rewards(&position(), START, committed, DEVR_DECIMALS).unwrap(), full);
+        assert_eq!(
+            pending_rewards(&position(), START + SECONDS_IN_THIRTY_DAYS, committed, DEVR_DECIMALS)
+                .unwrap(),
+            full
+        );
+
+        // The elapsed basis only quotes what has accrued
+        let halfway = START + SECONDS_IN_THIRTY_DAYS / 2;
+        let accrued = pending_rewards(&position(), halfway, window(REWARD_BASIS_ELAPSED), DEVR_DECIMALS)
+            .unwrap();
+        assert!(accrued.abs_diff(full / 2) <= 1);
+    }
+
+    #[test]
+    fn test_quote_excludes_compounded_rewards_and_uses_reward_decimals() {
+        let committed = window(REWARD_BASIS_COMMITTED);
+        let full = pending_rewards(&position(), START, committed, DEVR_DECIMALS).unwrap();
+
+        let mut compounded = position();
+        compounded.compounded_rewards = full / 4;
+        assert_eq!(
+            pending_rewards(&compounded, START, committed, DEVR_DECIMALS).unwrap(),
+            full - full / 4
+        );
+
+        // A 6-decimal reward token is quoted in its own units
+        assert_eq!(pending_rewards(&position(), START, committed, 6).unwrap(), full / 1_000);
+    }
+}
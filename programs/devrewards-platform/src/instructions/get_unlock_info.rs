@@ -0,0 +1,91 @@
+use crate::constants::{get_seconds_to_unlock, is_stake_matured};
+use crate::state::StakeAccount;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct GetUnlockInfo<'info> {
+    #[account(
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        has_one = user
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: Stake owner, only used to derive the stake PDA
+    pub user: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct UnlockInfo {
+    pub unlock_timestamp: i64,  // Stored maturity_timestamp (staked_at + lock_duration)
+    pub seconds_remaining: i64, // Until the unstake gate opens, 0 once it has
+    pub is_unlocked: bool,      // Whether unstake's StillLocked check passes now
+}
+
+/// Lock countdown at `now`, shared with time_to_maturity. Both fields follow the
+/// unstake gate, so the gate opens UNSTAKE_SKEW_TOLERANCE seconds before
+/// `unlock_timestamp`.
+pub fn unlock_info(stake_account: &StakeAccount, now: i64) -> UnlockInfo {
+    UnlockInfo {
+        unlock_timestamp: stake_account.maturity_timestamp,
+        seconds_remaining: get_seconds_to_unlock(stake_account.maturity_timestamp, now),
+        is_unlocked: is_stake_matured(stake_account.maturity_timestamp, now),
+    }
+}
+
+/// Read-only countdown for clients - call it with `.view()` / `simulate`
+pub fn handler(ctx: Context<GetUnlockInfo>, stake_count: u64) -> Result<UnlockInfo> {
+    let info = unlock_info(&ctx.accounts.stake_account, Clock::get()?.unix_timestamp);
+
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Unlocks at: {}", info.unlock_timestamp);
+    msg!("Seconds remaining: {}", info.seconds_remaining);
+    msg!("Unlocked: {}", info.is_unlocked);
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+    use crate::state::test_stake_account;
+
+    const START: i64 = 1_700_000_000;
+
+    fn position() -> StakeAccount {
+        test_stake_account(START, SECONDS_IN_THIRTY_DAYS)
+    }
+
+    #[test]
+    fn test_locked_position_counts_down() {
+        let info = unlock_info(&position(), START);
+        assert_eq!(info.unlock_timestamp, START + SECONDS_IN_THIRTY_DAYS);
+        assert_eq!(info.seconds_remaining, SECONDS_IN_THIRTY_DAYS - UNSTAKE_SKEW_TOLERANCE);
+        assert!(!info.is_unlocked);
+    }
+
+    #[test]
+    fn test_past_the_lock_is_unlocked() {
+        let unlock_at = START + SECONDS_IN_THIRTY_DAYS;
+        for now in [unlock_at, unlock_at + 1, unlock_at + SECONDS_IN_NINETY_DAYS] {
+            let info = unlock_info(&position(), now);
+            assert_eq!(info.seconds_remaining, 0);
+            assert!(info.is_unlocked);
+        }
+    }
+
+    #[test]
+    fn test_unlock_follows_the_unstake_gate() {
+        // Inside the skew tolerance unstake already succeeds, so the getter agrees
+        let unlock_at = START + SECONDS_IN_THIRTY_DAYS;
+        let info = unlock_info(&position(), unlock_at - UNSTAKE_SKEW_TOLERANCE);
+        assert!(info.is_unlocked);
+        assert_eq!(info.seconds_remaining, 0);
+
+        let info = unlock_info(&position(), unlock_at - UNSTAKE_SKEW_TOLERANCE - 1);
+        assert!(!info.is_unlocked);
+        assert_eq!(info.seconds_remaining, 1);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::error::ErrorCode;
+use crate::governance::VotePower;
+use crate::state::StakeAccount;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetVotingPower<'info> {
+    /// CHECK: Voter whose positions are summed; every stake account must belong to it
+    pub user: UncheckedAccount<'info>,
+    // remaining_accounts: the user's StakeAccount PDAs, each at most once
+}
+
+/// Read-only voting power, returned as return data - call it with `.view()` / `simulate`.
+/// Sums only the positions passed in, so callers pass every open one.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, GetVotingPower<'info>>) -> Result<u64> {
+    let user = ctx.accounts.user.key();
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut voting_power: u64 = 0;
+
+    for stake_info in ctx.remaining_accounts.iter() {
+        require!(
+            !seen.contains(stake_info.key),
+            ErrorCode::DuplicateStakeAccount
+        );
+        seen.push(stake_info.key());
+
+        let stake_account = Account::<StakeAccount>::try_from(stake_info)?;
+        require_keys_eq!(stake_account.user, user, ErrorCode::Unauthorized);
+
+        voting_power = voting_power
+            .checked_add(VotePower::of(&stake_account)?)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+
+    msg!("User: {}", user);
+    msg!("Positions: {}", seen.len());
+    msg!("Voting power: {}", voting_power);
+
+    Ok(voting_power)
+}
@@ -0,0 +1,58 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
+use crate::error::ErrorCode;
+use crate::events::ApyHistoryEvent;
+use crate::state::{ApyHistory, ApyHistoryEntry, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitApyHistory<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ApyHistory::LEN,
+        seeds = [b"apy-history"],
+        bump
+    )]
+    pub apy_history: Account<'info, ApyHistory>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the rate-change log, seeded with the schedule currently in the config.
+/// Every later APY schedule change appends to it.
+pub fn handler(ctx: Context<InitApyHistory>) -> Result<()> {
+    let clock = Clock::get()?;
+    let apy_history = &mut ctx.accounts.apy_history;
+    apy_history.version = CURRENT_ACCOUNT_VERSION;
+    apy_history.bump = ctx.bumps.apy_history;
+
+    let entry = ApyHistoryEntry {
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+        tiers: ctx.accounts.config.apy_tiers,
+    };
+    let index = apy_history.record(entry)?;
+
+    emit!(ApyHistoryEvent {
+        index,
+        total_recorded: apy_history.total_recorded,
+        tiers: entry.tiers,
+        slot: entry.slot,
+        timestamp: entry.timestamp,
+    });
+
+    msg!("✅ APY history initialized!");
+    msg!("Recorded entry #{} at slot {}", apy_history.total_recorded, clock.slot);
+
+    Ok(())
+}
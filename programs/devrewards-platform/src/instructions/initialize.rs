@@ -1,3 +1,4 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
 use crate::state::{GlobalStats, TokenConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
@@ -67,26 +68,38 @@ pub struct Initialize<'info> {
 }
 
 pub fn handler(ctx: Context<Initialize>) -> Result<()> {
-    let config = &mut ctx.accounts.config;
-
-    config.mint = ctx.accounts.mint.key();
-    config.mint_authority = ctx.accounts.mint_authority.key();
-    config.admin = ctx.accounts.admin.key();
-    config.vault = ctx.accounts.vault.key();
-    config.vault_authority = ctx.accounts.vault_authority.key();
-    config.daily_claim_amount = 100_000_000_000;
-    config.config_bump = ctx.bumps.config;
-    config.mint_authority_bump = ctx.bumps.mint_authority;
-    config.mint_bump = ctx.bumps.mint;
-    config.vault_bump = ctx.bumps.vault;
-    config.vault_authority_bump = ctx.bumps.vault_authority;
-    config.global_stats_bump = ctx.bumps.global_stats;
+    ctx.accounts.config.set_inner(TokenConfig {
+        mint: ctx.accounts.mint.key(),
+        mint_authority: ctx.accounts.mint_authority.key(),
+        admin: ctx.accounts.admin.key(),
+        vault: ctx.accounts.vault.key(),
+        vault_authority: ctx.accounts.vault_authority.key(),
+        config_bump: ctx.bumps.config,
+        mint_authority_bump: ctx.bumps.mint_authority,
+        mint_bump: ctx.bumps.mint,
+        vault_bump: ctx.bumps.vault,
+        vault_authority_bump: ctx.bumps.vault_authority,
+        global_stats_bump: ctx.bumps.global_stats,
+        reward_decimals: ctx.accounts.mint.decimals, // Rewards paid in DEVR by default
+        ..TokenConfig::with_defaults()
+    });
+    let config = &ctx.accounts.config;
 
     // Initialize global stats
     let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.version = CURRENT_ACCOUNT_VERSION;
     global_stats.total_staked = 0;
     global_stats.total_stakes = 0;
     global_stats.total_rewards_paid = 0;
+    global_stats.total_unstaked_principal = 0;
+    global_stats.staking_paused = false;
+    global_stats.peak_total_staked = 0;
+    global_stats.insurance_fund_balance = 0;
+    global_stats.insurance_fund_used = 0;
+    global_stats.total_penalties_collected = 0;
+    global_stats.total_rewards_funded = 0;
+    global_stats.total_reward_liability = 0;
+    global_stats.total_minted = 0;
     global_stats.bump = ctx.bumps.global_stats;
 
     msg!("✅ DevRewards initialized!");
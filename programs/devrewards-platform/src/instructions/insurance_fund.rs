@@ -0,0 +1,157 @@
+use crate::constants::{BASIS_POINTS, CONFIG_FIELD_INSURANCE_FEE_BPS, MAX_INSURANCE_FEE_BPS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, InsuranceFeeUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct InitInsuranceFund<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Backstop for reward-vault shortfalls, controlled by the vault authority
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"insurance-fund"],
+        bump
+    )]
+    pub insurance_fund: Account<'info, TokenAccount>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA derived from seeds, only used as the fund's authority
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Reads the insurance fund if an admin has created it. Unstakes work without one;
+/// they just route no fees and have nothing to draw on.
+pub fn load_insurance_fund(info: &AccountInfo) -> Result<Option<TokenAccount>> {
+    if info.owner != &anchor_spl::token::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+
+    let data = info.try_borrow_data()?;
+    Ok(Some(TokenAccount::try_deserialize(&mut &data[..])?))
+}
+
+/// Cut of an unstake's rewards routed into the insurance fund
+pub fn insurance_fee(rewards: u64, insurance_fee_bps: u64) -> u64 {
+    ((rewards as u128 * insurance_fee_bps as u128) / BASIS_POINTS as u128) as u64
+}
+
+/// Splits what an unstake owes between the vault and the insurance fund.
+/// Returns (from_vault, from_fund, uncovered); the fund only pays the vault's shortfall.
+pub fn cover_shortfall(owed: u64, vault_balance: u64, fund_balance: u64) -> (u64, u64, u64) {
+    let from_vault = owed.min(vault_balance);
+    let shortfall = owed - from_vault;
+    let from_fund = shortfall.min(fund_balance);
+    (from_vault, from_fund, shortfall - from_fund)
+}
+
+pub fn init_handler(ctx: Context<InitInsuranceFund>) -> Result<()> {
+    msg!("✅ Insurance fund created!");
+    msg!("Fund: {}", ctx.accounts.insurance_fund.key());
+
+    Ok(())
+}
+
+/// Set the share of unstake rewards paid into the insurance fund (0 = off)
+pub fn set_fee_handler(ctx: Context<SetInsuranceFee>, insurance_fee_bps: u64) -> Result<()> {
+    require!(
+        insurance_fee_bps <= MAX_INSURANCE_FEE_BPS,
+        ErrorCode::InvalidInsuranceFee
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_fee = config.insurance_fee_bps;
+    config.insurance_fee_bps = insurance_fee_bps;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(InsuranceFeeUpdatedEvent {
+        admin,
+        insurance_fee_bps,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_INSURANCE_FEE_BPS, old_fee, insurance_fee_bps, admin, timestamp);
+
+    msg!("✅ Insurance fee updated!");
+    msg!("Fee: {} bps of rewards", insurance_fee_bps);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVR: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_fund_fully_covers_shortfall() {
+        // Vault is 20 DEVR short of a 110 DEVR payout; the fund holds 50
+        let (from_vault, from_fund, uncovered) = cover_shortfall(110 * DEVR, 90 * DEVR, 50 * DEVR);
+        assert_eq!(from_vault, 90 * DEVR);
+        assert_eq!(from_fund, 20 * DEVR);
+        assert_eq!(uncovered, 0);
+    }
+
+    #[test]
+    fn test_fund_partially_covers_shortfall() {
+        let (from_vault, from_fund, uncovered) = cover_shortfall(110 * DEVR, 90 * DEVR, 5 * DEVR);
+        assert_eq!(from_vault, 90 * DEVR);
+        assert_eq!(from_fund, 5 * DEVR);
+        assert_eq!(uncovered, 15 * DEVR);
+    }
+
+    #[test]
+    fn test_empty_fund_covers_nothing() {
+        let (from_vault, from_fund, uncovered) = cover_shortfall(110 * DEVR, 90 * DEVR, 0);
+        assert_eq!((from_vault, from_fund, uncovered), (90 * DEVR, 0, 20 * DEVR));
+    }
+
+    #[test]
+    fn test_fund_untouched_without_shortfall() {
+        let (from_vault, from_fund, uncovered) = cover_shortfall(110 * DEVR, 500 * DEVR, 50 * DEVR);
+        assert_eq!((from_vault, from_fund, uncovered), (110 * DEVR, 0, 0));
+    }
+
+    #[test]
+    fn test_insurance_fee() {
+        assert_eq!(insurance_fee(10 * DEVR, 500), DEVR / 2); // 5%
+        assert_eq!(insurance_fee(10 * DEVR, 0), 0);
+        assert_eq!(insurance_fee(u64::MAX, MAX_INSURANCE_FEE_BPS), u64::MAX / 10);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::constants::{to_ui_amount, CURRENT_ACCOUNT_VERSION, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::{LpContributionEvent, LpFeesDepositedEvent, LpRewardsClaimedEvent};
+use crate::state::{GlobalStats, LiquidityProvider, LpPool, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct FundRewardVault<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = LpPool::LEN,
+        seeds = [b"lp-pool"],
+        bump
+    )]
+    pub lp_pool: Account<'info, LpPool>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = LiquidityProvider::LEN,
+        seeds = [b"lp", funder.key().as_ref()],
+        bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(mut, token::mint = config.mint, token::authority = funder)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositLpFees<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"lp-pool"],
+        bump = lp_pool.bump
+    )]
+    pub lp_pool: Account<'info, LpPool>,
+
+    // Treasury holding fees owed to LPs, controlled by the vault authority
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        token::mint = mint,
+        token::authority = vault_authority,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA derived from seeds, only used as the treasury's authority
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = depositor)]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimLpRewards<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [b"lp-pool"],
+        bump = lp_pool.bump
+    )]
+    pub lp_pool: Account<'info, LpPool>,
+
+    #[account(
+        mut,
+        seeds = [b"lp", provider.key().as_ref()],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = lp_pool.treasury_bump
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign treasury transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = provider)]
+    pub provider_token_account: Account<'info, TokenAccount>,
+
+    pub provider: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Fund the reward vault and register the contribution for a share of LP fees
+pub fn fund_handler(ctx: Context<FundRewardVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    require!(
+        ctx.accounts.funder_token_account.amount >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+    ctx.accounts.global_stats.record_rewards_funded(amount)?;
+
+    let lp_pool = &mut ctx.accounts.lp_pool;
+    if lp_pool.bump == 0 {
+        lp_pool.version = CURRENT_ACCOUNT_VERSION;
+        lp_pool.bump = ctx.bumps.lp_pool;
+    }
+
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    if liquidity_provider.bump == 0 {
+        liquidity_provider.version = CURRENT_ACCOUNT_VERSION;
+        liquidity_provider.provider = ctx.accounts.funder.key();
+        liquidity_provider.bump = ctx.bumps.liquidity_provider;
+    }
+
+    liquidity_provider.add_contribution(amount, lp_pool.acc_fees_per_share)?;
+    lp_pool.total_contributed = lp_pool
+        .total_contributed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(LpContributionEvent {
+        provider: liquidity_provider.provider,
+        amount,
+        total_contributed: liquidity_provider.contributed,
+        pool_total_contributed: lp_pool.total_contributed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Reward vault funded!");
+    msg!("Provider: {}", liquidity_provider.provider);
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Pool total: {} DEVR", to_ui_amount(lp_pool.total_contributed, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+/// Route protocol fees to LPs, pro-rata to their contribution
+pub fn deposit_fees_handler(ctx: Context<DepositLpFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+    require!(
+        ctx.accounts.depositor_token_account.amount >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    let lp_pool = &mut ctx.accounts.lp_pool;
+    lp_pool.distribute(amount)?;
+    lp_pool.treasury_bump = ctx.bumps.treasury;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.depositor_token_account.to_account_info(),
+        to: ctx.accounts.treasury.to_account_info(),
+        authority: ctx.accounts.depositor.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+    emit!(LpFeesDepositedEvent {
+        depositor: ctx.accounts.depositor.key(),
+        amount,
+        acc_fees_per_share: lp_pool.acc_fees_per_share,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ LP fees deposited!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, ctx.accounts.mint.decimals));
+
+    Ok(())
+}
+
+pub fn claim_handler(ctx: Context<ClaimLpRewards>) -> Result<()> {
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.settle(ctx.accounts.lp_pool.acc_fees_per_share)?;
+
+    let amount = liquidity_provider.unclaimed_fees;
+    require!(amount > 0, ErrorCode::NoLpFeesToClaim);
+
+    liquidity_provider.unclaimed_fees = 0;
+    liquidity_provider.total_claimed = liquidity_provider
+        .total_claimed
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.treasury.to_account_info(),
+        to: ctx.accounts.provider_token_account.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let seeds = &[
+        b"vault-authority".as_ref(),
+        &[ctx.accounts.config.vault_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        amount,
+    )?;
+
+    emit!(LpRewardsClaimedEvent {
+        provider: liquidity_provider.provider,
+        amount,
+        total_claimed: liquidity_provider.total_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ LP rewards claimed!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Total claimed: {} DEVR", to_ui_amount(liquidity_provider.total_claimed, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,552 @@
+use crate::constants::{
+    default_apy_tiers, get_apy_for_duration, get_maturity_timestamp, MAX_MIGRATION_BATCH,
+    STAKE_ACCOUNT_VERSION,
+};
+use crate::error::ErrorCode;
+use crate::events::StakesMigratedEvent;
+use crate::instructions::migrate_global_accounts::{
+    grown_data, write_resized, UNVERSIONED_COUNTER_LENS, UNVERSIONED_USER_CLAIM_LENS,
+    UNVERSIONED_USER_STAKE_STATS_LENS,
+};
+use crate::state::{ApyHistory, StakeAccount, StakeCounter, UserClaim, UserStakeStats};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateAllStakes<'info> {
+    // Owner of every position in the batch; pays the extra rent for the larger layout
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: The user's counter PDA, validated by seeds. Read manually since a counter
+    /// written before unbonding_count and active_stakes no longer deserializes.
+    #[account(
+        mut,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump
+    )]
+    pub counter: UncheckedAccount<'info>,
+
+    /// CHECK: The user's claim record PDA, validated by seeds. Read manually, same as the counter.
+    #[account(
+        mut,
+        seeds = [b"user-claim", user.key().as_ref()],
+        bump
+    )]
+    pub user_claim: UncheckedAccount<'info>,
+
+    /// CHECK: The user's stake stats PDA, validated by seeds. Read manually, same as the counter.
+    #[account(
+        mut,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: UncheckedAccount<'info>,
+
+    /// CHECK: APY history PDA, validated by seeds. Read manually since it may not exist;
+    /// positions opened before it did were quoted the compiled-in tiers.
+    #[account(
+        seeds = [b"apy-history"],
+        bump
+    )]
+    pub apy_history: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the user's writable StakeAccount PDAs, legacy or versioned
+}
+
+/// StakeAccount as first deployed, before freezing and layout versioning
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyStakeAccount {
+    pub user: Pubkey,
+    pub staked_amount: u64,
+    pub staked_at: i64,
+    pub lock_duration: i64,
+    pub stake_index: u64,
+    pub bump: u8,
+}
+
+pub const LEGACY_STAKE_ACCOUNT_LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 1;
+/// The legacy layout plus frozen and frozen_at, still without a version byte
+pub const FROZEN_LEGACY_STAKE_ACCOUNT_LEN: usize = LEGACY_STAKE_ACCOUNT_LEN + 1 + 8;
+
+/// Bytes each StakeAccount version appended to the one before, from version 2 on
+const STAKE_ACCOUNT_GROWTH: [usize; STAKE_ACCOUNT_VERSION as usize - 1] = [
+    8 + 8, // 2: boost_amount, boost_multiplier_bps
+    8 + 8, // 3: apy_numerator, apy_denominator
+    8,     // 4: reward_liability
+    1,     // 5: is_withdrawn
+    8,     // 6: unstake_requested_at
+];
+
+/// Size of a StakeAccount written at `version`, or None for a version that never existed
+pub fn stake_account_len(version: u8) -> Option<usize> {
+    if version == 0 || version > STAKE_ACCOUNT_VERSION {
+        return None;
+    }
+    let appended_since: usize = STAKE_ACCOUNT_GROWTH[version as usize - 1..].iter().sum();
+    Some(StakeAccount::LEN - appended_since)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum StakeLayout {
+    Legacy,
+    Outdated(u8), // Versioned, but older than STAKE_ACCOUNT_VERSION
+    Current,
+    Unknown,
+}
+
+/// Tells a stake account's layout from its version byte. The size must match
+/// what that version wrote, so a truncated or mislabelled account is rejected.
+pub fn stake_layout(data: &[u8]) -> StakeLayout {
+    if data.len() < 8 || &data[..8] != StakeAccount::DISCRIMINATOR {
+        return StakeLayout::Unknown;
+    }
+    // The legacy layouts have no version byte
+    if data.len() == LEGACY_STAKE_ACCOUNT_LEN || data.len() == FROZEN_LEGACY_STAKE_ACCOUNT_LEN {
+        return StakeLayout::Legacy;
+    }
+
+    let version = data[8];
+    match stake_account_len(version) {
+        Some(len) if len == data.len() && version == STAKE_ACCOUNT_VERSION => StakeLayout::Current,
+        Some(len) if len == data.len() => StakeLayout::Outdated(version),
+        _ => StakeLayout::Unknown,
+    }
+}
+
+/// One migration step: fills in what version `from + 1` added to the layout.
+/// `history` is the APY log, None before an admin created it.
+fn upgrade_stake(stake_account: &mut StakeAccount, from: u8, history: Option<&ApyHistory>) {
+    match from {
+        // Boost deposits came later; an older position carries none
+        1 => {
+            stake_account.boost_amount = 0;
+            stake_account.boost_multiplier_bps = 0;
+        }
+        // Rates weren't locked in yet, so the position gets the schedule in effect
+        // when it was staked, not whatever it is now
+        2 => {
+            let tiers = history.map_or_else(default_apy_tiers, |history| {
+                history.tiers_at(stake_account.staked_at)
+            });
+            let (apy_numerator, apy_denominator) =
+                get_apy_for_duration(&tiers, stake_account.lock_duration);
+            stake_account.apy_numerator = apy_numerator;
+            stake_account.apy_denominator = apy_denominator;
+        }
+        // Opened before reservations existed, so nothing to release at unstake
+        3 => stake_account.reward_liability = 0,
+        // Still on chain, so its full unstake hasn't happened
+        4 => stake_account.is_withdrawn = false,
+        // request_unstake didn't exist yet, so no request is pending
+        5 => stake_account.unstake_requested_at = 0,
+        _ => {}
+    }
+    stake_account.version = from + 1;
+}
+
+/// Rebuilds a versioned position in the current layout, one version step at a time.
+/// Fields its version predates read as zero until their step fills them in.
+pub fn migrate_stake(
+    data: &[u8],
+    version: u8,
+    history: Option<&ApyHistory>,
+) -> Result<StakeAccount> {
+    let mut padded = data.to_vec();
+    padded.resize(StakeAccount::LEN, 0);
+    let mut stake_account = StakeAccount::try_deserialize(&mut &padded[..])?;
+    for from in version..STAKE_ACCOUNT_VERSION {
+        upgrade_stake(&mut stake_account, from, history);
+    }
+    Ok(stake_account)
+}
+
+/// Rebuilds a legacy position in the current layout. It becomes a version 1
+/// position (maturity derived from the lock, a freeze kept, later fields zero),
+/// then takes every version step from there.
+pub fn migrate_legacy_stake(data: &[u8], history: Option<&ApyHistory>) -> Result<StakeAccount> {
+    let legacy = LegacyStakeAccount::deserialize(&mut &data[8..])?;
+    // A review hold placed before versioning still holds
+    let (frozen, frozen_at) = if data.len() == FROZEN_LEGACY_STAKE_ACCOUNT_LEN {
+        <(bool, i64)>::deserialize(&mut &data[LEGACY_STAKE_ACCOUNT_LEN..])?
+    } else {
+        (false, 0)
+    };
+
+    let mut stake_account = StakeAccount {
+        version: 1,
+        user: legacy.user,
+        staked_amount: legacy.staked_amount,
+        staked_at: legacy.staked_at,
+        lock_duration: legacy.lock_duration,
+        maturity_timestamp: get_maturity_timestamp(legacy.staked_at, legacy.lock_duration)
+            .ok_or(ErrorCode::ArithmeticOverflow)?,
+        stake_index: legacy.stake_index,
+        bump: legacy.bump,
+        frozen,
+        frozen_at,
+        unfrozen_at: 0,
+        loyalty_bonus_bps: 0,
+        epoch_bonus_bps: 0,
+        last_compound_at: 0,
+        compounded_rewards: 0,
+        weighted_staked_seconds: 0,
+        boost_amount: 0,
+        boost_multiplier_bps: 0,
+        apy_numerator: 0,
+        apy_denominator: 0,
+        reward_liability: 0,
+        is_withdrawn: false,
+        unstake_requested_at: 0,
+    };
+    for from in 1..STAKE_ACCOUNT_VERSION {
+        upgrade_stake(&mut stake_account, from, history);
+    }
+    Ok(stake_account)
+}
+
+/// Grows one of the signer's own accounts to its current layout. False when it
+/// was never created or is already current.
+fn grow_user_account<'info, T: AccountSerialize>(
+    info: &AccountInfo<'info>,
+    defaults: &T,
+    unversioned_lens: &[usize],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<bool> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(false);
+    }
+
+    let grown = grown_data(&info.try_borrow_data()?, defaults, unversioned_lens)?;
+    match grown {
+        Some(grown) => {
+            write_resized(info, &grown, payer, system_program)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Migrate a batch of the signer's positions to the current layout in one go, and
+/// grow their counter, claim record and stake stats if those predate their latest
+/// fields. Positions already current are skipped, not failed.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateAllStakes<'info>>) -> Result<()> {
+    let user_info = ctx.accounts.user.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    // Nothing is unbonding or counted open yet; both counts saturate at zero on close
+    let counter_grown = grow_user_account(
+        &ctx.accounts.counter.to_account_info(),
+        &StakeCounter::default(),
+        &UNVERSIONED_COUNTER_LENS,
+        &user_info,
+        &system_program,
+    )?;
+    // No nonce used, no streak and no auto-claim opt-in yet
+    let user_claim_grown = grow_user_account(
+        &ctx.accounts.user_claim.to_account_info(),
+        &UserClaim::default(),
+        &UNVERSIONED_USER_CLAIM_LENS,
+        &user_info,
+        &system_program,
+    )?;
+    let user_stake_stats_grown = grow_user_account(
+        &ctx.accounts.user_stake_stats.to_account_info(),
+        &UserStakeStats::default(),
+        &UNVERSIONED_USER_STAKE_STATS_LENS,
+        &user_info,
+        &system_program,
+    )?;
+    let accounts_grown = counter_grown || user_claim_grown || user_stake_stats_grown;
+
+    let stakes = ctx.remaining_accounts;
+    require!(
+        stakes.len() <= MAX_MIGRATION_BATCH && (!stakes.is_empty() || accounts_grown),
+        ErrorCode::InvalidMigrationBatch
+    );
+
+    let user = ctx.accounts.user.key();
+    let history = ApyHistory::load_history(&ctx.accounts.apy_history)?;
+    let mut migrated: u64 = 0;
+
+    for stake_info in stakes.iter() {
+        require!(
+            stake_info.owner == ctx.program_id && stake_info.is_writable,
+            ErrorCode::InvalidStakeMigration
+        );
+
+        let stake_account = {
+            let data = stake_info.try_borrow_data()?;
+            match stake_layout(&data) {
+                StakeLayout::Current => continue,
+                StakeLayout::Legacy => migrate_legacy_stake(&data, history.as_ref())?,
+                StakeLayout::Outdated(version) => migrate_stake(&data, version, history.as_ref())?,
+                StakeLayout::Unknown => return err!(ErrorCode::InvalidStakeMigration),
+            }
+        };
+
+        // Only the owner's own positions, at their canonical PDA
+        require_keys_eq!(stake_account.user, user, ErrorCode::Unauthorized);
+        let expected = Pubkey::create_program_address(
+            &[
+                b"stake",
+                user.as_ref(),
+                &stake_account.stake_index.to_le_bytes(),
+                &[stake_account.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidStakeMigration))?;
+        require_keys_eq!(stake_info.key(), expected, ErrorCode::InvalidStakeMigration);
+
+        let mut data = Vec::with_capacity(StakeAccount::LEN);
+        stake_account.try_serialize(&mut data)?;
+        write_resized(stake_info, &data, &user_info, &system_program)?;
+
+        migrated += 1;
+    }
+
+    emit!(StakesMigratedEvent {
+        user,
+        requested: stakes.len() as u64,
+        migrated,
+        counter_grown,
+        user_claim_grown,
+        user_stake_stats_grown,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Stakes migrated!");
+    msg!("Migrated: {} / {}", migrated, stakes.len());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        APY_HISTORY_LEN, SECONDS_IN_THIRTY_DAYS, TIER_2_APY_DENOMINATOR, TIER_2_APY_NUMERATOR,
+    };
+    use crate::state::{test_stake_account, ApyHistoryEntry};
+
+    /// A log whose schedule changed at each (timestamp, tier 2 APY %)
+    fn history_with(checkpoints: &[(i64, u64)]) -> ApyHistory {
+        let mut history = ApyHistory {
+            version: 1,
+            head: 0,
+            count: 0,
+            total_recorded: 0,
+            entries: [ApyHistoryEntry::default(); APY_HISTORY_LEN],
+            bump: 255,
+        };
+        for &(timestamp, tier_2_apy) in checkpoints {
+            let mut tiers = default_apy_tiers();
+            tiers[1].apy_numerator = tier_2_apy;
+            history.record(ApyHistoryEntry { timestamp, slot: 0, tiers }).unwrap();
+        }
+        history
+    }
+
+    fn legacy_data(user: Pubkey, stake_index: u64) -> Vec<u8> {
+        let legacy = LegacyStakeAccount {
+            user,
+            staked_amount: 10_000_000_000,
+            staked_at: 1_700_000_000,
+            lock_duration: 604_800,
+            stake_index,
+            bump: 254,
+        };
+        let mut data = StakeAccount::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data
+    }
+
+    fn current_data(user: Pubkey, stake_index: u64) -> Vec<u8> {
+        let mut data = legacy_data(user, stake_index);
+        let stake_account = migrate_legacy_stake(&data, None).unwrap();
+        data.clear();
+        stake_account.try_serialize(&mut data).unwrap();
+        data.resize(StakeAccount::LEN, 0);
+        data
+    }
+
+    #[test]
+    fn test_mixed_batch_migrates_only_legacy() {
+        let user = Pubkey::new_unique();
+        let batch = [
+            legacy_data(user, 0),
+            current_data(user, 1),
+            legacy_data(user, 2),
+            current_data(user, 3),
+        ];
+
+        let layouts: Vec<StakeLayout> = batch.iter().map(|data| stake_layout(data)).collect();
+        assert_eq!(
+            layouts,
+            [
+                StakeLayout::Legacy,
+                StakeLayout::Current,
+                StakeLayout::Legacy,
+                StakeLayout::Current
+            ]
+        );
+        assert_eq!(LEGACY_STAKE_ACCOUNT_LEN, batch[0].len());
+    }
+
+    #[test]
+    fn test_legacy_stake_backfilled_to_current_layout() {
+        let user = Pubkey::new_unique();
+        let migrated = migrate_legacy_stake(&legacy_data(user, 3), None).unwrap();
+
+        assert_eq!(migrated.version, STAKE_ACCOUNT_VERSION);
+        assert_eq!(migrated.user, user);
+        assert_eq!(migrated.staked_amount, 10_000_000_000);
+        assert_eq!(migrated.stake_index, 3);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.maturity_timestamp, 1_700_000_000 + 604_800);
+        assert!(!migrated.frozen);
+        assert_eq!(migrated.compounded_rewards, 0);
+        // 7-day lock keeps the compiled-in tier 1 rate
+        assert_eq!((migrated.apy_numerator, migrated.apy_denominator), (5, 100));
+
+        // Round-trips through the current layout
+        let mut data = Vec::new();
+        migrated.try_serialize(&mut data).unwrap();
+        let reloaded = StakeAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.maturity_timestamp, migrated.maturity_timestamp);
+        assert_eq!(reloaded.user, user);
+    }
+
+    #[test]
+    fn test_frozen_legacy_stake_keeps_its_hold() {
+        let user = Pubkey::new_unique();
+        let mut data = legacy_data(user, 1);
+        (true, 1_700_050_000i64).serialize(&mut data).unwrap();
+        assert_eq!(data.len(), FROZEN_LEGACY_STAKE_ACCOUNT_LEN);
+        assert_eq!(stake_layout(&data), StakeLayout::Legacy);
+
+        let migrated = migrate_legacy_stake(&data, None).unwrap();
+        assert_eq!(migrated.version, STAKE_ACCOUNT_VERSION);
+        assert_eq!((migrated.user, migrated.stake_index), (user, 1));
+        assert!(migrated.frozen);
+        assert_eq!(migrated.frozen_at, 1_700_050_000);
+
+        // The original layout has no hold to keep
+        let migrated = migrate_legacy_stake(&legacy_data(user, 1), None).unwrap();
+        assert!(!migrated.frozen);
+        assert_eq!(migrated.frozen_at, 0);
+    }
+
+    /// A position as `version` wrote it: its own version byte, cut to that version's size
+    fn versioned_data(version: u8) -> Vec<u8> {
+        let stake_account = StakeAccount {
+            version,
+            boost_amount: 1_000_000_000,
+            boost_multiplier_bps: 1_000,
+            apy_numerator: 1,
+            apy_denominator: 1,
+            reward_liability: 90_000_000_000,
+            unstake_requested_at: 1_700_100_000,
+            ..test_stake_account(1_700_000_000, SECONDS_IN_THIRTY_DAYS)
+        };
+        let mut data = Vec::new();
+        stake_account.try_serialize(&mut data).unwrap();
+        data.truncate(stake_account_len(version).unwrap());
+        data
+    }
+
+    #[test]
+    fn test_each_version_is_sized_by_what_it_appended() {
+        assert_eq!(stake_account_len(STAKE_ACCOUNT_VERSION), Some(StakeAccount::LEN));
+        assert_eq!(stake_account_len(5), Some(StakeAccount::LEN - 8));
+        assert_eq!(stake_account_len(4), Some(StakeAccount::LEN - 8 - 1));
+        // Version 1 is the layout migrate_all_stakes first wrote: up to weighted_staked_seconds
+        assert_eq!(stake_account_len(1), Some(StakeAccount::LEN - 49));
+        assert_eq!(stake_account_len(0), None);
+        assert_eq!(stake_account_len(STAKE_ACCOUNT_VERSION + 1), None);
+    }
+
+    #[test]
+    fn test_every_outdated_version_migrates_step_by_step() {
+        for version in 1..STAKE_ACCOUNT_VERSION {
+            let data = versioned_data(version);
+            assert_eq!(stake_layout(&data), StakeLayout::Outdated(version));
+
+            let migrated = migrate_stake(&data, version, None).unwrap();
+            assert_eq!(migrated.version, STAKE_ACCOUNT_VERSION);
+            assert_eq!(migrated.staked_amount, 10_000_000_000_000);
+            assert_eq!(migrated.maturity_timestamp, 1_700_000_000 + SECONDS_IN_THIRTY_DAYS);
+
+            // Fields the version already had survive; later ones take their step's value
+            let had = |added_in: u8| version >= added_in;
+            assert_eq!(migrated.boost_amount, if had(2) { 1_000_000_000 } else { 0 });
+            let apy = (migrated.apy_numerator, migrated.apy_denominator);
+            assert_eq!(apy, if had(3) { (1, 1) } else { (TIER_2_APY_NUMERATOR, TIER_2_APY_DENOMINATOR) });
+            assert_eq!(migrated.reward_liability, if had(4) { 90_000_000_000 } else { 0 });
+            assert!(!migrated.is_withdrawn);
+            assert_eq!(migrated.unstake_requested_at, 0);
+
+            // The result is a readable current-layout account
+            let mut rewritten = Vec::new();
+            migrated.try_serialize(&mut rewritten).unwrap();
+            assert_eq!(stake_layout(&rewritten), StakeLayout::Current);
+        }
+    }
+
+    #[test]
+    fn test_pre_rate_positions_get_the_schedule_they_were_staked_under() {
+        let staked_at = 1_700_000_000;
+        // Tier 2 was 12% when the position opened, 8% by the time it migrates
+        let history = history_with(&[(staked_at - 100, 12), (staked_at + 100, 8)]);
+
+        for version in 1..3 {
+            let migrated = migrate_stake(&versioned_data(version), version, Some(&history)).unwrap();
+            assert_eq!((migrated.apy_numerator, migrated.apy_denominator), (12, 100));
+        }
+        // Already stored rates are never re-priced
+        let migrated = migrate_stake(&versioned_data(3), 3, Some(&history)).unwrap();
+        assert_eq!((migrated.apy_numerator, migrated.apy_denominator), (1, 1));
+
+        // A 7-day legacy lock is tier 1, unchanged by the log
+        let user = Pubkey::new_unique();
+        let legacy = migrate_legacy_stake(&legacy_data(user, 0), Some(&history)).unwrap();
+        assert_eq!((legacy.apy_numerator, legacy.apy_denominator), (5, 100));
+    }
+
+    #[test]
+    fn test_positions_older_than_the_log_get_the_compiled_in_schedule() {
+        let staked_at = 1_700_000_000;
+        let history = history_with(&[(staked_at + 100, 8)]);
+
+        let migrated = migrate_stake(&versioned_data(2), 2, Some(&history)).unwrap();
+        let apy = (migrated.apy_numerator, migrated.apy_denominator);
+        assert_eq!(apy, (TIER_2_APY_NUMERATOR, TIER_2_APY_DENOMINATOR));
+    }
+
+    #[test]
+    fn test_unknown_layouts_rejected() {
+        let user = Pubkey::new_unique();
+
+        // Not a stake account at all
+        let mut other = legacy_data(user, 0);
+        other[0] ^= 0xff;
+        assert_eq!(stake_layout(&other), StakeLayout::Unknown);
+
+        // A stake account of some intermediate size
+        let mut truncated = current_data(user, 0);
+        truncated.truncate(StakeAccount::LEN - 8);
+        assert_eq!(stake_layout(&truncated), StakeLayout::Unknown);
+        assert_eq!(stake_layout(&[]), StakeLayout::Unknown);
+
+        // A version byte that doesn't match the account's size
+        let mut mislabelled = versioned_data(3);
+        mislabelled[8] = 4;
+        assert_eq!(stake_layout(&mislabelled), StakeLayout::Unknown);
+        let mut future = current_data(user, 0);
+        future[8] = STAKE_ACCOUNT_VERSION + 1;
+        assert_eq!(stake_layout(&future), StakeLayout::Unknown);
+    }
+}
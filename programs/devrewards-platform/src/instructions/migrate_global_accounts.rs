@@ -0,0 +1,369 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
+use crate::error::ErrorCode;
+use crate::events::GlobalAccountsMigratedEvent;
+use crate::state::{ApyHistory, ApyTier, GlobalStats, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateGlobalAccounts<'info> {
+    /// CHECK: Config PDA, validated by seeds. Read manually since a config written
+    /// before its latest settings no longer deserializes as TokenConfig.
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: Global stats PDA, validated by seeds. Read manually, same as the config.
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump
+    )]
+    pub global_stats: UncheckedAccount<'info>,
+
+    // Where a config from before apy_tiers takes its schedule from, if an admin changed it
+    #[account(
+        seeds = [b"apy-history"],
+        bump = apy_history.bump
+    )]
+    pub apy_history: Option<Account<'info, ApyHistory>>,
+
+    // Checked against the stored config's admin; pays the rent for the larger layouts
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Sizes each account was written at before the version byte went in front of its
+// fields. No versioned layout is ever this short, so the size alone tells them apart.
+pub const UNVERSIONED_CONFIG_LENS: [usize; 2] = [
+    8 + 5 * 32 + 8 + 6,          // As first deployed
+    8 + 5 * 32 + 8 + 6 + 3 * 16, // With claim_tiers
+];
+pub const UNVERSIONED_GLOBAL_STATS_LENS: [usize; 1] = [8 + 3 * 8 + 1];
+pub const UNVERSIONED_COUNTER_LENS: [usize; 1] = [8 + 8 + 1];
+pub const UNVERSIONED_USER_CLAIM_LENS: [usize; 1] = [8 + 32 + 8 + 8 + 1];
+pub const UNVERSIONED_USER_STAKE_STATS_LENS: [usize; 1] = [8 + 32 + 8 + 1];
+
+/// An account written before the last fields of its layout were appended, grown to
+/// the current size. The bytes it has are kept and each appended field takes its
+/// value in `defaults`; a layout from before versioning (one of `unversioned_lens`)
+/// first gets its version byte. None when the account is already full size.
+pub fn grown_data<T: AccountSerialize>(
+    data: &[u8],
+    defaults: &T,
+    unversioned_lens: &[usize],
+) -> Result<Option<Vec<u8>>> {
+    let mut grown = Vec::new();
+    defaults.try_serialize(&mut grown)?;
+    require!(
+        data.len() >= 8 && data[..8] == grown[..8],
+        ErrorCode::InvalidAccountMigration
+    );
+    if data.len() >= grown.len() {
+        return Ok(None);
+    }
+
+    let mut old = data[..8].to_vec();
+    if unversioned_lens.contains(&data.len()) {
+        old.push(CURRENT_ACCOUNT_VERSION);
+    }
+    old.extend_from_slice(&data[8..]);
+    grown[..old.len()].copy_from_slice(&old);
+    Ok(Some(grown))
+}
+
+/// Whether a config of `len` bytes was written before `apy_tiers`, its last field
+pub fn predates_apy_tiers(len: usize) -> bool {
+    len <= TokenConfig::LEN - 3 * ApyTier::INIT_SPACE
+}
+
+/// Rewrites an account at the size of `data`, topping up its rent from `payer` first
+pub fn write_resized<'info>(
+    info: &AccountInfo<'info>,
+    data: &[u8],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(data.len());
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.clone(),
+            to: info.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program.clone(), cpi_accounts), top_up)?;
+    }
+
+    info.resize(data.len())?;
+    info.try_borrow_mut_data()?.copy_from_slice(data);
+    Ok(())
+}
+
+/// Grows the config and global stats written by an older program to the current
+/// layouts. Settings added since take their defaults; accounts already at full
+/// size are left alone.
+pub fn handler(ctx: Context<MigrateGlobalAccounts>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let global_stats_info = ctx.accounts.global_stats.to_account_info();
+    require!(
+        config_info.owner == ctx.program_id && global_stats_info.owner == ctx.program_id,
+        ErrorCode::InvalidAccountMigration
+    );
+
+    let config_len = config_info.data_len();
+    let mut grown_config =
+        grown_data(
+            &config_info.try_borrow_data()?,
+            &TokenConfig::with_defaults(),
+            &UNVERSIONED_CONFIG_LENS,
+        )?;
+    // The schedule used to live only in the log, so it carries over rather than
+    // resetting to the compiled-in tiers
+    let logged_tiers = ctx
+        .accounts
+        .apy_history
+        .as_ref()
+        .and_then(|history| history.latest());
+    if let (Some(grown), Some(entry)) = (grown_config.as_mut(), logged_tiers) {
+        if predates_apy_tiers(config_len) {
+            let mut config = TokenConfig::try_deserialize(&mut &grown[..])?;
+            config.apy_tiers = entry.tiers;
+            grown.clear();
+            config.try_serialize(grown)?;
+        }
+    }
+    let config = match &grown_config {
+        Some(grown) => TokenConfig::try_deserialize(&mut &grown[..])?,
+        None => TokenConfig::try_deserialize(&mut &config_info.try_borrow_data()?[..])?,
+    };
+    require_keys_eq!(
+        config.admin,
+        ctx.accounts.admin.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let global_stats_len = global_stats_info.data_len();
+    let grown_global_stats =
+        match grown_data(
+            &global_stats_info.try_borrow_data()?,
+            &GlobalStats::default(),
+            &UNVERSIONED_GLOBAL_STATS_LENS,
+        )? {
+            Some(grown) => {
+                // The all-time high starts from today's total, not from zero
+                let mut global_stats = GlobalStats::try_deserialize(&mut &grown[..])?;
+                global_stats.update_peak();
+                let mut data = Vec::with_capacity(grown.len());
+                global_stats.try_serialize(&mut data)?;
+                Some(data)
+            }
+            None => None,
+        };
+
+    let admin = ctx.accounts.admin.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    if let Some(grown) = &grown_config {
+        write_resized(&config_info, grown, &admin, &system_program)?;
+    }
+    if let Some(grown) = &grown_global_stats {
+        write_resized(&global_stats_info, grown, &admin, &system_program)?;
+    }
+
+    emit!(GlobalAccountsMigratedEvent {
+        admin: admin.key(),
+        config_len: config_len as u64,
+        global_stats_len: global_stats_len as u64,
+        config_grown: grown_config.is_some(),
+        global_stats_grown: grown_global_stats.is_some(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Global accounts migrated!");
+    msg!("Config: {} -> {} bytes", config_len, TokenConfig::LEN);
+    msg!("Global stats: {} -> {} bytes", global_stats_len, GlobalStats::LEN);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{
+        default_apy_tiers, DEFAULT_MAX_REWARD_ELIGIBLE_DURATION, MAX_TRANSFER_AMOUNT,
+        NO_REWARD_CAP, NO_UNSTAKE_CAP,
+    };
+    use crate::state::{test_global_stats, StakeCounter, UserClaim, UserStakeStats};
+
+    /// `account` as an older program wrote it: everything from `field_offset` on missing
+    fn written_before<T: AccountSerialize>(account: &T, field_offset: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.truncate(field_offset);
+        data
+    }
+
+    /// Like `written_before`, but from before the version byte existed
+    fn written_unversioned<T: AccountSerialize>(account: &T, field_offset: usize) -> Vec<u8> {
+        let mut data = written_before(account, field_offset);
+        data.remove(8);
+        data
+    }
+
+    #[test]
+    fn test_old_config_grows_into_default_settings() {
+        let admin = Pubkey::new_unique();
+        let config = TokenConfig {
+            admin,
+            daily_claim_amount: 42,
+            ..TokenConfig::with_defaults()
+        };
+        // As first versioned: up to and including claim_tiers
+        let old = written_before(&config, 8 + 1 + 5 * 32 + 8 + 6 + 3 * 16);
+
+        let grown = grown_data(&old, &TokenConfig::with_defaults(), &UNVERSIONED_CONFIG_LENS).unwrap().unwrap();
+        assert_eq!(grown.len(), TokenConfig::LEN);
+        let grown = TokenConfig::try_deserialize(&mut &grown[..]).unwrap();
+        // What the old config held is kept
+        assert_eq!(grown.admin, admin);
+        assert_eq!(grown.daily_claim_amount, 42);
+        // Settings it predates take their defaults, not zero, which would block unstakes,
+        // earn no rewards or cap rewards at nothing
+        assert_eq!(grown.max_unstake_per_tx, NO_UNSTAKE_CAP);
+        assert_eq!(grown.max_reward_eligible_duration, DEFAULT_MAX_REWARD_ELIGIBLE_DURATION);
+        assert_eq!(grown.max_total_rewards, NO_REWARD_CAP);
+        assert_eq!(grown.max_transfer, MAX_TRANSFER_AMOUNT);
+        assert_eq!(grown.apy_tiers, default_apy_tiers());
+        assert!(predates_apy_tiers(old.len()));
+    }
+
+    #[test]
+    fn test_config_with_a_schedule_keeps_it() {
+        let mut tiers = default_apy_tiers();
+        tiers[2].apy_numerator = 25;
+        let config = TokenConfig { apy_tiers: tiers, ..TokenConfig::with_defaults() };
+
+        let mut current = Vec::new();
+        config.try_serialize(&mut current).unwrap();
+        assert!(!predates_apy_tiers(current.len()));
+        assert!(grown_data(&current, &TokenConfig::with_defaults(), &UNVERSIONED_CONFIG_LENS).unwrap().is_none());
+
+        // Written just before apy_tiers was appended
+        let before = written_before(&config, TokenConfig::LEN - 3 * ApyTier::INIT_SPACE);
+        assert!(predates_apy_tiers(before.len()));
+    }
+
+    #[test]
+    fn test_old_global_stats_and_counter_grow_with_zeroes() {
+        // As first versioned: total_staked, total_stakes, total_rewards_paid, bump
+        let global_stats = test_global_stats(5_000);
+        let old = written_before(&global_stats, 8 + 1 + 3 * 8 + 1);
+        let grown = grown_data(&old, &GlobalStats::default(), &UNVERSIONED_GLOBAL_STATS_LENS).unwrap().unwrap();
+        let grown = GlobalStats::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!(grown.total_staked, 5_000);
+        assert_eq!(grown.total_reward_liability, 0);
+        assert_eq!(grown.total_minted, 0);
+
+        // Before unbonding_count and active_stakes
+        let counter = StakeCounter {
+            version: 1,
+            stake_count: 3,
+            bump: 254,
+            unbonding_count: 0,
+            active_stakes: 0,
+        };
+        let old = written_before(&counter, 8 + 1 + 8 + 1);
+        let grown = grown_data(&old, &StakeCounter::default(), &UNVERSIONED_COUNTER_LENS).unwrap().unwrap();
+        assert_eq!(grown.len(), StakeCounter::LEN);
+        let grown = StakeCounter::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!((grown.stake_count, grown.bump), (3, 254));
+        assert_eq!((grown.unbonding_count, grown.active_stakes), (0, 0));
+    }
+
+    #[test]
+    fn test_unversioned_accounts_get_their_version_byte() {
+        // As first deployed: keys, daily_claim_amount and bumps, no version byte
+        let admin = Pubkey::new_unique();
+        let config = TokenConfig { admin, daily_claim_amount: 42, ..TokenConfig::with_defaults() };
+        let old = written_unversioned(&config, 8 + 1 + 5 * 32 + 8 + 6);
+        assert_eq!(old.len(), UNVERSIONED_CONFIG_LENS[0]);
+        let grown = grown_data(&old, &TokenConfig::with_defaults(), &UNVERSIONED_CONFIG_LENS)
+            .unwrap()
+            .unwrap();
+        let grown = TokenConfig::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!(grown.version, CURRENT_ACCOUNT_VERSION);
+        assert_eq!(grown.admin, admin);
+        assert_eq!(grown.daily_claim_amount, 42);
+        assert_eq!(grown.max_transfer, MAX_TRANSFER_AMOUNT);
+
+        // With claim_tiers, still before versioning
+        let old = written_unversioned(&config, 8 + 1 + 5 * 32 + 8 + 6 + 3 * 16);
+        assert_eq!(old.len(), UNVERSIONED_CONFIG_LENS[1]);
+        let grown = grown_data(&old, &TokenConfig::with_defaults(), &UNVERSIONED_CONFIG_LENS)
+            .unwrap()
+            .unwrap();
+        let grown = TokenConfig::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!(grown.admin, admin);
+        assert_eq!(grown.claim_tiers[2].multiplier_bps, config.claim_tiers[2].multiplier_bps);
+
+        let old = written_unversioned(&test_global_stats(5_000), 8 + 1 + 3 * 8 + 1);
+        let grown = grown_data(&old, &GlobalStats::default(), &UNVERSIONED_GLOBAL_STATS_LENS)
+            .unwrap()
+            .unwrap();
+        let grown = GlobalStats::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!((grown.version, grown.total_staked, grown.bump), (1, 5_000, 255));
+
+        let counter = StakeCounter { version: 1, stake_count: 3, bump: 254, ..Default::default() };
+        let old = written_unversioned(&counter, 8 + 1 + 8 + 1);
+        let grown = grown_data(&old, &StakeCounter::default(), &UNVERSIONED_COUNTER_LENS)
+            .unwrap()
+            .unwrap();
+        let grown = StakeCounter::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!((grown.version, grown.stake_count, grown.bump), (1, 3, 254));
+
+        let user = Pubkey::new_unique();
+        let claim = UserClaim {
+            version: 1,
+            user,
+            last_claim_time: 1_700_000_000,
+            total_claimed: 500,
+            bump: 253,
+            ..Default::default()
+        };
+        let old = written_unversioned(&claim, 8 + 1 + 32 + 8 + 8 + 1);
+        let grown = grown_data(&old, &UserClaim::default(), &UNVERSIONED_USER_CLAIM_LENS)
+            .unwrap()
+            .unwrap();
+        assert_eq!(grown.len(), UserClaim::LEN);
+        let grown = UserClaim::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!((grown.version, grown.user, grown.bump), (1, user, 253));
+        assert_eq!((grown.last_claim_time, grown.total_claimed), (1_700_000_000, 500));
+        assert_eq!((grown.claim_nonce, grown.current_streak), (0, 0));
+
+        let stats = UserStakeStats { version: 1, user, total_active_staked: 700, bump: 252 };
+        let old = written_unversioned(&stats, UserStakeStats::LEN);
+        let grown = grown_data(&old, &UserStakeStats::default(), &UNVERSIONED_USER_STAKE_STATS_LENS)
+            .unwrap()
+            .unwrap();
+        let grown = UserStakeStats::try_deserialize(&mut &grown[..]).unwrap();
+        assert_eq!((grown.version, grown.user, grown.total_active_staked), (1, user, 700));
+    }
+
+    #[test]
+    fn test_full_size_and_foreign_accounts() {
+        let mut current = Vec::new();
+        StakeCounter::default().try_serialize(&mut current).unwrap();
+        assert!(grown_data(&current, &StakeCounter::default(), &UNVERSIONED_COUNTER_LENS).unwrap().is_none());
+
+        // Another account type is never grown into this one
+        assert_eq!(
+            grown_data(&current, &GlobalStats::default(), &UNVERSIONED_GLOBAL_STATS_LENS).unwrap_err(),
+            error!(ErrorCode::InvalidAccountMigration)
+        );
+        assert!(grown_data(&[], &GlobalStats::default(), &UNVERSIONED_GLOBAL_STATS_LENS).is_err());
+    }
+}
@@ -7,6 +7,69 @@ pub mod revoke_delegate;
 pub mod stake;
 pub mod unstake;
 pub mod create_metadata;
+pub mod close_token_account;
+pub mod emit_parameter_preview;
+pub mod freeze_stake;
+pub mod update_claim_tiers;
+pub mod time_to_maturity;
+pub mod set_delegation_cap;
+pub mod circuit_breaker;
+pub mod batch_add_to_allowlist;
+pub mod batch_transfer;
+pub mod set_feature_flags;
+pub mod lp_rewards;
+pub mod init_apy_history;
+pub mod unstake_and_restake;
+pub mod reclaim_orphan_stake;
+pub mod set_epoch_config;
+pub mod compound_rewards_to_new_stake;
+pub mod set_unstake_cap;
+pub mod auto_claim;
+pub mod validate_stake;
+pub mod set_reward_duration_cap;
+pub mod set_vault_authority;
+pub mod set_reward_accrual_delay;
+pub mod set_min_reward_reserve;
+pub mod sweep_excess_rewards;
+pub mod add_to_stake;
+pub mod blocklist;
+pub mod insurance_fund;
+pub mod migrate_all_stakes;
+pub mod migrate_global_accounts;
+pub mod apy_curve;
+pub mod set_delegation_cooldown;
+pub mod set_payout_destination;
+pub mod boost_vault;
+pub mod set_reward_basis;
+pub mod dump_config;
+pub mod update_config;
+pub mod set_early_unstake_penalty;
+pub mod emergency_unstake;
+pub mod set_pause;
+pub mod set_apy_tiers;
+pub mod fund_vault;
+pub mod extend_stake;
+pub mod set_max_streak_bonus;
+pub mod get_pending_rewards;
+pub mod admin_transfer;
+pub mod set_max_total_rewards;
+pub mod set_claim_cooldown;
+pub mod set_max_lifetime_claim;
+pub mod update_metadata;
+pub mod claim_and_stake;
+pub mod referral;
+pub mod slash_stake;
+pub mod get_voting_power;
+pub mod close_user_claim;
+pub mod emergency_vault_withdraw;
+pub mod set_reward_config;
+pub mod set_transfer_limits;
+pub mod set_allowlist_root;
+pub mod set_max_delegation_amount;
+pub mod get_unlock_info;
+pub mod snapshot_stats;
+pub mod request_unstake;
+pub mod set_unstake_delay;
 
 pub use initialize::*;
 pub use claim_tokens::*;
@@ -16,4 +79,67 @@ pub use delegated_transfer::*;
 pub use revoke_delegate::*;
 pub use stake::*;
 pub use unstake::*;
-pub use create_metadata::*;
\ No newline at end of file
+pub use create_metadata::*;
+pub use close_token_account::*;
+pub use emit_parameter_preview::*;
+pub use freeze_stake::*;
+pub use update_claim_tiers::*;
+pub use time_to_maturity::*;
+pub use set_delegation_cap::*;
+pub use circuit_breaker::*;
+pub use batch_add_to_allowlist::*;
+pub use batch_transfer::*;
+pub use set_feature_flags::*;
+pub use lp_rewards::*;
+pub use init_apy_history::*;
+pub use unstake_and_restake::*;
+pub use reclaim_orphan_stake::*;
+pub use set_epoch_config::*;
+pub use compound_rewards_to_new_stake::*;
+pub use set_unstake_cap::*;
+pub use auto_claim::*;
+pub use validate_stake::*;
+pub use set_reward_duration_cap::*;
+pub use set_vault_authority::*;
+pub use set_reward_accrual_delay::*;
+pub use set_min_reward_reserve::*;
+pub use sweep_excess_rewards::*;
+pub use add_to_stake::*;
+pub use blocklist::*;
+pub use insurance_fund::*;
+pub use migrate_all_stakes::*;
+pub use migrate_global_accounts::*;
+pub use apy_curve::*;
+pub use set_delegation_cooldown::*;
+pub use set_payout_destination::*;
+pub use boost_vault::*;
+pub use set_reward_basis::*;
+pub use dump_config::*;
+pub use update_config::*;
+pub use set_early_unstake_penalty::*;
+pub use emergency_unstake::*;
+pub use set_pause::*;
+pub use set_apy_tiers::*;
+pub use fund_vault::*;
+pub use extend_stake::*;
+pub use set_max_streak_bonus::*;
+pub use get_pending_rewards::*;
+pub use admin_transfer::*;
+pub use set_max_total_rewards::*;
+pub use set_claim_cooldown::*;
+pub use set_max_lifetime_claim::*;
+pub use update_metadata::*;
+pub use claim_and_stake::*;
+pub use referral::*;
+pub use slash_stake::*;
+pub use get_voting_power::*;
+pub use close_user_claim::*;
+pub use emergency_vault_withdraw::*;
+pub use set_reward_config::*;
+pub use set_transfer_limits::*;
+pub use set_allowlist_root::*;
+pub use set_max_delegation_amount::*;
+pub use get_unlock_info::*;
+pub use snapshot_stats::*;
+pub use request_unstake::*;
+pub use set_unstake_delay::*;
\ No newline at end of file
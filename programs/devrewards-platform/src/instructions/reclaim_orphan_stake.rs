@@ -0,0 +1,192 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::OrphanReclaimedEvent;
+use crate::state::{GlobalStats, StakeAccount, StakeCounter, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, stake_count: u64)]
+pub struct ReclaimOrphanStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.as_ref(), &stake_count.to_le_bytes()],
+        bump,
+        has_one = user @ ErrorCode::Unauthorized,
+        close = owner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: User's stake counter PDA, validated by seeds. It may never have been
+    /// created for an orphan, so it is read manually.
+    #[account(
+        seeds = [b"stake-counter", user.as_ref()],
+        bump
+    )]
+    pub counter: UncheckedAccount<'info>,
+
+    /// CHECK: Stake owner, receives the rent. Must match the `user` argument.
+    #[account(mut, address = user)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut, token::mint = config.mint, token::authority = owner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// A legitimate position always sits below the counter (stake bumps it in the
+/// same tx) and records the index it was created at.
+pub fn is_orphaned(stake_count: u64, recorded_index: u64, counter_stake_count: u64) -> bool {
+    stake_count >= counter_stake_count || recorded_index != stake_count
+}
+
+fn counter_stake_count(counter: &AccountInfo) -> Result<u64> {
+    if counter.owner != &crate::ID || counter.data_is_empty() {
+        return Ok(0);
+    }
+
+    let data = counter.try_borrow_data()?;
+    Ok(StakeCounter::try_deserialize(&mut &data[..])?.stake_count)
+}
+
+/// An orphan was never credited to total_staked, so its principal must come from
+/// vault surplus - never from tokens backing tracked positions or their reserved rewards
+pub fn check_reclaim_backing(
+    global_stats: &GlobalStats,
+    vault_balance: u64,
+    principal: u64,
+) -> Result<()> {
+    let remaining = vault_balance
+        .checked_sub(principal)
+        .ok_or(ErrorCode::InsufficientVaultBalance)?;
+    global_stats.check_vault_backing(remaining)
+}
+
+pub fn handler(ctx: Context<ReclaimOrphanStake>, user: Pubkey, stake_count: u64) -> Result<()> {
+    let stake_account = &ctx.accounts.stake_account;
+    let counter_stake_count = counter_stake_count(&ctx.accounts.counter)?;
+
+    require!(
+        is_orphaned(stake_count, stake_account.stake_index, counter_stake_count),
+        ErrorCode::StakeNotOrphaned
+    );
+
+    let principal = stake_account.staked_amount;
+    let global_stats = &mut ctx.accounts.global_stats;
+    check_reclaim_backing(global_stats, ctx.accounts.vault.amount, principal)?;
+
+    if principal > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            principal,
+        )?;
+    }
+
+    // Count the refund as returned principal so lifetime totals still reconcile
+    global_stats.total_unstaked_principal = global_stats
+        .total_unstaked_principal
+        .checked_add(principal)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    emit!(OrphanReclaimedEvent {
+        user,
+        stake_index: stake_count,
+        recorded_index: stake_account.stake_index,
+        counter_stake_count,
+        principal_returned: principal,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("🧹 Orphaned stake reclaimed!");
+    msg!("User: {}", user);
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Principal returned: {} DEVR", to_ui_amount(principal, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_global_stats;
+
+    #[test]
+    fn test_legitimate_position_is_not_orphaned() {
+        // Index 2 created normally, counter now at 5
+        assert!(!is_orphaned(2, 2, 5));
+    }
+
+    #[test]
+    fn test_index_at_or_past_counter_is_orphaned() {
+        assert!(is_orphaned(5, 5, 5));
+        assert!(is_orphaned(7, 7, 5));
+        // Counter never created
+        assert!(is_orphaned(0, 0, 0));
+    }
+
+    #[test]
+    fn test_mismatched_recorded_index_is_orphaned() {
+        assert!(is_orphaned(1, 3, 5));
+    }
+
+    #[test]
+    fn test_reclaim_never_touches_reserved_rewards() {
+        let mut global_stats = test_global_stats(1_000);
+        global_stats.total_reward_liability = 200;
+
+        // Surplus of 50 on top of principal and reservations
+        assert!(check_reclaim_backing(&global_stats, 1_250, 50).is_ok());
+        assert_eq!(
+            check_reclaim_backing(&global_stats, 1_250, 51).unwrap_err(),
+            error!(ErrorCode::InsufficientRewardReserve)
+        );
+        assert_eq!(
+            check_reclaim_backing(&global_stats, 900, 50).unwrap_err(),
+            error!(ErrorCode::InsufficientVaultBalance)
+        );
+        assert_eq!(
+            check_reclaim_backing(&global_stats, 40, 50).unwrap_err(),
+            error!(ErrorCode::InsufficientVaultBalance)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use crate::constants::{
+    to_ui_amount, CONFIG_FIELD_REFERRAL_BONUS, CURRENT_ACCOUNT_VERSION, DEVR_DECIMALS, MAX_REFERRAL_BONUS,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ReferralBonusUpdatedEvent};
+use crate::state::{Referral, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = referrer,
+        space = Referral::LEN,
+        seeds = [b"referral", referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetReferralBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Opens the referrer's tracking account so claims can name them
+pub fn register_handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    referral.version = CURRENT_ACCOUNT_VERSION;
+    referral.referrer = ctx.accounts.referrer.key();
+    referral.total_referred = 0;
+    referral.referral_rewards = 0;
+    referral.bump = ctx.bumps.referral;
+
+    msg!("✅ Referrer registered: {}", referral.referrer);
+
+    Ok(())
+}
+
+/// Set the bonus minted to a referrer per referred claim; 0 keeps counting referrals
+/// without paying anything
+pub fn set_bonus_handler(ctx: Context<SetReferralBonus>, referral_bonus: u64) -> Result<()> {
+    require!(
+        referral_bonus <= MAX_REFERRAL_BONUS,
+        ErrorCode::InvalidReferralBonus
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_bonus = config.referral_bonus;
+    config.referral_bonus = referral_bonus;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(ReferralBonusUpdatedEvent {
+        admin,
+        referral_bonus,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_REFERRAL_BONUS, old_bonus, referral_bonus, admin, timestamp);
+
+    msg!("✅ Referral bonus updated!");
+    msg!("Bonus: {} DEVR", to_ui_amount(referral_bonus, DEVR_DECIMALS));
+
+    Ok(())
+}
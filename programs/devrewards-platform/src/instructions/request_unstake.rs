@@ -0,0 +1,68 @@
+use crate::constants::is_stake_matured;
+use crate::error::ErrorCode;
+use crate::events::UnstakeRequestedEvent;
+use crate::state::{StakeAccount, StakeCounter, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump = counter.bump
+    )]
+    pub counter: Account<'info, StakeCounter>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        constraint = stake_account.user == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub user: Signer<'info>,
+}
+
+/// First phase of a two-phase unstake: starts the `unstake_delay` countdown on a
+/// matured position. `unstake` pays out once the delay has passed.
+pub fn handler(ctx: Context<RequestUnstake>, stake_count: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let stake_account = &mut ctx.accounts.stake_account;
+    // The delay runs after the lock, not alongside it
+    require!(
+        is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StillLocked
+    );
+
+    stake_account.request_unstake(current_time)?;
+    ctx.accounts.counter.begin_unbonding()?;
+
+    let unstake_delay = ctx.accounts.config.unstake_delay;
+    let withdrawable_at = current_time.saturating_add(unstake_delay);
+    emit!(UnstakeRequestedEvent {
+        user: stake_account.user,
+        stake_index: stake_count,
+        unstake_delay,
+        withdrawable_at,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Unstake requested!");
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Withdrawable at: {}", withdrawable_at);
+
+    Ok(())
+}
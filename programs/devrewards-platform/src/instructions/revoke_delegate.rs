@@ -1,3 +1,6 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
+use crate::events::DelegateRevokedEvent;
+use crate::state::DelegationRecord;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Revoke, Token, TokenAccount};
 
@@ -5,10 +8,22 @@ use anchor_spl::token::{self, Revoke, Token, TokenAccount};
 pub struct RevokeDelegate<'info> {
     #[account(mut)]
     pub token_account: Account<'info, TokenAccount>,
+
+    // Revoke time, checked against the delegation cooldown on the next approve
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = DelegationRecord::LEN,
+        seeds = [b"delegation-record", token_account.key().as_ref()],
+        bump
+    )]
+    pub delegation_record: Account<'info, DelegationRecord>,
     
+    #[account(mut)]
     pub owner: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
@@ -21,6 +36,21 @@ pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     
     token::revoke(cpi_ctx)?;
+
+    let delegation_record = &mut ctx.accounts.delegation_record;
+    if delegation_record.token_account == Pubkey::default() {
+        delegation_record.version = CURRENT_ACCOUNT_VERSION;
+        delegation_record.token_account = ctx.accounts.token_account.key();
+        delegation_record.bump = ctx.bumps.delegation_record;
+    }
+    let timestamp = Clock::get()?.unix_timestamp;
+    delegation_record.last_revoked_at = timestamp;
+
+    emit!(DelegateRevokedEvent {
+        owner: ctx.accounts.owner.key(),
+        token_account: ctx.accounts.token_account.key(),
+        timestamp,
+    });
     
     msg!("✅ Delegation revoked!");
     msg!("Token account: {}", ctx.accounts.token_account.key());
@@ -0,0 +1,48 @@
+use crate::constants::CONFIG_FIELD_ALLOWLIST_ROOT;
+use crate::error::ErrorCode;
+use crate::events::{AllowlistRootUpdatedEvent, ConfigPubkeyChangedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Restrict faucet claims to the wallets committed to by `allowlist_root`.
+/// The all-zero root opens the faucet to everyone again.
+pub fn handler(ctx: Context<SetAllowlistRoot>, allowlist_root: [u8; 32]) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_root = config.allowlist_root;
+    config.allowlist_root = allowlist_root;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(AllowlistRootUpdatedEvent {
+        admin,
+        previous_root,
+        allowlist_root,
+        timestamp,
+    });
+    // The root is 32 bytes, so it travels as the Pubkey-valued variant
+    emit!(ConfigPubkeyChangedEvent {
+        field: CONFIG_FIELD_ALLOWLIST_ROOT,
+        old_value: Pubkey::new_from_array(previous_root),
+        new_value: Pubkey::new_from_array(allowlist_root),
+        admin,
+        timestamp,
+    });
+
+    msg!("✅ Allowlist root updated!");
+    msg!("Root: {:?}", allowlist_root);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use crate::constants::{
+    BASIS_POINTS, CONFIG_FIELD_APY_TIER_DENOMINATOR, CONFIG_FIELD_APY_TIER_MIN_DURATION,
+    CONFIG_FIELD_APY_TIER_NUMERATOR, MAX_TIER_APY_BPS,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ApyHistoryEvent};
+use crate::state::{ApyHistory, ApyHistoryEntry, ApyTier, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetApyTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    // Logged when passed; the schedule itself lives in the config, so the log is optional
+    #[account(
+        mut,
+        seeds = [b"apy-history"],
+        bump = apy_history.bump
+    )]
+    pub apy_history: Option<Account<'info, ApyHistory>>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Thresholds must strictly increase from tier 1 to tier 3, and each rate must be
+/// a real fraction no higher than `MAX_TIER_APY_BPS`
+pub fn valid_apy_tiers(tiers: &[ApyTier; 3]) -> bool {
+    let rates_valid = tiers.iter().all(|tier| {
+        tier.apy_denominator > 0
+            && (tier.apy_numerator as u128 * BASIS_POINTS as u128)
+                <= MAX_TIER_APY_BPS as u128 * tier.apy_denominator as u128
+    });
+    let thresholds_ascending = tiers[0].min_duration >= 0
+        && tiers.windows(2).all(|pair| pair[0].min_duration < pair[1].min_duration);
+    rates_valid && thresholds_ascending
+}
+
+/// Replaces the APY schedule for new stakes. Open positions keep the rate they
+/// locked in; the change is appended to the history log once it exists.
+pub fn handler(ctx: Context<SetApyTiers>, tiers: [ApyTier; 3]) -> Result<()> {
+    require!(valid_apy_tiers(&tiers), ErrorCode::InvalidApyTiers);
+
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let old_tiers = config.apy_tiers;
+    config.apy_tiers = tiers;
+
+    let entry = ApyHistoryEntry {
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+        tiers,
+    };
+    if let Some(apy_history) = ctx.accounts.apy_history.as_mut() {
+        let index = apy_history.record(entry)?;
+        emit!(ApyHistoryEvent {
+            index,
+            total_recorded: apy_history.total_recorded,
+            tiers: entry.tiers,
+            slot: entry.slot,
+            timestamp: entry.timestamp,
+        });
+    }
+
+    let admin = ctx.accounts.admin.key();
+    for (i, (old, new)) in old_tiers.iter().zip(tiers.iter()).enumerate() {
+        emit_config_changed(
+            CONFIG_FIELD_APY_TIER_MIN_DURATION[i],
+            old.min_duration as u64,
+            new.min_duration as u64,
+            admin,
+            entry.timestamp,
+        );
+        emit_config_changed(
+            CONFIG_FIELD_APY_TIER_NUMERATOR[i],
+            old.apy_numerator,
+            new.apy_numerator,
+            admin,
+            entry.timestamp,
+        );
+        emit_config_changed(
+            CONFIG_FIELD_APY_TIER_DENOMINATOR[i],
+            old.apy_denominator,
+            new.apy_denominator,
+            admin,
+            entry.timestamp,
+        );
+    }
+
+    msg!("✅ APY tiers updated!");
+    for (i, tier) in tiers.iter().enumerate() {
+        msg!(
+            "Tier {}: {}/{} from {} days",
+            i + 1,
+            tier.apy_numerator,
+            tier.apy_denominator,
+            tier.min_duration / 86400
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{default_apy_tiers, get_apy_for_duration, SECONDS_IN_NINETY_DAYS};
+
+    #[test]
+    fn test_default_schedule_is_valid() {
+        assert!(valid_apy_tiers(&default_apy_tiers()));
+    }
+
+    #[test]
+    fn test_rejects_bad_rates() {
+        let mut tiers = default_apy_tiers();
+        tiers[1].apy_denominator = 0;
+        assert!(!valid_apy_tiers(&tiers));
+
+        // Exactly 100% is allowed, anything above is not
+        let mut tiers = default_apy_tiers();
+        tiers[2] = ApyTier { apy_numerator: 1, apy_denominator: 1, ..tiers[2] };
+        assert!(valid_apy_tiers(&tiers));
+        tiers[2].apy_numerator = 101;
+        tiers[2].apy_denominator = 100;
+        assert!(!valid_apy_tiers(&tiers));
+
+        tiers[2].apy_numerator = u64::MAX;
+        tiers[2].apy_denominator = 1;
+        assert!(!valid_apy_tiers(&tiers));
+    }
+
+    #[test]
+    fn test_rejects_unordered_thresholds() {
+        let mut tiers = default_apy_tiers();
+        tiers[1].min_duration = tiers[2].min_duration;
+        assert!(!valid_apy_tiers(&tiers));
+
+        let mut tiers = default_apy_tiers();
+        tiers[0].min_duration = -1;
+        assert!(!valid_apy_tiers(&tiers));
+    }
+
+    #[test]
+    fn test_new_schedule_is_quoted_for_new_stakes() {
+        let mut tiers = default_apy_tiers();
+        tiers[2].apy_numerator = 25;
+        assert!(valid_apy_tiers(&tiers));
+        assert_eq!(get_apy_for_duration(&tiers, SECONDS_IN_NINETY_DAYS), (25, 100));
+    }
+}
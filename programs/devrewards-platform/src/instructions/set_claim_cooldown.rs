@@ -0,0 +1,44 @@
+use crate::constants::{CONFIG_FIELD_CLAIM_COOLDOWN_SECONDS, MAX_CLAIM_COOLDOWN};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ClaimCooldownUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetClaimCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the minimum time between faucet claims, e.g. an hour on testnet and a day on mainnet
+pub fn handler(ctx: Context<SetClaimCooldown>, claim_cooldown_seconds: i64) -> Result<()> {
+    require!(
+        claim_cooldown_seconds > 0 && claim_cooldown_seconds <= MAX_CLAIM_COOLDOWN,
+        ErrorCode::InvalidClaimCooldown
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cooldown = config.claim_cooldown_seconds;
+    config.claim_cooldown_seconds = claim_cooldown_seconds;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(ClaimCooldownUpdatedEvent {
+        admin,
+        claim_cooldown_seconds,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_CLAIM_COOLDOWN_SECONDS, old_cooldown as u64, claim_cooldown_seconds as u64, admin, timestamp);
+
+    msg!("✅ Claim cooldown updated!");
+    msg!("Cooldown: {} seconds", claim_cooldown_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::{BASIS_POINTS, CONFIG_FIELD_MAX_TOTAL_DELEGATION_BPS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, DelegationCapUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDelegationCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetDelegationCap>, max_total_delegation_bps: u64) -> Result<()> {
+    require!(
+        max_total_delegation_bps > 0 && max_total_delegation_bps <= BASIS_POINTS,
+        ErrorCode::InvalidDelegationCap
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_total_delegation_bps;
+    config.max_total_delegation_bps = max_total_delegation_bps;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(DelegationCapUpdatedEvent {
+        admin,
+        max_total_delegation_bps,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_TOTAL_DELEGATION_BPS, old_cap, max_total_delegation_bps, admin, timestamp);
+
+    msg!("✅ Delegation cap updated!");
+    msg!("Max total delegation: {} bps", max_total_delegation_bps);
+
+    Ok(())
+}
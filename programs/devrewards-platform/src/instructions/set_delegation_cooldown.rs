@@ -0,0 +1,43 @@
+use crate::constants::{CONFIG_FIELD_DELEGATION_COOLDOWN, MAX_DELEGATION_COOLDOWN};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, DelegationCooldownUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDelegationCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetDelegationCooldown>, delegation_cooldown: i64) -> Result<()> {
+    require!(
+        (0..=MAX_DELEGATION_COOLDOWN).contains(&delegation_cooldown),
+        ErrorCode::InvalidDelegationCooldown
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cooldown = config.delegation_cooldown;
+    config.delegation_cooldown = delegation_cooldown;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(DelegationCooldownUpdatedEvent {
+        admin,
+        delegation_cooldown,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_DELEGATION_COOLDOWN, old_cooldown as u64, delegation_cooldown as u64, admin, timestamp);
+
+    msg!("✅ Delegation cooldown updated!");
+    msg!("Cooldown: {} seconds", delegation_cooldown);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::{CONFIG_FIELD_EARLY_UNSTAKE_PENALTY_BPS, MAX_EARLY_UNSTAKE_PENALTY_BPS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, EarlyUnstakePenaltyUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetEarlyUnstakePenalty<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetEarlyUnstakePenalty>, early_unstake_penalty_bps: u64) -> Result<()> {
+    require!(
+        early_unstake_penalty_bps <= MAX_EARLY_UNSTAKE_PENALTY_BPS,
+        ErrorCode::PenaltyTooHigh
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_penalty = config.early_unstake_penalty_bps;
+    config.early_unstake_penalty_bps = early_unstake_penalty_bps;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(EarlyUnstakePenaltyUpdatedEvent {
+        admin,
+        early_unstake_penalty_bps,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_EARLY_UNSTAKE_PENALTY_BPS, old_penalty, early_unstake_penalty_bps, admin, timestamp);
+
+    msg!("✅ Early unstake penalty updated!");
+    msg!("Penalty: {} bps of principal", early_unstake_penalty_bps);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::constants::{CONFIG_FIELD_EPOCH_LENGTH, CONFIG_FIELD_EPOCH_START};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, EpochConfigUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetEpochConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Configure program epochs for the epoch bonus. epoch_length = 0 turns it off.
+pub fn handler(ctx: Context<SetEpochConfig>, epoch_start: i64, epoch_length: i64) -> Result<()> {
+    require!(
+        epoch_start >= 0 && epoch_length >= 0,
+        ErrorCode::InvalidEpochConfig
+    );
+
+    let config = &mut ctx.accounts.config;
+    let (old_start, old_length) = (config.epoch_start, config.epoch_length);
+    config.epoch_start = epoch_start;
+    config.epoch_length = epoch_length;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(EpochConfigUpdatedEvent {
+        admin,
+        epoch_start,
+        epoch_length,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_EPOCH_START, old_start as u64, epoch_start as u64, admin, timestamp);
+    emit_config_changed(CONFIG_FIELD_EPOCH_LENGTH, old_length as u64, epoch_length as u64, admin, timestamp);
+
+    msg!("✅ Epoch config updated!");
+    msg!("Epoch start: {}", epoch_start);
+    msg!("Epoch length: {} days", epoch_length / 86400);
+
+    Ok(())
+}
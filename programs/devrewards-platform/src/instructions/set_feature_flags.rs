@@ -0,0 +1,60 @@
+use crate::constants::{
+    ALL_FEATURES, CONFIG_FIELD_FEATURE_FLAGS, CURRENT_ACCOUNT_VERSION, DEFAULT_FEATURE_FLAGS,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, FeatureFlagsUpdatedEvent};
+use crate::state::{FeatureFlags, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeatureFlags<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = FeatureFlags::LEN,
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: Account<'info, FeatureFlags>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetFeatureFlags>, flags: u64) -> Result<()> {
+    require!(flags & !ALL_FEATURES == 0, ErrorCode::InvalidFeatureFlags);
+
+    let feature_flags = &mut ctx.accounts.feature_flags;
+    let old_flags = if feature_flags.bump == 0 {
+        feature_flags.version = CURRENT_ACCOUNT_VERSION;
+        feature_flags.bump = ctx.bumps.feature_flags;
+        DEFAULT_FEATURE_FLAGS
+    } else {
+        feature_flags.flags
+    };
+    feature_flags.flags = flags;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(FeatureFlagsUpdatedEvent {
+        admin,
+        old_flags,
+        new_flags: flags,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_FEATURE_FLAGS, old_flags, flags, admin, timestamp);
+
+    msg!("✅ Feature flags updated!");
+    msg!("Flags: {:#b} -> {:#b}", old_flags, flags);
+
+    Ok(())
+}
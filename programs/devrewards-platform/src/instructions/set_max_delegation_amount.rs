@@ -0,0 +1,49 @@
+use crate::constants::{to_ui_amount, CONFIG_FIELD_MAX_DELEGATION_AMOUNT, DEVR_DECIMALS, NO_DELEGATION_AMOUNT_CAP};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, MaxDelegationAmountUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxDelegationAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Caps any single `approve_delegate` allowance; 0 lifts the cap
+pub fn handler(ctx: Context<SetMaxDelegationAmount>, max_delegation_amount: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_max_delegation_amount = config.max_delegation_amount;
+    config.max_delegation_amount = max_delegation_amount;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(MaxDelegationAmountUpdatedEvent {
+        admin,
+        max_delegation_amount,
+        timestamp,
+    });
+    emit_config_changed(
+        CONFIG_FIELD_MAX_DELEGATION_AMOUNT,
+        old_max_delegation_amount,
+        max_delegation_amount,
+        admin,
+        timestamp,
+    );
+
+    msg!("✅ Max delegation amount updated!");
+    if max_delegation_amount == NO_DELEGATION_AMOUNT_CAP {
+        msg!("Max delegation: unlimited");
+    } else {
+        msg!("Max delegation: {} DEVR", to_ui_amount(max_delegation_amount, DEVR_DECIMALS));
+    }
+
+    Ok(())
+}
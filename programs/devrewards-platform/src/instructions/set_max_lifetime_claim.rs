@@ -0,0 +1,40 @@
+use crate::constants::{to_ui_amount, CONFIG_FIELD_MAX_LIFETIME_CLAIM, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, LifetimeClaimCapUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxLifetimeClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set how much any one wallet may ever claim from the faucet. 0 removes the cap;
+/// wallets already at or over a lowered cap simply can't claim again.
+pub fn handler(ctx: Context<SetMaxLifetimeClaim>, max_lifetime_claim: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_lifetime_claim;
+    config.max_lifetime_claim = max_lifetime_claim;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(LifetimeClaimCapUpdatedEvent {
+        admin,
+        max_lifetime_claim,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_LIFETIME_CLAIM, old_cap, max_lifetime_claim, admin, timestamp);
+
+    msg!("✅ Lifetime claim cap updated!");
+    msg!("Max lifetime claim: {} DEVR", to_ui_amount(max_lifetime_claim, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::{CONFIG_FIELD_MAX_STREAK_BONUS_BPS, MAX_STREAK_BONUS_BPS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, MaxStreakBonusUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxStreakBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMaxStreakBonus>, max_streak_bonus_bps: u64) -> Result<()> {
+    require!(
+        max_streak_bonus_bps <= MAX_STREAK_BONUS_BPS,
+        ErrorCode::StreakBonusTooHigh
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_streak_bonus_bps;
+    config.max_streak_bonus_bps = max_streak_bonus_bps;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(MaxStreakBonusUpdatedEvent {
+        admin,
+        max_streak_bonus_bps,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_STREAK_BONUS_BPS, old_cap, max_streak_bonus_bps, admin, timestamp);
+
+    msg!("✅ Streak bonus cap updated!");
+    msg!("Cap: {} bps of the daily claim", max_streak_bonus_bps);
+
+    Ok(())
+}
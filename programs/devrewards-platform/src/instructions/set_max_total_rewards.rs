@@ -0,0 +1,40 @@
+use crate::constants::{to_ui_amount, CONFIG_FIELD_MAX_TOTAL_REWARDS, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, RewardCapUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxTotalRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the lifetime ceiling on staking rewards. u64::MAX disables it; a value at or
+/// below what's already been paid stops every further reward payout.
+pub fn handler(ctx: Context<SetMaxTotalRewards>, max_total_rewards: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_total_rewards;
+    config.max_total_rewards = max_total_rewards;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(RewardCapUpdatedEvent {
+        admin,
+        max_total_rewards,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_TOTAL_REWARDS, old_cap, max_total_rewards, admin, timestamp);
+
+    msg!("✅ Reward cap updated!");
+    msg!("Max total rewards: {} DEVR", to_ui_amount(max_total_rewards, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::constants::{to_ui_amount, CONFIG_FIELD_MIN_REWARD_RESERVE, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, MinRewardReserveUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMinRewardReserve<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the floor the reward vault keeps through treasury sweeps. Size it to cover
+/// outstanding obligations plus a buffer; 0 leaves only the obligations check.
+pub fn handler(ctx: Context<SetMinRewardReserve>, min_reward_reserve: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_reserve = config.min_reward_reserve;
+    config.min_reward_reserve = min_reward_reserve;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(MinRewardReserveUpdatedEvent {
+        admin,
+        min_reward_reserve,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MIN_REWARD_RESERVE, old_reserve, min_reward_reserve, admin, timestamp);
+
+    msg!("✅ Minimum reward reserve updated!");
+    msg!("Reserve: {} DEVR", to_ui_amount(min_reward_reserve, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::constants::CONFIG_FIELD_PAUSED;
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ProgramPauseUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Kill switch for stakes, claims and unstakes. Never checks `paused` itself,
+/// so the admin can always unpause.
+pub fn handler(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let was_paused = config.paused;
+    config.paused = paused;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(ProgramPauseUpdatedEvent {
+        admin,
+        paused,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_PAUSED, was_paused as u64, paused as u64, admin, timestamp);
+
+    if paused {
+        msg!("✅ Program paused!");
+    } else {
+        msg!("✅ Program unpaused!");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::constants::CURRENT_ACCOUNT_VERSION;
+use crate::error::ErrorCode;
+use crate::events::PayoutDestinationSetEvent;
+use crate::state::{TokenConfig, UserPreferences};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(destination: Pubkey)]
+pub struct SetPayoutDestination<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPreferences::LEN,
+        seeds = [b"user-preferences", user.key().as_ref()],
+        bump
+    )]
+    pub user_preferences: Account<'info, UserPreferences>,
+
+    // Unstakes pay principal and rewards here, so it must hold DEVR
+    #[account(
+        address = destination,
+        constraint = destination_token_account.mint == config.mint @ ErrorCode::InvalidPayoutDestination
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetPayoutDestination>, destination: Pubkey) -> Result<()> {
+    let user_preferences = &mut ctx.accounts.user_preferences;
+    if user_preferences.user == Pubkey::default() {
+        user_preferences.version = CURRENT_ACCOUNT_VERSION;
+        user_preferences.user = ctx.accounts.user.key();
+        user_preferences.bump = ctx.bumps.user_preferences;
+    }
+
+    let old_destination = user_preferences.payout_destination;
+    user_preferences.payout_destination = destination;
+
+    emit!(PayoutDestinationSetEvent {
+        user: user_preferences.user,
+        old_destination,
+        new_destination: destination,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Payout destination set!");
+    msg!("Destination: {}", destination);
+
+    Ok(())
+}
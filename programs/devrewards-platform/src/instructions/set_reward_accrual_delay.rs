@@ -0,0 +1,45 @@
+use crate::constants::{CONFIG_FIELD_REWARD_ACCRUAL_DELAY, MAX_REWARD_ACCRUAL_DELAY};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, RewardAccrualDelayUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardAccrualDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the warm-up after staking that earns no rewards. Applies to every open
+/// position at payout time, not just new ones.
+pub fn handler(ctx: Context<SetRewardAccrualDelay>, reward_accrual_delay: i64) -> Result<()> {
+    require!(
+        (0..=MAX_REWARD_ACCRUAL_DELAY).contains(&reward_accrual_delay),
+        ErrorCode::InvalidAccrualDelay
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_delay = config.reward_accrual_delay;
+    config.reward_accrual_delay = reward_accrual_delay;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(RewardAccrualDelayUpdatedEvent {
+        admin,
+        reward_accrual_delay,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_REWARD_ACCRUAL_DELAY, old_delay as u64, reward_accrual_delay as u64, admin, timestamp);
+
+    msg!("✅ Reward accrual delay updated!");
+    msg!("Delay: {} hours", reward_accrual_delay / 3600);
+
+    Ok(())
+}
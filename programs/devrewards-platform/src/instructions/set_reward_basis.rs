@@ -0,0 +1,47 @@
+use crate::constants::{CONFIG_FIELD_REWARD_BASIS, REWARD_BASIS_COMMITTED, REWARD_BASIS_ELAPSED};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, RewardBasisUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardBasis<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Choose whether unstakes pay on the committed lock or on time actually staked
+pub fn handler(ctx: Context<SetRewardBasis>, reward_basis: u8) -> Result<()> {
+    require!(
+        reward_basis == REWARD_BASIS_COMMITTED || reward_basis == REWARD_BASIS_ELAPSED,
+        ErrorCode::InvalidRewardBasis
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_basis = config.reward_basis;
+    config.reward_basis = reward_basis;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(RewardBasisUpdatedEvent {
+        admin,
+        reward_basis,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_REWARD_BASIS, old_basis as u64, reward_basis as u64, admin, timestamp);
+
+    msg!("✅ Reward basis updated!");
+    msg!(
+        "Basis: {}",
+        if reward_basis == REWARD_BASIS_ELAPSED { "elapsed time" } else { "committed duration" }
+    );
+
+    Ok(())
+}
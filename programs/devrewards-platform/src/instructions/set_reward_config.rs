@@ -0,0 +1,109 @@
+use crate::constants::{CONFIG_FIELD_REWARD_DECIMALS, CONFIG_FIELD_REWARD_MINT, CURRENT_ACCOUNT_VERSION};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ConfigPubkeyChangedEvent, RewardConfigUpdatedEvent};
+use crate::state::{RewardConfig, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetRewardConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = RewardConfig::LEN,
+        seeds = [b"reward-config"],
+        bump
+    )]
+    pub reward_config: Account<'info, RewardConfig>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    // Must be signable by the program; the stake vault itself for the stake mint
+    #[account(
+        constraint = reward_vault.mint == reward_mint.key() @ ErrorCode::RewardTokenMismatch,
+        constraint = reward_vault.owner == vault_authority.key() @ ErrorCode::RewardAccountMismatch
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, only checked as the reward vault's owner
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Points reward payouts at `reward_mint` / `reward_vault`. Passing the stake mint
+/// and vault restores the default single-mint payouts.
+pub fn handler(ctx: Context<SetRewardConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let reward_mint = &ctx.accounts.reward_mint;
+    let reward_vault = ctx.accounts.reward_vault.key();
+
+    let split = reward_mint.key() != config.mint;
+    require!(
+        split || reward_vault == config.vault,
+        ErrorCode::RewardAccountMismatch
+    );
+
+    let reward_config = &mut ctx.accounts.reward_config;
+    // A freshly created account means payouts were still in the stake mint
+    let old_reward_mint = if reward_config.reward_mint == Pubkey::default() {
+        config.mint
+    } else {
+        reward_config.reward_mint
+    };
+    let old_reward_decimals = config.reward_decimals;
+    reward_config.version = CURRENT_ACCOUNT_VERSION;
+    reward_config.reward_mint = reward_mint.key();
+    reward_config.reward_vault = reward_vault;
+    reward_config.bump = ctx.bumps.reward_config;
+
+    // Quotes and payouts scale rewards to the reward token's decimals
+    config.reward_decimals = reward_mint.decimals;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(RewardConfigUpdatedEvent {
+        admin,
+        reward_mint: reward_mint.key(),
+        reward_vault,
+        reward_decimals: reward_mint.decimals,
+        split,
+        timestamp,
+    });
+    emit!(ConfigPubkeyChangedEvent {
+        field: CONFIG_FIELD_REWARD_MINT,
+        old_value: old_reward_mint,
+        new_value: reward_mint.key(),
+        admin,
+        timestamp,
+    });
+    emit_config_changed(
+        CONFIG_FIELD_REWARD_DECIMALS,
+        old_reward_decimals as u64,
+        reward_mint.decimals as u64,
+        admin,
+        timestamp,
+    );
+
+    msg!("✅ Reward config updated!");
+    msg!("Reward mint: {}", reward_mint.key());
+    msg!("Reward vault: {}", reward_vault);
+    msg!("Split mint: {}", split);
+
+    Ok(())
+}
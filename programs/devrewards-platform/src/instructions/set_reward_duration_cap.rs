@@ -0,0 +1,46 @@
+use crate::constants::{
+    CONFIG_FIELD_MAX_REWARD_ELIGIBLE_DURATION, MAX_LOCK_DURATION, MIN_LOCK_DURATION,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, RewardDurationCapUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRewardDurationCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Cap the lock seconds that earn rewards. MAX_LOCK_DURATION effectively disables it.
+pub fn handler(ctx: Context<SetRewardDurationCap>, max_reward_eligible_duration: i64) -> Result<()> {
+    require!(
+        (MIN_LOCK_DURATION..=MAX_LOCK_DURATION).contains(&max_reward_eligible_duration),
+        ErrorCode::InvalidRewardDurationCap
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_reward_eligible_duration;
+    config.max_reward_eligible_duration = max_reward_eligible_duration;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(RewardDurationCapUpdatedEvent {
+        admin,
+        max_reward_eligible_duration,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_REWARD_ELIGIBLE_DURATION, old_cap as u64, max_reward_eligible_duration as u64, admin, timestamp);
+
+    msg!("✅ Reward duration cap updated!");
+    msg!("Max reward-eligible duration: {} days", max_reward_eligible_duration / 86400);
+
+    Ok(())
+}
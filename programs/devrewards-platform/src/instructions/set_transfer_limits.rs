@@ -0,0 +1,49 @@
+use crate::constants::{to_ui_amount, CONFIG_FIELD_MAX_TRANSFER, CONFIG_FIELD_MIN_TRANSFER, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, TransferLimitsUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetTransferLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the per-transfer bounds shared by `transfer`, `transfer_devr`,
+/// `delegated_transfer` and every leg of `batch_transfer`.
+pub fn handler(ctx: Context<SetTransferLimits>, min_transfer: u64, max_transfer: u64) -> Result<()> {
+    require!(
+        min_transfer > 0 && min_transfer <= max_transfer,
+        ErrorCode::InvalidTransferLimits
+    );
+
+    let config = &mut ctx.accounts.config;
+    let (old_min, old_max) = (config.min_transfer, config.max_transfer);
+    config.min_transfer = min_transfer;
+    config.max_transfer = max_transfer;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(TransferLimitsUpdatedEvent {
+        admin,
+        min_transfer,
+        max_transfer,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MIN_TRANSFER, old_min, min_transfer, admin, timestamp);
+    emit_config_changed(CONFIG_FIELD_MAX_TRANSFER, old_max, max_transfer, admin, timestamp);
+
+    msg!("✅ Transfer limits updated!");
+    msg!("Min transfer: {} tokens", to_ui_amount(min_transfer, DEVR_DECIMALS));
+    msg!("Max transfer: {} tokens", to_ui_amount(max_transfer, DEVR_DECIMALS));
+
+    Ok(())
+}
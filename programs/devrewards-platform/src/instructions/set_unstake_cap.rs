@@ -0,0 +1,47 @@
+use crate::constants::{
+    to_ui_amount, CONFIG_FIELD_MAX_UNSTAKE_PER_TX, DEVR_DECIMALS, MIN_STAKE_AMOUNT,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, UnstakeCapUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetUnstakeCap<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the per-transaction withdrawal cap. u64::MAX disables it.
+pub fn handler(ctx: Context<SetUnstakeCap>, max_unstake_per_tx: u64) -> Result<()> {
+    // A cap below the minimum stake would make every position unwithdrawable
+    require!(
+        max_unstake_per_tx >= MIN_STAKE_AMOUNT,
+        ErrorCode::AmountTooSmall
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_cap = config.max_unstake_per_tx;
+    config.max_unstake_per_tx = max_unstake_per_tx;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(UnstakeCapUpdatedEvent {
+        admin,
+        max_unstake_per_tx,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_MAX_UNSTAKE_PER_TX, old_cap, max_unstake_per_tx, admin, timestamp);
+
+    msg!("✅ Unstake cap updated!");
+    msg!("Max unstake per tx: {} DEVR", to_ui_amount(max_unstake_per_tx, DEVR_DECIMALS));
+
+    Ok(())
+}
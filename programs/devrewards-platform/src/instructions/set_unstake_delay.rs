@@ -0,0 +1,43 @@
+use crate::constants::{CONFIG_FIELD_UNSTAKE_DELAY, MAX_UNSTAKE_DELAY};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, UnstakeDelayUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetUnstakeDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetUnstakeDelay>, unstake_delay: i64) -> Result<()> {
+    require!(
+        (0..=MAX_UNSTAKE_DELAY).contains(&unstake_delay),
+        ErrorCode::InvalidUnstakeDelay
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_delay = config.unstake_delay;
+    config.unstake_delay = unstake_delay;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(UnstakeDelayUpdatedEvent {
+        admin,
+        unstake_delay,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_UNSTAKE_DELAY, old_delay as u64, unstake_delay as u64, admin, timestamp);
+
+    msg!("✅ Unstake delay updated!");
+    msg!("Delay: {} seconds", unstake_delay);
+
+    Ok(())
+}
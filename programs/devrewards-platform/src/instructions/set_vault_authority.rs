@@ -0,0 +1,88 @@
+use crate::constants::CONFIG_FIELD_VAULT_AUTHORITY;
+use crate::error::ErrorCode;
+use crate::events::{ConfigPubkeyChangedEvent, VaultAuthorityTransferredEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType, SetAuthority, Token, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SetVaultAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, current vault owner that signs the handover
+    #[account(
+        seeds = [b"vault-authority"],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Hands ownership of the vault to `new_authority` (e.g. a successor program's PDA).
+///
+/// MIGRATION ONLY: afterwards this program can no longer sign vault transfers, so
+/// unstakes, restakes and LP payouts from the vault stop working here for good.
+/// Run it only as part of a controlled migration, once positions are moved or paused.
+pub fn handler(ctx: Context<SetVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+    let old_authority = ctx.accounts.vault_authority.key();
+    require!(
+        new_authority != old_authority && new_authority != Pubkey::default(),
+        ErrorCode::InvalidVaultAuthority
+    );
+
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.vault_authority.to_account_info(),
+        account_or_mint: ctx.accounts.vault.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+    let signer = &[&seeds[..]];
+
+    token::set_authority(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        AuthorityType::AccountOwner,
+        Some(new_authority),
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.vault_authority = new_authority;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(VaultAuthorityTransferredEvent {
+        admin,
+        vault: ctx.accounts.vault.key(),
+        old_authority,
+        new_authority,
+        timestamp,
+    });
+    emit!(ConfigPubkeyChangedEvent {
+        field: CONFIG_FIELD_VAULT_AUTHORITY,
+        old_value: old_authority,
+        new_value: new_authority,
+        admin,
+        timestamp,
+    });
+
+    msg!("⚠️ Vault authority transferred - this program can no longer sign vault withdrawals");
+    msg!("Vault: {}", ctx.accounts.vault.key());
+    msg!("New authority: {}", new_authority);
+
+    Ok(())
+}
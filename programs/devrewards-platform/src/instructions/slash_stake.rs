@@ -0,0 +1,170 @@
+use super::unstake::{max_position_rewards, RewardWindow};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::SlashEvent;
+use crate::state::{GlobalStats, StakeAccount, TokenConfig, UserStakeStats};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, stake_count: u64)]
+pub struct SlashStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", user.as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        has_one = user @ ErrorCode::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    // Positions opened before stats tracking may not have one yet
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Principal left on a position after slashing `slash_amount` from it
+pub fn slashed_principal(staked_amount: u64, slash_amount: u64) -> Result<u64> {
+    require!(
+        slash_amount > 0 && slash_amount <= staked_amount,
+        ErrorCode::InvalidSlashAmount
+    );
+    Ok(staked_amount - slash_amount)
+}
+
+/// Cuts `slash_amount` from a position's principal. The slashed tokens never leave
+/// the vault; they become surplus, counted as a penalty, for the treasury to sweep.
+pub fn handler(
+    ctx: Context<SlashStake>,
+    user: Pubkey,
+    stake_count: u64,
+    slash_amount: u64,
+    reason_code: u8,
+) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let remaining_principal = slashed_principal(stake_account.staked_amount, slash_amount)?;
+    // Tranche weighting and compounded rewards shrink with the principal
+    stake_account.withdraw_principal(slash_amount)?;
+
+    // A smaller principal earns less, so part of its reservation is freed
+    let reward_liability =
+        max_position_rewards(stake_account, RewardWindow::from_config(&ctx.accounts.config))?;
+    let released = stake_account.reward_liability.saturating_sub(reward_liability);
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.release_rewards(released)?;
+    stake_account.reward_liability -= released;
+
+    global_stats.total_staked = global_stats
+        .total_staked
+        .checked_sub(slash_amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    global_stats.record_penalty(slash_amount)?;
+
+    let user_stake_stats = &mut ctx.accounts.user_stake_stats;
+    if user_stake_stats.user == Pubkey::default() {
+        user_stake_stats.version = CURRENT_ACCOUNT_VERSION;
+        user_stake_stats.user = user;
+        user_stake_stats.bump = ctx.bumps.user_stake_stats;
+    }
+    // Saturating: positions opened before stats tracking were never added
+    user_stake_stats.total_active_staked = user_stake_stats
+        .total_active_staked
+        .saturating_sub(slash_amount);
+
+    emit!(SlashEvent {
+        user,
+        stake_index: stake_count,
+        slashed_amount: slash_amount,
+        remaining_principal,
+        reason_code,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("⚔️ Stake slashed!");
+    msg!("User: {}", user);
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Slashed: {} DEVR", to_ui_amount(slash_amount, DEVR_DECIMALS));
+    msg!("Remaining principal: {} DEVR", to_ui_amount(remaining_principal, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::test_stake_account;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+
+    fn position(staked_amount: u64) -> StakeAccount {
+        StakeAccount { staked_amount, ..test_stake_account(1_700_000_000, SECONDS_IN_NINETY_DAYS) }
+    }
+
+    #[test]
+    fn test_slash_bounds() {
+        assert_eq!(slashed_principal(PRINCIPAL, 1).unwrap(), PRINCIPAL - 1);
+        // A full slash leaves an empty position for the user to close
+        assert_eq!(slashed_principal(PRINCIPAL, PRINCIPAL).unwrap(), 0);
+        assert!(slashed_principal(PRINCIPAL, 0).is_err());
+        assert!(slashed_principal(PRINCIPAL, PRINCIPAL + 1).is_err());
+    }
+
+    #[test]
+    fn test_slash_shrinks_the_reservation() {
+        let window = RewardWindow {
+            accrual_delay: 0,
+            max_duration: MAX_LOCK_DURATION,
+            basis: REWARD_BASIS_COMMITTED,
+        };
+        let full = max_position_rewards(&position(PRINCIPAL), window).unwrap();
+        let halved = max_position_rewards(&position(PRINCIPAL / 2), window).unwrap();
+        assert!(halved.abs_diff(full / 2) <= 1);
+    }
+
+    #[test]
+    fn test_slash_scales_tranches_down() {
+        let staked_at = 1_700_000_000;
+        let mut stake = position(100);
+        // Another 100 halfway through the lock earns over half of it
+        stake.add_tranche(100, staked_at + SECONDS_IN_NINETY_DAYS / 2).unwrap();
+        assert_eq!(stake.reward_principal(), 150);
+
+        let remaining = slashed_principal(stake.staked_amount, 100).unwrap();
+        stake.withdraw_principal(100).unwrap();
+        assert_eq!(stake.staked_amount, remaining);
+        // Half the position is gone, so half its weighted principal goes too
+        assert_eq!(stake.reward_principal(), 75);
+    }
+
+    #[test]
+    fn test_slash_scales_compounded_rewards_down() {
+        let mut stake = StakeAccount { compounded_rewards: 400, ..position(PRINCIPAL) };
+        stake.withdraw_principal(PRINCIPAL / 4).unwrap();
+        assert_eq!(stake.staked_amount, PRINCIPAL * 3 / 4);
+        assert_eq!(stake.compounded_rewards, 300);
+    }
+}
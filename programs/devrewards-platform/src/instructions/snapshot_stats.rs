@@ -0,0 +1,41 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::events::StatsSnapshotEvent;
+use crate::state::GlobalStats;
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct SnapshotStats<'info> {
+    #[account(
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+}
+
+/// Permissionless TVL snapshot for indexers building a time series
+pub fn handler(ctx: Context<SnapshotStats>) -> Result<()> {
+    let global_stats = &ctx.accounts.global_stats;
+    let vault_balance = ctx.accounts.vault.amount;
+
+    emit!(StatsSnapshotEvent {
+        total_staked: global_stats.total_staked,
+        total_stakes: global_stats.total_stakes,
+        total_rewards_paid: global_stats.total_rewards_paid,
+        vault_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("📸 Stats snapshot");
+    msg!("Total staked: {} DEVR", to_ui_amount(global_stats.total_staked, DEVR_DECIMALS));
+    msg!("Total stakes: {}", global_stats.total_stakes);
+    msg!("Vault balance: {} DEVR", to_ui_amount(vault_balance, DEVR_DECIMALS));
+
+    Ok(())
+}
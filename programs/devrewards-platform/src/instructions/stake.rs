@@ -1,7 +1,12 @@
 use crate::constants::*;
 use crate::error::ErrorCode;
-use crate::events::StakeEvent;
-use crate::state::{GlobalStats, StakeAccount, StakeCounter, TokenConfig};
+use super::boost_vault::load_boost_vault;
+use super::unstake::{reserve_position_rewards, PositionBooks, RewardWindow};
+use crate::events::{StakeBoostedEvent, StakeEvent};
+use crate::state::{
+    is_feature_enabled, ApyTier, FeatureFlags, GlobalStats, StakeAccount, StakeCounter,
+    TokenConfig, UserStakeStats, UserStats,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
@@ -22,7 +27,15 @@ pub struct Stake<'info> {
     )]
     pub counter: Account<'info, StakeCounter>,
 
-    #[account(init, payer = user, space = StakeAccount::LEN, seeds = [b"stake", user.key().as_ref(), &counter.stake_count.to_le_bytes()], bump)]
+    // init_if_needed so a desynced counter landing on an existing position fails
+    // with StakeIndexCollision in the handler instead of an opaque system error
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake", user.key().as_ref(), &counter.stake_count.to_le_bytes()],
+        bump
+    )]
     pub stake_account: Account<'info, StakeAccount>,
 
     #[account(mut, token::mint = config.mint, token::authority = user)]
@@ -31,7 +44,8 @@ pub struct Stake<'info> {
     #[account(
         mut,
         seeds = [b"vault"],
-        bump
+        bump,
+        address = config.vault
     )]
     pub vault: Account<'info, TokenAccount>,
 
@@ -42,6 +56,41 @@ pub struct Stake<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    /// CHECK: Boost vault token account PDA, validated by seeds. Read manually so
+    /// unboosted stakes work before an admin has created it.
+    #[account(
+        mut,
+        seeds = [b"boost-vault"],
+        bump
+    )]
+    pub boost_vault: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -50,7 +99,162 @@ pub struct Stake<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
+/// What a new position is opened with. Everything else on it starts at zero.
+pub struct PositionTerms {
+    pub amount: u64,
+    pub lock_duration: i64,
+    pub tiers: [ApyTier; 3], // Schedule the APY is locked in from
+    pub interpolated: bool,
+    pub loyalty_bonus_bps: u64,
+    pub epoch_bonus: bool, // Whether a lock covering a whole program epoch earns EPOCH_BONUS_BPS
+    pub boost_amount: u64,
+    pub boost_multiplier_bps: u64,
+    pub staked_at: i64,
+}
+
+impl PositionTerms {
+    /// A plain position: no loyalty snapshot, epoch bonus or boost
+    pub fn plain(
+        amount: u64,
+        lock_duration: i64,
+        tiers: [ApyTier; 3],
+        interpolated: bool,
+        staked_at: i64,
+    ) -> Self {
+        Self {
+            amount,
+            lock_duration,
+            staked_at,
+            tiers,
+            interpolated,
+            loyalty_bonus_bps: 0,
+            epoch_bonus: false,
+            boost_amount: 0,
+            boost_multiplier_bps: 0,
+        }
+    }
+}
+
+/// Fills in a freshly created stake account and books it: the counter hands out its
+/// index, the principal joins the global and per-user totals and the most the
+/// position can earn is reserved. `vault_balance` must already hold the principal.
+/// Every instruction that opens a position goes through here, so none of them drift apart.
+pub fn open_position(
+    stake_account: &mut StakeAccount,
+    user: Pubkey,
+    bump: u8,
+    terms: &PositionTerms,
+    config: &TokenConfig,
+    books: &mut PositionBooks,
+    vault_balance: u64,
+) -> Result<()> {
+    stake_account.version = STAKE_ACCOUNT_VERSION;
+    stake_account.user = user;
+    stake_account.staked_amount = terms.amount;
+    stake_account.staked_at = terms.staked_at;
+    stake_account.set_lock_duration(terms.lock_duration)?;
+    stake_account.lock_in_apy(&terms.tiers, terms.interpolated);
+    stake_account.bump = bump;
+    stake_account.stake_index = books.counter.stake_count;
+    stake_account.frozen = false;
+    stake_account.frozen_at = 0;
+    stake_account.unfrozen_at = 0;
+    stake_account.loyalty_bonus_bps = terms.loyalty_bonus_bps;
+    stake_account.last_compound_at = 0;
+    stake_account.compounded_rewards = 0;
+    stake_account.weighted_staked_seconds = 0;
+    stake_account.boost_amount = terms.boost_amount;
+    stake_account.boost_multiplier_bps = terms.boost_multiplier_bps;
+    stake_account.reward_liability = 0;
+    stake_account.is_withdrawn = false;
+    stake_account.unstake_requested_at = 0;
+
+    // Epoch incentive: the lock must cover at least one whole program epoch
+    stake_account.epoch_bonus_bps = if terms.epoch_bonus
+        && contains_full_epoch(
+            stake_account.staked_at,
+            stake_account.maturity_timestamp,
+            config.epoch_start,
+            config.epoch_length,
+        ) {
+        EPOCH_BONUS_BPS
+    } else {
+        0
+    };
+
+    books.counter.open_position()?;
+
+    let global_stats = &mut books.global_stats;
+    global_stats.total_staked = global_stats
+        .total_staked
+        .checked_add(terms.amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    global_stats.update_peak();
+    global_stats.total_stakes = global_stats
+        .total_stakes
+        .checked_add(1)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    books.user_stake_stats.total_active_staked = books
+        .user_stake_stats
+        .total_active_staked
+        .checked_add(terms.amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    books.user_stats.record_stake(terms.amount)?;
+
+    // The vault must already back the most this position can earn, on top of every
+    // principal and earlier reservation - otherwise the unstake would come up short
+    reserve_position_rewards(
+        stake_account,
+        books.global_stats,
+        RewardWindow::from_config(config),
+        vault_balance,
+    )?;
+    Ok(())
+}
+
+/// Stake until a fixed end time. The lock is derived from when the tx lands, so
+/// `min_tier` guards against a delayed inclusion dropping the position a tier.
+pub fn stake_until_handler(
+    ctx: Context<Stake>,
+    amount: u64,
+    end_timestamp: i64,
+    min_tier: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let lock_duration = end_timestamp
+        .checked_sub(clock.unix_timestamp)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let tiers = ctx.accounts.config.apy_tiers;
+    require!(
+        get_tier_for_duration(&tiers, lock_duration) >= min_tier,
+        ErrorCode::TierNotMet
+    );
+
+    handler(ctx, amount, lock_duration, 0)
+}
+
+/// Stake `amount` for `lock_duration`. A non-zero `boost_amount` is held in the
+/// boost vault alongside the position and raises its base APY until unstake.
+pub fn handler(
+    ctx: Context<Stake>,
+    amount: u64,
+    lock_duration: i64,
+    boost_amount: u64,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+    require!(
+        ctx.accounts.stake_account.is_unused(),
+        ErrorCode::StakeIndexCollision
+    );
     require!(amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
     require!(amount <= MAX_STAKE_AMOUNT, ErrorCode::AmountTooLarge);
     require!(
@@ -61,12 +265,31 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
         lock_duration <= MAX_LOCK_DURATION,
         ErrorCode::DurationTooLong
     );
+    let total_deposit = amount
+        .checked_add(boost_amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
     require!(
-        ctx.accounts.user_token_account.amount >= amount,
+        ctx.accounts.user_token_account.amount >= total_deposit,
         ErrorCode::InsufficientBalance
     );
+    require!(
+        boost_amount == 0 || load_boost_vault(&ctx.accounts.boost_vault)?.is_some(),
+        ErrorCode::BoostVaultNotInitialized
+    );
 
-    let (apy_numerator, apy_denominator) = get_apy_for_duration(lock_duration);
+    // Locked in from the latest schedule; unstake pays this rate whatever changes later
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    let interpolated = is_feature_enabled(flags, FEATURE_INTERPOLATED_APY);
+    let tiers = ctx.accounts.config.apy_tiers;
+    let (apy_numerator, apy_denominator) = get_apy_for_lock(&tiers, lock_duration, interpolated);
+
+    // Loyalty: snapshot what the user keeps liquid after this stake
+    let liquid_balance = ctx.accounts.user_token_account.amount - total_deposit;
+    let loyalty_bonus_bps = if is_feature_enabled(flags, FEATURE_LOYALTY_BONUS) {
+        get_loyalty_bonus_bps(liquid_balance)
+    } else {
+        0
+    };
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.user_token_account.to_account_info(),
@@ -79,51 +302,92 @@ pub fn handler(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<(
 
     token::transfer(cpi_ctx, amount)?;
 
-    let stake_account = &mut ctx.accounts.stake_account;
-    let counter = &mut ctx.accounts.counter;
-    let clock = Clock::get()?;
-
-    stake_account.user = ctx.accounts.user.key();
-    stake_account.staked_amount = amount;
-    stake_account.staked_at = clock.unix_timestamp;
-    stake_account.lock_duration = lock_duration;
-    stake_account.bump = ctx.bumps.stake_account;
-    stake_account.stake_index = counter.stake_count;
-
-    let global_stats = &mut ctx.accounts.global_stats;
-    global_stats.total_staked = global_stats
-        .total_staked
-        .checked_add(amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-    global_stats.total_stakes = global_stats
-        .total_stakes
-        .checked_add(1)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if boost_amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.boost_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), boost_amount)?;
+    }
+    let boost_multiplier_bps = get_boost_multiplier_bps(boost_amount, amount);
 
-    counter.stake_count = counter
-        .stake_count
-        .checked_add(1)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let user = ctx.accounts.user.key();
+    let clock = Clock::get()?;
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    ctx.accounts.vault.reload()?;
+    let config = &ctx.accounts.config;
+    let stake_account = &mut ctx.accounts.stake_account;
+    open_position(
+        stake_account,
+        user,
+        ctx.bumps.stake_account,
+        &PositionTerms {
+            amount,
+            lock_duration,
+            tiers,
+            interpolated,
+            loyalty_bonus_bps,
+            epoch_bonus: true,
+            boost_amount,
+            boost_multiplier_bps,
+            staked_at: clock.unix_timestamp,
+        },
+        config,
+        &mut PositionBooks {
+            counter: &mut ctx.accounts.counter,
+            global_stats: &mut ctx.accounts.global_stats,
+            user_stake_stats: &mut ctx.accounts.user_stake_stats,
+            user_stats: &mut ctx.accounts.user_stats,
+        },
+        ctx.accounts.vault.amount,
+    )?;
+    let epoch_bonus_bps = stake_account.epoch_bonus_bps;
 
+    let counter = &mut ctx.accounts.counter;
     if counter.bump == 0 {
+        counter.version = CURRENT_ACCOUNT_VERSION;
         counter.bump = ctx.bumps.counter;
+        counter.unbonding_count = 0;
     }
 
     emit!(StakeEvent {
-        user: ctx.accounts.user.key(),
+        user,
         stake_index: stake_account.stake_index,
         staked_amount: amount,
         lock_duration,
         apy_numerator,
         apy_denominator,
+        loyalty_bonus_bps,
+        epoch_bonus_bps,
         timestamp: clock.unix_timestamp,
     });
+    if boost_amount > 0 {
+        emit!(StakeBoostedEvent {
+            user,
+            stake_index: stake_account.stake_index,
+            boost_amount,
+            boost_multiplier_bps,
+            timestamp: clock.unix_timestamp,
+        });
+    }
 
     msg!("✅ Stake successful!");
     msg!("Stake index: {}", stake_account.stake_index);
-    msg!("Amount: {} DEVR", amount / 1_000_000_000);
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
     msg!("Duration: {} days", lock_duration / 86400);
     msg!("APY: {}%", (apy_numerator * 100) / apy_denominator);
+    if loyalty_bonus_bps > 0 {
+        msg!("Loyalty bonus: +{} bps APY", loyalty_bonus_bps);
+    }
+    if epoch_bonus_bps > 0 {
+        msg!("Epoch bonus: +{} bps APY", epoch_bonus_bps);
+    }
+    if boost_amount > 0 {
+        msg!("Boost: {} DEVR, APY x{} bps", to_ui_amount(boost_amount, DEVR_DECIMALS), BASIS_POINTS + boost_multiplier_bps);
+    }
 
     Ok(())
 }
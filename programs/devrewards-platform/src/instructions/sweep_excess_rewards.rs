@@ -0,0 +1,133 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
+use crate::error::ErrorCode;
+use crate::events::ExcessRewardsSweptEvent;
+use crate::state::{GlobalStats, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct SweepExcessRewards<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump = config.vault_authority_bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    // Treasury account receiving the swept tokens
+    #[account(mut, token::mint = config.mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// True if sweeping `amount` leaves the vault holding at least `min_reward_reserve`.
+/// A hard floor, checked on its own so it holds whatever the obligations come to.
+pub fn leaves_min_reserve(vault_balance: u64, amount: u64, min_reward_reserve: u64) -> bool {
+    vault_balance
+        .checked_sub(amount)
+        .is_some_and(|remaining| remaining >= min_reward_reserve)
+}
+
+/// Move reward-vault tokens that aren't needed by stakers to a treasury account
+pub fn handler(ctx: Context<SweepExcessRewards>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::AmountTooSmall);
+
+    let vault_balance = ctx.accounts.vault.amount;
+    let min_reward_reserve = ctx.accounts.config.min_reward_reserve;
+    require!(
+        leaves_min_reserve(vault_balance, amount, min_reward_reserve),
+        ErrorCode::ReserveBreach
+    );
+
+    // Staked principal and reserved rewards are never sweepable, whatever the
+    // reserve is set to
+    let remaining = vault_balance - amount;
+    ctx.accounts.global_stats.check_vault_backing(remaining)?;
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.vault_authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let seeds = &[
+        b"vault-authority".as_ref(),
+        &[ctx.accounts.config.vault_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    token::transfer(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        amount,
+    )?;
+
+    emit!(ExcessRewardsSweptEvent {
+        admin: ctx.accounts.admin.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        vault_balance: remaining,
+        min_reward_reserve,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Excess rewards swept!");
+    msg!("Amount: {} DEVR", to_ui_amount(amount, DEVR_DECIMALS));
+    msg!("Vault balance: {} DEVR", to_ui_amount(remaining, DEVR_DECIMALS));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVR: u64 = 1_000_000_000;
+
+    #[test]
+    fn test_sweep_leaving_reserve_succeeds() {
+        let balance = 1_000 * DEVR;
+        let reserve = 400 * DEVR;
+
+        assert!(leaves_min_reserve(balance, 100 * DEVR, reserve));
+        // Landing exactly on the reserve is allowed
+        assert!(leaves_min_reserve(balance, 600 * DEVR, reserve));
+        // No reserve configured: everything is sweepable as far as the floor goes
+        assert!(leaves_min_reserve(balance, balance, 0));
+    }
+
+    #[test]
+    fn test_sweep_breaching_reserve_fails() {
+        let balance = 1_000 * DEVR;
+        let reserve = 400 * DEVR;
+
+        assert!(!leaves_min_reserve(balance, 600 * DEVR + 1, reserve));
+        assert!(!leaves_min_reserve(balance, balance, reserve));
+        // More than the vault holds never passes
+        assert!(!leaves_min_reserve(balance, balance + 1, 0));
+        // A reserve above the balance blocks every sweep
+        assert!(!leaves_min_reserve(balance, 1, balance + 1));
+    }
+}
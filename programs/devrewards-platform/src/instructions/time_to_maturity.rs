@@ -0,0 +1,37 @@
+use super::get_unlock_info::unlock_info;
+use crate::events::MaturityEvent;
+use crate::state::StakeAccount;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct TimeToMaturity<'info> {
+    #[account(
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump = stake_account.bump,
+        has_one = user
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// CHECK: Stake owner, only used to derive the stake PDA
+    pub user: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<TimeToMaturity>, stake_count: u64) -> Result<()> {
+    let stake_account = &ctx.accounts.stake_account;
+    // Same countdown get_unlock_info returns, so the two views can't disagree
+    let info = unlock_info(stake_account, Clock::get()?.unix_timestamp);
+
+    emit!(MaturityEvent {
+        user: stake_account.user,
+        stake_index: stake_count,
+        seconds_remaining: info.seconds_remaining,
+        is_matured: info.is_unlocked,
+    });
+
+    msg!("Stake Index: #{}", stake_count);
+    msg!("Seconds remaining: {}", info.seconds_remaining);
+    msg!("Matured: {}", info.is_unlocked);
+
+    Ok(())
+}
@@ -1,53 +1,187 @@
+use crate::constants::{to_ui_amount, DEVR_DECIMALS};
 use crate::error::ErrorCode;
+use crate::events::TransferEvent;
+use crate::state::{Blocklist, TokenConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
-const MIN_TRANSFER: u64 = 1_000_000_000;
-const MAX_TRANSFER: u64 = 10_000_000_000_000;
-
 #[derive(Accounts)]
 pub struct TransferTokens<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
     #[account(mut)]
     pub from_token_account: Account<'info, TokenAccount>,
 
     #[account(mut)]
     pub to_token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: Recipient owner's blocklist PDA, validated by seeds. Only whether it
+    /// exists is read.
+    #[account(
+        seeds = [b"blocklist", to_token_account.owner.as_ref()],
+        bump
+    )]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
-    require!(amount >= MIN_TRANSFER, ErrorCode::AmountTooSmall);
+#[derive(Accounts)]
+pub struct TransferDevr<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        constraint = from_token_account.mint == config.mint @ ErrorCode::MintMismatch
+    )]
+    pub from_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = to_token_account.mint == config.mint @ ErrorCode::MintMismatch
+    )]
+    pub to_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Recipient owner's blocklist PDA, validated by seeds. Only whether it
+    /// exists is read.
+    #[account(
+        seeds = [b"blocklist", to_token_account.owner.as_ref()],
+        bump
+    )]
+    pub recipient_blocklist: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
 
-    require!(amount <= MAX_TRANSFER, ErrorCode::AmountTooLarge);
+/// Per-transfer bounds shared by every transfer path (TokenConfig.min_transfer / max_transfer)
+pub(crate) fn check_transfer_amount(amount: u64, min_transfer: u64, max_transfer: u64) -> Result<()> {
+    require!(amount >= min_transfer, ErrorCode::AmountTooSmall);
+    require!(amount <= max_transfer, ErrorCode::AmountTooLarge);
+    Ok(())
+}
+
+/// Checks and CPI shared by `transfer` and `transfer_devr`, after the amount bounds
+fn execute_transfer<'info>(
+    from_token_account: &Account<'info, TokenAccount>,
+    to_token_account: &Account<'info, TokenAccount>,
+    recipient_blocklist: &UncheckedAccount<'info>,
+    authority: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+    keep_reserve: u64,
+) -> Result<()> {
+    require_keys_neq!(
+        from_token_account.key(),
+        to_token_account.key(),
+        ErrorCode::SelfTransfer
+    );
 
     require!(
-        amount <= ctx.accounts.from_token_account.amount,
+        amount <= from_token_account.amount,
         ErrorCode::InsufficientBalance
     );
 
+    // Opt-in drain protection: keep_reserve = 0 disables it
+    let remaining = from_token_account.amount - amount;
+    require!(remaining >= keep_reserve, ErrorCode::ReserveViolation);
+
     require!(
-        ctx.accounts.from_token_account.mint == ctx.accounts.to_token_account.mint,
+        from_token_account.mint == to_token_account.mint,
         ErrorCode::MintMismatch
     );
 
+    require!(
+        !Blocklist::is_blocked(recipient_blocklist),
+        ErrorCode::RecipientBlocked
+    );
+
     let cpi_accounts = Transfer {
-        from: ctx.accounts.from_token_account.to_account_info(),
-        to: ctx.accounts.to_token_account.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
+        from: from_token_account.to_account_info(),
+        to: to_token_account.to_account_info(),
+        authority: authority.to_account_info(),
     };
 
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = token_program.to_account_info();
     let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
 
-    token::transfer(cpi_context, amount);
+    token::transfer(cpi_context, amount)?;
+
+    emit!(TransferEvent {
+        from: from_token_account.key(),
+        to: to_token_account.key(),
+        authority: authority.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
     msg!("✅ Transfer successful!");
-    msg!("From: {}", ctx.accounts.from_token_account.key());
-    msg!("To: {}", ctx.accounts.to_token_account.key());
-    msg!("Amount: {} tokens", amount / 1_000_000_000);
+    msg!("From: {}", from_token_account.key());
+    msg!("To: {}", to_token_account.key());
+    msg!("Amount: {} tokens", to_ui_amount(amount, DEVR_DECIMALS));
 
     Ok(())
 }
+
+pub fn handler(ctx: Context<TransferTokens>, amount: u64, keep_reserve: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    check_transfer_amount(amount, config.min_transfer, config.max_transfer)?;
+    execute_transfer(
+        &ctx.accounts.from_token_account,
+        &ctx.accounts.to_token_account,
+        &ctx.accounts.recipient_blocklist,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        amount,
+        keep_reserve,
+    )
+}
+
+/// Same as `transfer`, but both accounts must hold the platform's DEVR mint
+pub fn devr_handler(ctx: Context<TransferDevr>, amount: u64, keep_reserve: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    check_transfer_amount(amount, config.min_transfer, config.max_transfer)?;
+    execute_transfer(
+        &ctx.accounts.from_token_account,
+        &ctx.accounts.to_token_account,
+        &ctx.accounts.recipient_blocklist,
+        &ctx.accounts.authority,
+        &ctx.accounts.token_program,
+        amount,
+        keep_reserve,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{MAX_TRANSFER_AMOUNT, MIN_TRANSFER_AMOUNT, ONE_DEVR};
+
+    #[test]
+    fn test_default_transfer_limits() {
+        assert!(check_transfer_amount(MIN_TRANSFER_AMOUNT - 1, MIN_TRANSFER_AMOUNT, MAX_TRANSFER_AMOUNT).is_err());
+        assert!(check_transfer_amount(MIN_TRANSFER_AMOUNT, MIN_TRANSFER_AMOUNT, MAX_TRANSFER_AMOUNT).is_ok());
+        assert!(check_transfer_amount(MAX_TRANSFER_AMOUNT, MIN_TRANSFER_AMOUNT, MAX_TRANSFER_AMOUNT).is_ok());
+        assert!(check_transfer_amount(MAX_TRANSFER_AMOUNT + 1, MIN_TRANSFER_AMOUNT, MAX_TRANSFER_AMOUNT).is_err());
+    }
+
+    #[test]
+    fn test_admin_set_transfer_limits() {
+        // Bounds come from config, so a tightened range rejects what the defaults allow
+        let (min_transfer, max_transfer) = (5 * ONE_DEVR, 50 * ONE_DEVR);
+        assert!(check_transfer_amount(ONE_DEVR, min_transfer, max_transfer).is_err());
+        assert!(check_transfer_amount(10 * ONE_DEVR, min_transfer, max_transfer).is_ok());
+        assert!(check_transfer_amount(100 * ONE_DEVR, min_transfer, max_transfer).is_err());
+    }
+}
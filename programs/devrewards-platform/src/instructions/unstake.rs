@@ -1,7 +1,11 @@
 use crate::constants::*;
 use crate::error::ErrorCode;
-use crate::events::UnstakeEvent;
-use crate::state::{StakeAccount, StakeCounter, GlobalStats, TokenConfig};
+use super::insurance_fund::{cover_shortfall, insurance_fee, load_insurance_fund};
+use crate::events::{CircuitBreakerTrippedEvent, InsuranceFundDrawnEvent, UnstakeEvent};
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, RewardConfig, StakeAccount, StakeCounter,
+    TokenConfig, UserPreferences, UserStakeStats, UserStats,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
@@ -15,38 +19,43 @@ pub struct Unstake<'info> {
     pub config: Account<'info, TokenConfig>,
 
     #[account(
+        mut,
         seeds = [b"stake-counter", user.key().as_ref()],
         bump
     )]
     pub counter: Account<'info, StakeCounter>,
 
     // User's stake account - to read stake data and close
+    /// CHECK: PDA derived from seeds. Loaded and closed manually in the handler so a
+    /// retry on an already-closed position reports StakeAlreadyClosed.
     #[account(
         mut,
         seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
-        bump = stake_account.bump,
-        has_one = user,  // Security: Ensure stake belongs to signer
-        close = user  // Return rent to user after closing
+        bump
     )]
-    pub stake_account: Account<'info, StakeAccount>,
+    pub stake_account: UncheckedAccount<'info>,
 
-    // User's token account - destination for tokens + rewards
-    #[account(mut, token::mint = config.mint, token::authority = user)]
+    // Destination for tokens + rewards: the user's payout preference if set,
+    // otherwise one of their own DEVR accounts (checked in the handler)
+    #[account(mut, token::mint = config.mint)]
     pub user_token_account: Account<'info, TokenAccount>,
 
-    // Program's vault - source of tokens
+    // Program's vault - source of tokens. Seeds make it canonical; the address
+    // check guarantees it is also the vault config points at.
     #[account(
         mut,
         seeds = [b"vault"],
-        bump
+        bump,
+        address = config.vault
     )]
     pub vault: Account<'info, TokenAccount>,
 
     // Vault authority PDA - needed to sign withdrawal
-    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    /// CHECK: PDA derived from seeds and matched against config, used to sign vault transfers
     #[account(
         seeds = [b"vault-authority"],
-        bump
+        bump,
+        address = config.vault_authority
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
@@ -57,92 +66,1263 @@ pub struct Unstake<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
 
+    // Per-user active stake total (created here for positions opened before it existed)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    // Lifetime per-user rollups (created here for users who staked before it existed)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance fund token account PDA, validated by seeds. Read manually so
+    /// unstakes keep working before an admin has created it.
+    #[account(
+        mut,
+        seeds = [b"insurance-fund"],
+        bump
+    )]
+    pub insurance_fund: UncheckedAccount<'info>,
+
+    /// CHECK: Boost vault token account PDA, validated by seeds. Only read and
+    /// debited when the position carries a boost deposit.
+    #[account(
+        mut,
+        seeds = [b"boost-vault"],
+        bump
+    )]
+    pub boost_vault: UncheckedAccount<'info>,
+
+    /// CHECK: User preferences PDA, validated by seeds. Read manually since most users
+    /// never set a payout destination.
+    #[account(
+        seeds = [b"user-preferences", user.key().as_ref()],
+        bump
+    )]
+    pub user_preferences: UncheckedAccount<'info>,
+
+    /// CHECK: Reward config PDA, validated by seeds. Read manually so rewards are paid
+    /// from the stake vault in DEVR until an admin splits them out.
+    #[account(
+        seeds = [b"reward-config"],
+        bump
+    )]
+    pub reward_config: UncheckedAccount<'info>,
+
+    // Split-mint payouts only: the configured reward vault and the recipient's
+    // account for the reward token (checked in the handler)
+    #[account(mut)]
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user_reward_token_account: Option<Account<'info, TokenAccount>>,
+
     // User who is unstaking
     #[account(mut)]
     pub user: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// `amount * rate_numerator / rate_denominator` accrued for `duration` seconds of an
+/// Actual/365 year. Intermediates are u128 and each step is floored in turn.
+fn simple_interest(amount: u64, rate_numerator: u64, rate_denominator: u64, duration: i64) -> Result<u64> {
+    let amount_with_rate = (amount as u128)
+        .checked_mul(rate_numerator as u128)
+        .and_then(|scaled| scaled.checked_div(rate_denominator as u128))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let interest = amount_with_rate
+        .checked_mul(duration.max(0) as u128)
+        .and_then(|scaled| scaled.checked_div(SECONDS_PER_YEAR as u128))
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(u64::try_from(interest).map_err(|_| ErrorCode::ArithmeticOverflow)?)
 }
 
-pub fn handler(ctx: Context<Unstake>, stake_count: u64) -> Result<()> {
-    let stake_account = &ctx.accounts.stake_account;
+/// Simple-interest reward for a position over its committed lock period, using the
+/// Actual/365 day-count convention (see SECONDS_PER_YEAR)
+pub fn compute_rewards(
+    staked_amount: u64,
+    apy_numerator: u64,
+    apy_denominator: u64,
+    lock_duration: i64,
+) -> Result<u64> {
+    // Duration comes from the configured reward basis (see reward_duration).
+    // By default it's the committed lock, NOT time_elapsed:
+    // Example: Lock for 30 days → Get 30 days reward (even if unstake after 60 days)
+    simple_interest(staked_amount, apy_numerator, apy_denominator, lock_duration)
+}
+
+/// Extra reward from APY bonuses (loyalty, epoch) snapshotted at stake time
+pub fn compute_bonus_rewards(staked_amount: u64, bonus_bps: u64, lock_duration: i64) -> Result<u64> {
+    simple_interest(staked_amount, bonus_bps, BASIS_POINTS, lock_duration)
+}
+
+/// Base APY rewards raised by a boost multiplier (extra bps of the base amount)
+pub fn apply_boost(base_rewards: u64, boost_multiplier_bps: u64) -> Result<u64> {
+    let boost = (base_rewards as u128 * boost_multiplier_bps as u128) / BASIS_POINTS as u128;
+    u64::try_from(boost)
+        .ok()
+        .and_then(|boost| base_rewards.checked_add(boost))
+        .ok_or(error!(ErrorCode::ArithmeticOverflow))
+}
+
+/// Total rewards (base APY plus bonuses) a position earns over `reward_duration`
+fn rewards_over(stake_account: &StakeAccount, reward_duration: i64) -> Result<(u64, u64, u64)> {
+    // Blended across add_to_stake tranches
+    let staked_amount = stake_account.reward_principal();
+
+    // The rate locked in at stake time, not whatever the schedule says today
+    let apy_numerator = stake_account.apy_numerator;
+    let apy_denominator = stake_account.apy_denominator;
+    let base_rewards = apply_boost(
+        compute_rewards(staked_amount, apy_numerator, apy_denominator, reward_duration)?,
+        stake_account.boost_multiplier_bps,
+    )?;
+    let bonus_rewards = compute_bonus_rewards(
+        staked_amount,
+        stake_account.loyalty_bonus_bps + stake_account.epoch_bonus_bps,
+        reward_duration,
+    )?;
+    let principal_rewards = base_rewards
+        .checked_add(bonus_rewards)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    Ok((principal_rewards, apy_numerator, apy_denominator))
+}
+
+/// Config limits on which part of a lock earns rewards
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RewardWindow {
+    pub accrual_delay: i64, // Warm-up after staking that earns nothing
+    pub max_duration: i64,  // Cap on rewarded seconds, however long the lock
+    pub basis: u8,          // Committed lock or elapsed time (REWARD_BASIS_*)
+}
+
+impl RewardWindow {
+    pub fn from_config(config: &TokenConfig) -> Self {
+        Self {
+            accrual_delay: config.reward_accrual_delay,
+            max_duration: config.max_reward_eligible_duration,
+            basis: config.reward_basis,
+        }
+    }
+
+    /// Applies the warm-up (flooring at zero), then the cap
+    pub fn apply(&self, eligible_duration: i64) -> i64 {
+        eligible_duration
+            .saturating_sub(self.accrual_delay)
+            .max(0)
+            .min(self.max_duration)
+    }
+}
+
+/// Seconds of the lock that earn rewards: frozen time and the accrual delay are
+/// excluded and the rest is capped, however long the lock itself is. On the elapsed
+/// basis only time staked so far counts, so an early exit is pro-rated and a late
+/// one earns nothing extra.
+pub fn reward_duration(stake_account: &StakeAccount, now: i64, window: RewardWindow) -> i64 {
+    let eligible_duration = if window.basis == REWARD_BASIS_ELAPSED {
+        stake_account.accrued_eligible_duration(now)
+    } else {
+        stake_account.reward_eligible_duration(now)
+    };
+    window.apply(eligible_duration)
+}
+
+/// Rewards (principal/DEVR units) a matured position still pays out, with the APY used.
+/// Time under a review hold doesn't earn rewards; the tier still follows the lock.
+/// Rewards already compounded into new positions are not paid again.
+pub fn position_rewards(
+    stake_account: &StakeAccount,
+    now: i64,
+    window: RewardWindow,
+) -> Result<(u64, u64, u64)> {
+    let (rewards, apy_numerator, apy_denominator) =
+        rewards_over(stake_account, reward_duration(stake_account, now, window))?;
+
+    Ok((
+        rewards.saturating_sub(stake_account.compounded_rewards),
+        apy_numerator,
+        apy_denominator,
+    ))
+}
+
+/// Most a position can earn: its whole committed lock, whatever the reward basis
+pub fn max_position_rewards(stake_account: &StakeAccount, window: RewardWindow) -> Result<u64> {
+    let committed = RewardWindow {
+        basis: REWARD_BASIS_COMMITTED,
+        ..window
+    };
+    let (rewards, _, _) = position_rewards(stake_account, stake_account.maturity_timestamp, committed)?;
+    Ok(rewards)
+}
+
+/// Moves a position's reservation to the most it can now earn; only the difference
+/// is reserved or released. Returns the new reservation.
+pub fn reserve_position_rewards(
+    stake_account: &mut StakeAccount,
+    global_stats: &mut GlobalStats,
+    window: RewardWindow,
+    vault_balance: u64,
+) -> Result<u64> {
+    let reward_liability = max_position_rewards(stake_account, window)?;
+    if reward_liability > stake_account.reward_liability {
+        global_stats.reserve_rewards(reward_liability - stake_account.reward_liability, vault_balance)?;
+    } else {
+        global_stats.release_rewards(stake_account.reward_liability - reward_liability)?;
+    }
+    stake_account.reward_liability = reward_liability;
+    Ok(reward_liability)
+}
+
+/// Rewards accrued so far (pro-rated through `now`) that haven't been compounded yet
+pub fn uncompounded_rewards(
+    stake_account: &StakeAccount,
+    now: i64,
+    window: RewardWindow,
+) -> Result<u64> {
+    let (accrued, _, _) =
+        rewards_over(stake_account, window.apply(stake_account.accrued_eligible_duration(now)))?;
+    Ok(accrued.saturating_sub(stake_account.compounded_rewards))
+}
+
+/// Whether `destination` is where this user's unstakes pay out: their stored
+/// preference if they set one, otherwise any token account they own
+pub fn is_payout_destination(
+    preference: Option<Pubkey>,
+    destination: Pubkey,
+    destination_owner: Pubkey,
+    user: Pubkey,
+) -> bool {
+    match preference {
+        Some(preference) => destination == preference,
+        None => destination_owner == user,
+    }
+}
+
+/// Share of a position's rewards that goes with `amount` of its principal
+pub fn partial_rewards(rewards: u64, amount: u64, staked_amount: u64) -> u64 {
+    if amount >= staked_amount {
+        return rewards;
+    }
+    ((rewards as u128 * amount as u128) / staked_amount as u128) as u64
+}
+
+/// Whether split-mint rewards go to the configured reward vault's token, paid to the
+/// same owner as the principal. `reward_account` is the (mint, owner) passed in.
+pub fn is_reward_route(
+    reward_config: &RewardConfig,
+    reward_vault: Option<Pubkey>,
+    reward_account: Option<(Pubkey, Pubkey)>,
+    recipient: Pubkey,
+) -> bool {
+    reward_vault == Some(reward_config.reward_vault)
+        && reward_account == Some((reward_config.reward_mint, recipient))
+}
+
+/// Splits what an unstake owes into (stake vault, reward vault) amounts. Split-mint
+/// rewards come from the reward vault, so only principal leaves the stake vault.
+pub fn payout_sources(principal: u64, rewards: u64, split: bool) -> Result<(u64, u64)> {
+    if split {
+        return Ok((principal, rewards));
+    }
+    let total = principal
+        .checked_add(rewards)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok((total, 0))
+}
+
+/// Whether a single withdrawal stays under the anti-whale cap
+pub fn within_unstake_cap(total_amount: u64, max_unstake_per_tx: u64) -> bool {
+    max_unstake_per_tx == NO_UNSTAKE_CAP || total_amount <= max_unstake_per_tx
+}
+
+/// Program-wide and per-user tallies that opening or closing a position updates
+pub struct PositionBooks<'a> {
+    pub counter: &'a mut StakeCounter,
+    pub global_stats: &'a mut GlobalStats,
+    pub user_stake_stats: &'a mut UserStakeStats,
+    pub user_stats: &'a mut UserStats,
+}
+
+/// Effects of withdrawing `principal` plus `rewards` from a position. Applied before
+/// any tokens move (checks-effects-interactions), so a reentrant unstake finds the
+/// position already withdrawn. Returns the share of its reservation released.
+pub fn settle_unstake(
+    stake_account: &mut StakeAccount,
+    books: &mut PositionBooks,
+    principal: u64,
+    rewards: u64,
+) -> Result<u64> {
+    settle(stake_account, books, principal, rewards, 0)
+}
+
+/// Same as `settle_unstake` for a rollover: the whole position closes, but only
+/// `principal` and `rewards` leave the vault. The rest of the principal stays
+/// behind to back the new position.
+pub fn settle_rollover(
+    stake_account: &mut StakeAccount,
+    books: &mut PositionBooks,
+    principal: u64,
+    rewards: u64,
+) -> Result<u64> {
+    let restaked_principal = stake_account
+        .staked_amount
+        .checked_sub(principal)
+        .ok_or(ErrorCode::UnstakeAmountExceedsStake)?;
+    settle(stake_account, books, principal, rewards, restaked_principal)
+}
+
+fn settle(
+    stake_account: &mut StakeAccount,
+    books: &mut PositionBooks,
+    principal: u64,
+    rewards: u64,
+    restaked_principal: u64,
+) -> Result<u64> {
+    let staked_amount = stake_account.staked_amount;
+    let removed = principal
+        .checked_add(restaked_principal)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let closes_position = removed == staked_amount;
+    // The withdrawn share of the position's reservation is no longer owed
+    let released_liability = partial_rewards(stake_account.reward_liability, removed, staked_amount);
+    if closes_position {
+        stake_account.mark_withdrawn()?;
+    } else {
+        stake_account.withdraw_principal(removed)?;
+        stake_account.reward_liability -= released_liability;
+    }
+    // A request covers one withdrawal; what stays staked needs a new one
+    if stake_account.unstake_requested_at != 0 {
+        stake_account.unstake_requested_at = 0;
+        books.counter.finish_unbonding();
+    }
+
+    books.global_stats.record_unstake(principal, rewards)?;
+    books.global_stats.record_rollover(restaked_principal)?;
+    books.global_stats.release_rewards(released_liability)?;
+    // Saturating: positions opened before stats tracking were never added
+    books.user_stake_stats.total_active_staked = books
+        .user_stake_stats
+        .total_active_staked
+        .saturating_sub(removed);
+    books.user_stats.record_unstake(rewards, closes_position)?;
+    Ok(released_liability)
+}
+
+/// Circuit breaker: adds a withdrawal to the rolling window and pauses new stakes
+/// if the window's volume spikes past the threshold
+pub fn record_withdrawal(
+    global_stats: &mut GlobalStats,
+    config: &TokenConfig,
+    flags: u64,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    let tripped = is_feature_enabled(flags, FEATURE_CIRCUIT_BREAKER)
+        && global_stats.record_unstake_volume(
+            amount,
+            now,
+            config.circuit_breaker_window,
+            config.circuit_breaker_threshold,
+        )?;
+    if tripped {
+        emit!(CircuitBreakerTrippedEvent {
+            window_volume: global_stats.window_unstake_volume()?,
+            threshold: config.circuit_breaker_threshold,
+            window: config.circuit_breaker_window,
+            timestamp: now,
+        });
+        msg!("⚠️ Circuit breaker tripped - staking paused");
+    }
+    Ok(())
+}
+
+/// Withdraws a matured position. `amount` takes only part of the principal (with
+/// the same share of rewards) and leaves the rest staked; None withdraws it all.
+pub fn handler(ctx: Context<Unstake>, stake_count: u64, amount: Option<u64>) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let mut stake_account = StakeAccount::load_open(
+        &ctx.accounts.stake_account,
+        stake_count,
+        ctx.accounts.counter.stake_count,
+    )?;
+    // Security: Ensure stake belongs to signer
+    require_keys_eq!(
+        stake_account.user,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+    require!(!stake_account.is_withdrawn, ErrorCode::AlreadyWithdrawn);
+
+    require!(
+        is_payout_destination(
+            UserPreferences::load_payout_destination(&ctx.accounts.user_preferences)?,
+            ctx.accounts.user_token_account.key(),
+            ctx.accounts.user_token_account.owner,
+            ctx.accounts.user.key(),
+        ),
+        ErrorCode::PayoutDestinationMismatch
+    );
+
+    let reward_route =
+        RewardConfig::load_split(&ctx.accounts.reward_config, &ctx.accounts.config.mint)?;
+    if let Some(reward_config) = &reward_route {
+        require!(
+            is_reward_route(
+                reward_config,
+                ctx.accounts.reward_vault.as_ref().map(|vault| vault.key()),
+                ctx.accounts
+                    .user_reward_token_account
+                    .as_ref()
+                    .map(|account| (account.mint, account.owner)),
+                ctx.accounts.user_token_account.owner,
+            ),
+            ErrorCode::RewardAccountMismatch
+        );
+    }
+    let split = reward_route.is_some();
+
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp;
 
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+
     let time_elapsed = current_time - stake_account.staked_at;
 
     require!(
-        time_elapsed >= stake_account.lock_duration,
+        is_stake_matured(stake_account.maturity_timestamp, current_time),
         ErrorCode::StillLocked
     );
+    stake_account.check_unstake_delay(ctx.accounts.config.unstake_delay, current_time)?;
 
     let staked_amount = stake_account.staked_amount;
+    let principal = amount.unwrap_or(staked_amount);
+    require!(principal > 0, ErrorCode::AmountTooSmall);
+    require!(
+        principal <= staked_amount,
+        ErrorCode::UnstakeAmountExceedsStake
+    );
+    let closes_position = principal == staked_amount;
     let lock_duration = stake_account.lock_duration;
+    let window = RewardWindow::from_config(&ctx.accounts.config);
+    let (principal_rewards, apy_numerator, apy_denominator) =
+        position_rewards(&stake_account, current_time, window)?;
+    let principal_rewards = partial_rewards(principal_rewards, principal, staked_amount);
 
-    // Get the appropriate APY based on lock duration
-    let (apy_numerator, apy_denominator) = get_apy_for_duration(lock_duration);
-    let amount_with_apy = (staked_amount * apy_numerator) / apy_denominator;
-
-    // CRITICAL FIX: Use lock_duration, NOT time_elapsed
-    // User gets rewards for committed lock period only
-    // Example: Lock for 30 days → Get 30 days reward (even if unstake after 60 days)
-    let rewards = (amount_with_apy * lock_duration as u64) / SECONDS_PER_YEAR;
+    // APY accrues in principal (DEVR) units, and caps, the breaker and stats all count
+    // DEVR. Only the reward transfer is converted to reward-token units.
+    let reward_decimals = ctx.accounts.config.reward_decimals;
+    let rewards = convert_decimals(principal_rewards, DEVR_DECIMALS, reward_decimals)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    let reward_unit = 10u64
+        .checked_pow(reward_decimals as u32)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    let total_amount = staked_amount + rewards;
+    let total_amount = principal
+        .checked_add(principal_rewards)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
 
     require!(
-        ctx.accounts.vault.amount >= total_amount,
-        ErrorCode::InsufficientVaultBalance
+        within_unstake_cap(total_amount, ctx.accounts.config.max_unstake_per_tx),
+        ErrorCode::UnstakeTooLarge
     );
+    ctx.accounts
+        .global_stats
+        .check_reward_cap(principal_rewards, ctx.accounts.config.max_total_rewards)?;
+
+    let (stake_vault_owed, reward_vault_owed) = payout_sources(principal, rewards, split)?;
+    if let Some(reward_vault) = &ctx.accounts.reward_vault {
+        require!(
+            reward_vault.amount >= reward_vault_owed,
+            ErrorCode::InsufficientVaultBalance
+        );
+    }
+
+    // A short reward vault is topped up from the insurance fund, if there is one
+    let insurance_fund = load_insurance_fund(&ctx.accounts.insurance_fund)?;
+    let fund_balance = insurance_fund.as_ref().map_or(0, |fund| fund.amount);
+    let (from_vault, from_fund, uncovered) =
+        cover_shortfall(stake_vault_owed, ctx.accounts.vault.amount, fund_balance);
+    require!(uncovered == 0, ErrorCode::InsufficientVaultBalance);
+
+    // Fees only feed the fund while the vault can pay in full. The fund holds DEVR,
+    // so split-mint rewards carry no fee.
+    let fee = if !split && insurance_fund.is_some() && from_fund == 0 {
+        insurance_fee(principal_rewards, ctx.accounts.config.insurance_fee_bps)
+    } else {
+        0
+    };
+
+    // The boost deposit comes back in full once the position closes; it never
+    // earned from the reward vault
+    let boost_returned = if closes_position { stake_account.boost_amount } else { 0 };
+    let principal_rewards = principal_rewards - fee;
+    // A fee is only taken when rewards are paid in DEVR, where the two units match
+    let rewards = rewards - fee;
+    let total_amount = total_amount - fee;
+
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+
+    // Settled before any tokens move; see settle_unstake
+    settle_unstake(
+        &mut stake_account,
+        &mut PositionBooks {
+            counter: &mut ctx.accounts.counter,
+            global_stats: &mut ctx.accounts.global_stats,
+            user_stake_stats: &mut ctx.accounts.user_stake_stats,
+            user_stats: &mut ctx.accounts.user_stats,
+        },
+        principal,
+        principal_rewards,
+    )?;
+    stake_account.try_serialize(
+        &mut &mut ctx.accounts.stake_account.try_borrow_mut_data()?[..],
+    )?;
+    let global_stats = &mut ctx.accounts.global_stats;
+    global_stats.record_insurance_fee(fee)?;
+    global_stats.record_insurance_draw(from_fund)?;
+
+    let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+    let signer = &[&seeds[..]];
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.vault.to_account_info(),
         to: ctx.accounts.user_token_account.to_account_info(),
         authority: ctx.accounts.vault_authority.to_account_info(),
     };
-
     let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(
+        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+        from_vault - fee,
+    )?;
 
-    let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+    if fee > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.insurance_fund.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            fee,
+        )?;
+    }
 
-    let signer = &[&seeds[..]];
+    if from_fund > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.insurance_fund.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            from_fund,
+        )?;
 
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        emit!(InsuranceFundDrawnEvent {
+            user: stake_account.user,
+            stake_index: stake_count,
+            shortfall: from_fund,
+            fund_balance: fund_balance - from_fund,
+            timestamp: current_time,
+        });
+        msg!("🛟 Insurance fund covered {} DEVR shortfall", to_ui_amount(from_fund, DEVR_DECIMALS));
+    }
 
-    token::transfer(cpi_ctx, total_amount)?;
+    // Only split-mint payouts owe the reward vault, and then both accounts were checked
+    if reward_vault_owed > 0 {
+        if let (Some(reward_vault), Some(user_reward_token_account)) = (
+            &ctx.accounts.reward_vault,
+            &ctx.accounts.user_reward_token_account,
+        ) {
+            let cpi_accounts = Transfer {
+                from: reward_vault.to_account_info(),
+                to: user_reward_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            token::transfer(
+                CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+                reward_vault_owed,
+            )?;
+        }
+    }
 
-    // Update global stats
-    let global_stats = &mut ctx.accounts.global_stats;
-    global_stats.total_staked = global_stats
-        .total_staked
-        .checked_sub(staked_amount)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
-    global_stats.total_rewards_paid = global_stats
-        .total_rewards_paid
-        .checked_add(rewards)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if boost_returned > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.boost_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            boost_returned,
+        )?;
+    }
+    if closes_position {
+        // Return rent to user after closing
+        StakeAccount::close(
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+        )?;
+        ctx.accounts.counter.close_position();
+    }
+
+    // Circuit breaker: pause new stakes if withdrawals spike within the window
+    record_withdrawal(
+        &mut ctx.accounts.global_stats,
+        &ctx.accounts.config,
+        FeatureFlags::load_flags(&ctx.accounts.feature_flags)?,
+        total_amount,
+        current_time,
+    )?;
 
     // Emit event for off-chain tracking
     emit!(UnstakeEvent {
         user: stake_account.user,
         stake_index: stake_count,
-        principal: staked_amount,
-        rewards,
+        principal,
+        rewards: principal_rewards,
         total_withdrawn: total_amount,
         lock_duration,
+        reward_duration: reward_duration(
+            &stake_account,
+            current_time,
+            RewardWindow::from_config(&ctx.accounts.config),
+        ),
         apy_numerator,
         apy_denominator,
+        destination: ctx.accounts.user_token_account.key(),
+        boost_returned,
+        remaining_principal: stake_account.staked_amount,
         timestamp: clock.unix_timestamp,
     });
 
     msg!("✅ Tokens unstaked successfully!");
     msg!("User: {}", stake_account.user);
     msg!("Stake Index: #{}", stake_count);
-    msg!("Principal: {} DEVR", staked_amount / 1_000_000_000);
-    msg!("Rewards: {} DEVR", rewards / 1_000_000_000);
-    msg!("Total returned: {} DEVR", total_amount / 1_000_000_000);
+    msg!("Principal: {} DEVR", to_ui_amount(principal, DEVR_DECIMALS));
+    if !closes_position {
+        msg!("Still staked: {} DEVR", to_ui_amount(stake_account.staked_amount, DEVR_DECIMALS));
+    }
+    msg!(
+        "Rewards: {} (reward token, {} decimals)",
+        rewards / reward_unit,
+        reward_decimals
+    );
+    msg!("Total returned: {} DEVR", to_ui_amount(total_amount, DEVR_DECIMALS));
+    if boost_returned > 0 {
+        msg!("Boost returned: {} DEVR", to_ui_amount(boost_returned, DEVR_DECIMALS));
+    }
     msg!("Lock duration: {} days", lock_duration / 86400);
     msg!("Time staked: {} days", time_elapsed / 86400);
     msg!("APY Rate: {}%", (apy_numerator * 100) / apy_denominator);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{test_global_stats, test_stake_account};
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+
+    fn unlock_at_from(staked_at: i64, lock_duration: i64) -> i64 {
+        get_maturity_timestamp(staked_at, lock_duration).unwrap()
+    }
+
+    fn window(accrual_delay: i64, max_duration: i64) -> RewardWindow {
+        RewardWindow {
+            accrual_delay,
+            max_duration,
+            basis: REWARD_BASIS_COMMITTED,
+        }
+    }
+
+    fn rewards_for(lock_duration: i64) -> u64 {
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), lock_duration);
+        compute_rewards(PRINCIPAL, num, denom, lock_duration).unwrap()
+    }
+
+    #[test]
+    fn test_golden_rewards_tier_1_seven_days() {
+        // 5% APY for 7 days
+        assert_eq!(rewards_for(SECONDS_IN_SEVEN_DAYS), 9_589_041_095);
+    }
+
+    #[test]
+    fn test_golden_rewards_tier_2_thirty_days() {
+        // 10% APY for 30 days
+        assert_eq!(rewards_for(SECONDS_IN_THIRTY_DAYS), 82_191_780_821);
+    }
+
+    #[test]
+    fn test_golden_rewards_tier_3_ninety_days() {
+        // 20% APY for 90 days
+        assert_eq!(rewards_for(SECONDS_IN_NINETY_DAYS), 493_150_684_931);
+    }
+
+    #[test]
+    fn test_max_stake_over_max_lock_does_not_overflow() {
+        // 100,000 DEVR at tier 3 (20%) for 10 years: the old u64 intermediates overflowed
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), MAX_LOCK_DURATION);
+        assert_eq!((num, denom), (TIER_3_APY_NUMERATOR, TIER_3_APY_DENOMINATOR));
+        assert!((MAX_STAKE_AMOUNT / denom * num).checked_mul(MAX_LOCK_DURATION as u64).is_none());
+
+        // 20% a year for exactly 10 Actual/365 years is 2x the principal
+        assert_eq!(
+            compute_rewards(MAX_STAKE_AMOUNT, num, denom, MAX_LOCK_DURATION).unwrap(),
+            200_000_000_000_000
+        );
+        assert_eq!(
+            compute_bonus_rewards(MAX_STAKE_AMOUNT, 10_000, MAX_LOCK_DURATION).unwrap(),
+            1_000_000_000_000_000
+        );
+
+        // A result that can't fit in u64 is an error, not a wrapped value
+        assert_eq!(
+            compute_rewards(u64::MAX, 1, 1, MAX_LOCK_DURATION).err(),
+            Some(error!(ErrorCode::ArithmeticOverflow))
+        );
+        assert_eq!(apply_boost(u64::MAX, 1).err(), Some(error!(ErrorCode::ArithmeticOverflow)));
+    }
+
+    #[test]
+    fn test_golden_rewards_just_below_boundaries() {
+        // One second short of 30 days still pays tier 1
+        assert_eq!(rewards_for(SECONDS_IN_THIRTY_DAYS - 1), 41_095_874_556);
+
+        // One second short of 90 days still pays tier 2
+        assert_eq!(rewards_for(SECONDS_IN_NINETY_DAYS - 1), 246_575_310_755);
+    }
+
+    #[test]
+    fn test_exact_ninety_day_boundary_is_consistent() {
+        // Locked for exactly 90 days, unstaked at exactly the 90-day mark
+        let staked_at = 1_700_000_000;
+        let lock_duration = SECONDS_IN_NINETY_DAYS;
+        let unlock_at = staked_at + lock_duration;
+
+        // Tier helper, maturity gate and countdown all agree at the boundary second
+        assert_eq!(get_tier_for_duration(&default_apy_tiers(), lock_duration), 3);
+        assert!(is_stake_matured(unlock_at_from(staked_at, lock_duration), unlock_at));
+        assert_eq!(get_seconds_to_maturity(unlock_at_from(staked_at, lock_duration), unlock_at), 0);
+
+        // ...one second earlier the countdown is still running; the gate allows for skew
+        assert!(is_stake_matured(unlock_at_from(staked_at, lock_duration), unlock_at - 1));
+        assert_eq!(get_seconds_to_maturity(unlock_at_from(staked_at, lock_duration), unlock_at - 1), 1);
+
+        // Pays exactly the tier 3 reward (20% APY for 90 days)
+        assert_eq!(rewards_for(lock_duration), 493_150_684_931);
+    }
+
+    #[test]
+    fn test_every_tier_boundary_matures_on_its_last_second() {
+        let staked_at = 1_700_000_000;
+        for (lock_duration, tier) in [
+            (SECONDS_IN_SEVEN_DAYS, 1),
+            (SECONDS_IN_THIRTY_DAYS, 2),
+            (SECONDS_IN_NINETY_DAYS, 3),
+        ] {
+            let unlock_at = staked_at + lock_duration;
+            let gate_opens_at = unlock_at - UNSTAKE_SKEW_TOLERANCE;
+            assert_eq!(get_tier_for_duration(&default_apy_tiers(), lock_duration), tier);
+            assert!(!is_stake_matured(unlock_at_from(staked_at, lock_duration), gate_opens_at - 1));
+            assert!(is_stake_matured(unlock_at_from(staked_at, lock_duration), gate_opens_at));
+        }
+    }
+
+    #[test]
+    fn test_unstake_within_skew_tolerance() {
+        let staked_at = 1_700_000_000;
+        let lock_duration = SECONDS_IN_SEVEN_DAYS;
+        let maturity = unlock_at_from(staked_at, lock_duration);
+
+        // Unstaking at lock_duration - tolerance counts as matured...
+        assert!(is_stake_matured(maturity, staked_at + lock_duration - UNSTAKE_SKEW_TOLERANCE));
+        // ...but not a second before, so locks shrink by at most the tolerance
+        assert!(!is_stake_matured(maturity, staked_at + lock_duration - UNSTAKE_SKEW_TOLERANCE - 1));
+        assert!(!is_stake_matured(maturity, staked_at));
+        const { assert!(UNSTAKE_SKEW_TOLERANCE <= 60) };
+    }
+
+    #[test]
+    fn test_freeze_interval_reduces_payout_proportionally() {
+        let staked_at = 1_700_000_000;
+        let day = 86_400;
+        let mut position = StakeAccount {
+            frozen_at: staked_at + 5 * day,
+            unfrozen_at: staked_at + 20 * day,
+            ..test_stake_account(staked_at, SECONDS_IN_THIRTY_DAYS)
+        };
+
+        // Half the lock spent frozen -> half the tier 2 reward
+        let now = staked_at + SECONDS_IN_THIRTY_DAYS;
+        let reward_duration = position.reward_eligible_duration(now);
+        assert_eq!(reward_duration, SECONDS_IN_THIRTY_DAYS / 2);
+
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), position.lock_duration);
+        assert_eq!(
+            compute_rewards(PRINCIPAL, num, denom, reward_duration).unwrap(),
+            41_095_890_410
+        );
+
+        // Unfrozen stake earns the full golden value
+        position.frozen_at = 0;
+        position.unfrozen_at = 0;
+        assert_eq!(
+            compute_rewards(PRINCIPAL, num, denom, position.reward_eligible_duration(now)).unwrap(),
+            82_191_780_821
+        );
+    }
+
+    #[test]
+    fn test_actual_365_annualized_returns() {
+        // 1,000 DEVR keeps the annual amounts round
+        let principal = 1_000_000_000_000;
+        let year = SECONDS_PER_YEAR as i64;
+
+        // A full 365-day year earns exactly the tier APY: 20% of 1,000 DEVR
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), year);
+        assert_eq!(compute_rewards(principal, num, denom, year).unwrap(), 200_000_000_000);
+
+        // 90 days earns 90/365 of the annual amount (not a quarter)
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS);
+        let annual = principal * num / denom;
+        assert_eq!(
+            compute_rewards(principal, num, denom, SECONDS_IN_NINETY_DAYS).unwrap(),
+            annual * 90 / DAYS_PER_YEAR
+        );
+
+        // Twelve 30-day periods are 360 days, so they fall 5 days short of a year
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS);
+        let monthly = compute_rewards(principal, num, denom, SECONDS_IN_THIRTY_DAYS).unwrap();
+        let annual = principal * num / denom;
+        let five_days = annual * 5 / DAYS_PER_YEAR;
+        assert!((annual - 12 * monthly).abs_diff(five_days) <= 12); // Flooring per period
+    }
+
+    #[test]
+    fn test_lock_beyond_reward_cap_earns_capped_rewards() {
+        let staked_at = 1_700_000_000;
+        let ten_years = MAX_LOCK_DURATION;
+        let mut position = StakeAccount {
+            staked_amount: 100_000_000_000, // 100 DEVR
+            ..test_stake_account(staked_at, ten_years)
+        };
+        let now = position.maturity_timestamp;
+        let cap = window(0, DEFAULT_MAX_REWARD_ELIGIBLE_DURATION);
+
+        // A 10-year lock is rewarded as if it were 3 years: 20% x 3 = 60 DEVR
+        assert_eq!(
+            reward_duration(&position, now, cap),
+            DEFAULT_MAX_REWARD_ELIGIBLE_DURATION
+        );
+        let (rewards, _, _) = position_rewards(&position, now, cap).unwrap();
+        assert_eq!(rewards, 60_000_000_000);
+
+        // Locks under the cap are unaffected
+        position.lock_duration = SECONDS_IN_NINETY_DAYS;
+        position.maturity_timestamp = staked_at + SECONDS_IN_NINETY_DAYS;
+        let now = position.maturity_timestamp;
+        assert_eq!(reward_duration(&position, now, cap), SECONDS_IN_NINETY_DAYS);
+        assert_eq!(
+            position_rewards(&position, now, cap).unwrap().0,
+            position_rewards(&position, now, window(0, MAX_LOCK_DURATION)).unwrap().0
+        );
+    }
+
+    #[test]
+    fn test_accrual_delay_reduces_rewards() {
+        // 30-day lock with a 1-day warm-up earns for 29 days
+        let delayed = window(SECONDS_PER_DAY, MAX_LOCK_DURATION);
+        assert_eq!(delayed.apply(SECONDS_IN_THIRTY_DAYS), 29 * SECONDS_PER_DAY);
+
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_THIRTY_DAYS);
+        let full = compute_rewards(PRINCIPAL, num, denom, SECONDS_IN_THIRTY_DAYS).unwrap();
+        let reduced = compute_rewards(PRINCIPAL, num, denom, delayed.apply(SECONDS_IN_THIRTY_DAYS)).unwrap();
+        assert_eq!(reduced, 79_452_054_794);
+        assert!(reduced < full);
+    }
+
+    #[test]
+    fn test_short_lock_inside_accrual_delay_earns_nothing() {
+        // A 7-day lock with a 10-day warm-up floors at zero
+        let delayed = window(10 * SECONDS_PER_DAY, MAX_LOCK_DURATION);
+        assert_eq!(delayed.apply(SECONDS_IN_SEVEN_DAYS), 0);
+
+        let (num, denom) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_SEVEN_DAYS);
+        assert_eq!(
+            compute_rewards(PRINCIPAL, num, denom, delayed.apply(SECONDS_IN_SEVEN_DAYS)).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_accrual_delay_applies_before_the_cap() {
+        // 10-year lock, 1-day delay, 3-year cap: the cap still binds
+        let both = window(SECONDS_PER_DAY, DEFAULT_MAX_REWARD_ELIGIBLE_DURATION);
+        assert_eq!(both.apply(MAX_LOCK_DURATION), DEFAULT_MAX_REWARD_ELIGIBLE_DURATION);
+    }
+
+    #[test]
+    fn test_large_position_hits_unstake_cap() {
+        // 10,000 DEVR for 90 days pays principal + 493 DEVR of rewards
+        let total = PRINCIPAL + rewards_for(SECONDS_IN_NINETY_DAYS);
+        let cap = 5_000_000_000_000; // 5,000 DEVR per transaction
+
+        assert!(!within_unstake_cap(total, cap));
+        assert!(within_unstake_cap(cap, cap));
+        assert!(within_unstake_cap(total, NO_UNSTAKE_CAP));
+    }
+
+    #[test]
+    fn test_loyalty_bonus_rewards() {
+        // +1% APY on 10,000 DEVR for 365 days = 100 DEVR
+        let year = SECONDS_PER_YEAR as i64;
+        assert_eq!(
+            compute_bonus_rewards(PRINCIPAL, LOYALTY_APY_BONUS_BPS, year).unwrap(),
+            100_000_000_000
+        );
+
+        // No bonus snapshotted, nothing extra
+        assert_eq!(compute_bonus_rewards(PRINCIPAL, 0, year).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reward_basis_early_on_time_and_late() {
+        let staked_at = 1_700_000_000;
+        let position = ninety_day_position(staked_at);
+        let maturity = position.maturity_timestamp;
+        let committed = window(0, MAX_LOCK_DURATION);
+        let elapsed = RewardWindow {
+            basis: REWARD_BASIS_ELAPSED,
+            ..committed
+        };
+        let full = rewards_for(SECONDS_IN_NINETY_DAYS);
+
+        // Early (an override/flexible exit a third of the way in)
+        let early = staked_at + 30 * SECONDS_PER_DAY;
+        assert_eq!(reward_duration(&position, early, committed), SECONDS_IN_NINETY_DAYS);
+        assert_eq!(reward_duration(&position, early, elapsed), 30 * SECONDS_PER_DAY);
+        assert_eq!(position_rewards(&position, early, committed).unwrap().0, full);
+        let (num, den) = get_apy_for_duration(&default_apy_tiers(), SECONDS_IN_NINETY_DAYS);
+        assert_eq!(
+            position_rewards(&position, early, elapsed).unwrap().0,
+            compute_rewards(PRINCIPAL, num, den, 30 * SECONDS_PER_DAY).unwrap()
+        );
+
+        // On time, and late: both pay exactly the committed lock
+        for now in [maturity, maturity + 60 * SECONDS_PER_DAY] {
+            assert_eq!(position_rewards(&position, now, committed).unwrap().0, full);
+            assert_eq!(position_rewards(&position, now, elapsed).unwrap().0, full);
+        }
+    }
+
+    #[test]
+    fn test_payout_destination_preference() {
+        let user = Pubkey::new_unique();
+        let own_account = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let treasury_owner = Pubkey::new_unique();
+
+        // Unset: any account the user owns
+        assert!(is_payout_destination(None, own_account, user, user));
+        assert!(!is_payout_destination(None, treasury, treasury_owner, user));
+
+        // Set: only the stored destination, even over the user's own accounts
+        assert!(is_payout_destination(Some(treasury), treasury, treasury_owner, user));
+        assert!(!is_payout_destination(Some(treasury), own_account, user, user));
+    }
+
+    fn ninety_day_position(staked_at: i64) -> StakeAccount {
+        test_stake_account(staked_at, SECONDS_IN_NINETY_DAYS)
+    }
+
+    #[test]
+    fn test_tier_change_after_stake_does_not_alter_payout() {
+        let staked_at = 1_700_000_000;
+        let no_cap = window(0, MAX_LOCK_DURATION);
+        let position = ninety_day_position(staked_at);
+        let maturity = position.maturity_timestamp;
+        let (before, apy_numerator, apy_denominator) =
+            position_rewards(&position, maturity, no_cap).unwrap();
+        assert_eq!(before, rewards_for(SECONDS_IN_NINETY_DAYS));
+
+        // Governance raises tier 3 from 20% to 30% mid-lock; the open position
+        // still pays the 20% it locked in
+        let mut raised = default_apy_tiers();
+        raised[2].apy_numerator = 30;
+        assert_eq!((apy_numerator, apy_denominator), (20, 100));
+        assert_eq!(
+            position_rewards(&position, maturity, no_cap).unwrap(),
+            (before, apy_numerator, apy_denominator)
+        );
+
+        // Only a position opened under the new schedule earns the new rate
+        let mut later = ninety_day_position(staked_at + 45 * SECONDS_PER_DAY);
+        later.lock_in_apy(&raised, false);
+        let (later_rewards, later_numerator, _) =
+            position_rewards(&later, later.maturity_timestamp, no_cap).unwrap();
+        assert_eq!(later_numerator, 30);
+        assert_eq!(
+            later_rewards,
+            compute_rewards(PRINCIPAL, 30, 100, SECONDS_IN_NINETY_DAYS).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_boosted_and_unboosted_payouts() {
+        let staked_at = 1_700_000_000;
+        let no_cap = window(0, MAX_LOCK_DURATION);
+        let plain = ninety_day_position(staked_at);
+        let maturity = plain.maturity_timestamp;
+        let (plain_rewards, _, _) = position_rewards(&plain, maturity, no_cap).unwrap();
+        assert_eq!(plain_rewards, rewards_for(SECONDS_IN_NINETY_DAYS));
+
+        // 2,000 DEVR boost on 10,000 DEVR -> base rate x1.2
+        let mut boosted = ninety_day_position(staked_at);
+        boosted.boost_amount = 2_000_000_000_000;
+        boosted.boost_multiplier_bps =
+            get_boost_multiplier_bps(boosted.boost_amount, boosted.staked_amount);
+        assert_eq!(boosted.boost_multiplier_bps, 2_000);
+        let (boosted_rewards, _, _) = position_rewards(&boosted, maturity, no_cap).unwrap();
+        assert_eq!(boosted_rewards, plain_rewards + plain_rewards / 5);
+
+        // Bonuses snapshotted separately aren't multiplied
+        boosted.loyalty_bonus_bps = LOYALTY_APY_BONUS_BPS;
+        let (with_bonus, _, _) = position_rewards(&boosted, maturity, no_cap).unwrap();
+        assert_eq!(
+            with_bonus - boosted_rewards,
+            compute_bonus_rewards(PRINCIPAL, LOYALTY_APY_BONUS_BPS, SECONDS_IN_NINETY_DAYS).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_partial_unstake_then_rest_pays_the_full_position() {
+        let staked_at = 1_700_000_000;
+        let no_cap = window(0, MAX_LOCK_DURATION);
+        let mut position = ninety_day_position(staked_at);
+        // A mid-lock top-up and some compounded rewards, so both get split too
+        position.add_tranche(2_000_000_000_000, staked_at + 30 * SECONDS_PER_DAY).unwrap();
+        position.compounded_rewards = 50_000_000_000;
+        let maturity = position.maturity_timestamp;
+        let (full_rewards, _, _) = position_rewards(&position, maturity, no_cap).unwrap();
+
+        // Take a quarter out first: a quarter of the rewards goes with it
+        let staked = position.staked_amount;
+        let first = partial_rewards(full_rewards, staked / 4, staked);
+        assert_eq!(first, full_rewards / 4);
+        position.withdraw_principal(staked / 4).unwrap();
+        assert_eq!(position.staked_amount, staked - staked / 4);
+
+        // The rest pays what's left, give or take rounding
+        let (rest, _, _) = position_rewards(&position, maturity, no_cap).unwrap();
+        assert!((first + rest).abs_diff(full_rewards) <= 2);
+
+        // Withdrawing everything is the whole payout
+        assert_eq!(partial_rewards(rest, position.staked_amount, position.staked_amount), rest);
+    }
+
+    #[test]
+    fn test_partial_unstake_cannot_exceed_stake() {
+        let mut position = ninety_day_position(1_700_000_000);
+        assert_eq!(
+            position.withdraw_principal(PRINCIPAL + 1).err(),
+            Some(error!(ErrorCode::UnstakeAmountExceedsStake))
+        );
+        assert_eq!(position.staked_amount, PRINCIPAL);
+    }
+
+    #[test]
+    fn test_split_mint_pays_rewards_from_the_reward_vault() {
+        let rewards = 50_000_000_000;
+        // Single-mint: the stake vault pays everything
+        assert_eq!(payout_sources(PRINCIPAL, rewards, false).unwrap(), (PRINCIPAL + rewards, 0));
+        // Split-mint: principal from the stake vault, rewards from the reward vault
+        assert_eq!(payout_sources(PRINCIPAL, rewards, true).unwrap(), (PRINCIPAL, rewards));
+        assert!(payout_sources(u64::MAX, 1, false).is_err());
+    }
+
+    #[test]
+    fn test_reward_route_matches_config_and_recipient() {
+        let reward_config = RewardConfig {
+            version: 1,
+            reward_mint: Pubkey::new_unique(),
+            reward_vault: Pubkey::new_unique(),
+            bump: 255,
+        };
+        let recipient = Pubkey::new_unique();
+        let account = Some((reward_config.reward_mint, recipient));
+        let vault = Some(reward_config.reward_vault);
+
+        assert!(is_reward_route(&reward_config, vault, account, recipient));
+        // Missing accounts
+        assert!(!is_reward_route(&reward_config, None, account, recipient));
+        assert!(!is_reward_route(&reward_config, vault, None, recipient));
+        // Wrong vault, wrong mint, or someone else's reward account
+        assert!(!is_reward_route(&reward_config, Some(Pubkey::new_unique()), account, recipient));
+        assert!(!is_reward_route(&reward_config, vault, Some((Pubkey::new_unique(), recipient)), recipient));
+        assert!(!is_reward_route(&reward_config, vault, Some((reward_config.reward_mint, Pubkey::new_unique())), recipient));
+    }
+
+    fn open_books() -> (StakeCounter, GlobalStats, UserStakeStats, UserStats) {
+        let user = Pubkey::new_unique();
+        let counter = StakeCounter {
+            version: 1,
+            stake_count: 1,
+            bump: 255,
+            unbonding_count: 1,
+            active_stakes: 1,
+        };
+        let user_stake_stats = UserStakeStats {
+            version: 1,
+            user,
+            total_active_staked: PRINCIPAL,
+            bump: 255,
+        };
+        let user_stats = UserStats {
+            version: 1,
+            user,
+            total_ever_staked: PRINCIPAL,
+            total_rewards_earned: 0,
+            stakes_opened: 1,
+            stakes_closed: 0,
+            bump: 255,
+        };
+        (counter, test_global_stats(PRINCIPAL), user_stake_stats, user_stats)
+    }
+
+    #[test]
+    fn test_settled_unstake_cannot_pay_twice() {
+        let start = 1_700_000_000;
+        let mut position = StakeAccount {
+            reward_liability: 90_000_000_000,
+            unstake_requested_at: start + SECONDS_IN_THIRTY_DAYS,
+            ..test_stake_account(start, SECONDS_IN_THIRTY_DAYS)
+        };
+        let (mut counter, mut global_stats, mut user_stake_stats, mut user_stats) = open_books();
+        global_stats.total_reward_liability = position.reward_liability;
+        let mut books = PositionBooks {
+            counter: &mut counter,
+            global_stats: &mut global_stats,
+            user_stake_stats: &mut user_stake_stats,
+            user_stats: &mut user_stats,
+        };
+
+        let rewards = 82_191_780_821;
+        let released = settle_unstake(&mut position, &mut books, PRINCIPAL, rewards).unwrap();
+        assert_eq!(released, 90_000_000_000);
+        assert!(position.is_withdrawn);
+        assert_eq!(position.unstake_requested_at, 0);
+        assert_eq!(books.counter.unbonding_count, 0);
+        assert_eq!(books.global_stats.total_staked, 0);
+        assert_eq!(books.global_stats.total_rewards_paid, rewards);
+        assert_eq!(books.global_stats.total_reward_liability, 0);
+        assert_eq!(books.user_stake_stats.total_active_staked, 0);
+        assert_eq!(books.user_stats.stakes_closed, 1);
+
+        // A reentrant unstake (before the transfers and the close) finds the position
+        // already settled and pays nothing more
+        assert_eq!(
+            settle_unstake(&mut position, &mut books, PRINCIPAL, rewards).unwrap_err(),
+            error!(ErrorCode::AlreadyWithdrawn)
+        );
+        assert_eq!(books.global_stats.total_rewards_paid, rewards);
+        assert_eq!(books.user_stats.total_rewards_earned, rewards);
+    }
+
+    #[test]
+    fn test_partial_settle_keeps_the_rest_reserved() {
+        let start = 1_700_000_000;
+        let mut position = StakeAccount {
+            reward_liability: 80_000,
+            unstake_requested_at: start + SECONDS_IN_THIRTY_DAYS,
+            ..test_stake_account(start, SECONDS_IN_THIRTY_DAYS)
+        };
+        let (mut counter, mut global_stats, mut user_stake_stats, mut user_stats) = open_books();
+        global_stats.total_reward_liability = position.reward_liability;
+        let mut books = PositionBooks {
+            counter: &mut counter,
+            global_stats: &mut global_stats,
+            user_stake_stats: &mut user_stake_stats,
+            user_stats: &mut user_stats,
+        };
+
+        let released = settle_unstake(&mut position, &mut books, PRINCIPAL / 4, 1_000).unwrap();
+        assert_eq!(released, 20_000);
+        assert!(!position.is_withdrawn);
+        assert_eq!(position.staked_amount, PRINCIPAL - PRINCIPAL / 4);
+        assert_eq!(position.reward_liability, 60_000);
+        assert_eq!(books.global_stats.total_reward_liability, 60_000);
+        assert_eq!(books.user_stake_stats.total_active_staked, PRINCIPAL - PRINCIPAL / 4);
+        assert_eq!(books.user_stats.stakes_closed, 0);
+        // The request is spent; the rest needs a new one
+        assert_eq!(position.unstake_requested_at, 0);
+        assert_eq!(books.counter.unbonding_count, 0);
+    }
+
+    #[test]
+    fn test_rollover_books_only_what_leaves_the_vault() {
+        let start = 1_700_000_000;
+        let mut position = StakeAccount {
+            reward_liability: 90_000_000_000,
+            ..test_stake_account(start, SECONDS_IN_THIRTY_DAYS)
+        };
+        let (mut counter, mut global_stats, mut user_stake_stats, mut user_stats) = open_books();
+        global_stats.total_reward_liability = position.reward_liability;
+        let mut books = PositionBooks {
+            counter: &mut counter,
+            global_stats: &mut global_stats,
+            user_stake_stats: &mut user_stake_stats,
+            user_stats: &mut user_stats,
+        };
+
+        // A quarter of the principal and 1,000 of rewards are withdrawn, the rest restaked
+        let released = settle_rollover(&mut position, &mut books, PRINCIPAL / 4, 1_000).unwrap();
+        assert_eq!(released, 90_000_000_000);
+        assert!(position.is_withdrawn);
+        assert_eq!(books.global_stats.total_staked, 0);
+        assert_eq!(books.global_stats.total_unstaked_principal, PRINCIPAL / 4);
+        assert_eq!(books.global_stats.total_rewards_paid, 1_000);
+        assert_eq!(books.global_stats.total_reward_liability, 0);
+        assert_eq!(books.user_stake_stats.total_active_staked, 0);
+        assert_eq!(books.user_stats.total_rewards_earned, 1_000);
+        assert_eq!(books.user_stats.stakes_closed, 1);
+    }
+}
@@ -0,0 +1,416 @@
+use super::stake::{open_position, PositionTerms};
+use super::unstake::{
+    position_rewards, record_withdrawal, reward_duration, settle_rollover, within_unstake_cap,
+    PositionBooks, RewardWindow,
+};
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{RestakeEvent, UnstakeEvent};
+use crate::state::{
+    is_feature_enabled, FeatureFlags, GlobalStats, RewardConfig, StakeAccount, StakeCounter, TokenConfig,
+    UserStakeStats, UserStats,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+#[instruction(stake_count: u64)]
+pub struct UnstakeAndRestake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake-counter", user.key().as_ref()],
+        bump = counter.bump
+    )]
+    pub counter: Account<'info, StakeCounter>,
+
+    /// CHECK: PDA derived from seeds. Loaded and closed manually, same as `unstake`.
+    #[account(
+        mut,
+        seeds = [b"stake", user.key().as_ref(), &stake_count.to_le_bytes()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    // Position the remainder is rolled into
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake", user.key().as_ref(), &counter.stake_count.to_le_bytes()],
+        bump
+    )]
+    pub new_stake_account: Account<'info, StakeAccount>,
+
+    #[account(mut, token::mint = config.mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA derived from seeds, used to sign vault transfers
+    #[account(
+        seeds = [b"vault-authority"],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStakeStats::LEN,
+        seeds = [b"user-stake-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stake_stats: Account<'info, UserStakeStats>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStats::LEN,
+        seeds = [b"user-stats", user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Account<'info, UserStats>,
+
+    /// CHECK: Feature flags PDA, validated by seeds. Read manually so behavior falls back
+    /// to the defaults before an admin has created it.
+    #[account(
+        seeds = [b"feature-flags"],
+        bump
+    )]
+    pub feature_flags: UncheckedAccount<'info>,
+
+    /// CHECK: Reward config PDA, validated by seeds. Read manually; rewards can only
+    /// become principal while they are paid in the stake mint.
+    #[account(
+        seeds = [b"reward-config"],
+        bump
+    )]
+    pub reward_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Splits a matured payout into the part withdrawn and the part restaked.
+/// Rewards are split pro-rata so each one is counted exactly once.
+/// Returns (withdrawn_principal, withdrawn_rewards, restake_principal, restake_rewards).
+pub fn split_payout(principal: u64, rewards: u64, withdraw_amount: u64) -> Option<(u64, u64, u64, u64)> {
+    let total = principal.checked_add(rewards)?;
+    if withdraw_amount >= total {
+        return None;
+    }
+
+    let withdrawn_rewards =
+        ((rewards as u128).checked_mul(withdraw_amount as u128)? / total as u128) as u64;
+    let withdrawn_principal = withdraw_amount - withdrawn_rewards;
+    Some((
+        withdrawn_principal,
+        withdrawn_rewards,
+        principal - withdrawn_principal,
+        rewards - withdrawn_rewards,
+    ))
+}
+
+/// Compounding split: everything is restaked, or only the rewards with the
+/// principal paid out. Same tuple shape as `split_payout`.
+pub fn compound_split(principal: u64, rewards: u64, rewards_only: bool) -> (u64, u64, u64, u64) {
+    if rewards_only {
+        (principal, 0, 0, rewards)
+    } else {
+        (0, 0, principal, rewards)
+    }
+}
+
+pub fn handler(
+    ctx: Context<UnstakeAndRestake>,
+    stake_count: u64,
+    withdraw_amount: u64,
+    restake_duration: i64,
+) -> Result<()> {
+    roll_over(ctx, stake_count, restake_duration, |principal, rewards| {
+        split_payout(principal, rewards, withdraw_amount)
+    })
+}
+
+/// Unstake a matured position and compound its rewards (or the whole payout) into
+/// a fresh position, without the tokens leaving the vault
+pub fn compound_handler(
+    ctx: Context<UnstakeAndRestake>,
+    stake_count: u64,
+    rewards_only: bool,
+    restake_duration: i64,
+) -> Result<()> {
+    roll_over(ctx, stake_count, restake_duration, |principal, rewards| {
+        Some(compound_split(principal, rewards, rewards_only))
+    })
+}
+
+fn roll_over(
+    ctx: Context<UnstakeAndRestake>,
+    stake_count: u64,
+    restake_duration: i64,
+    split: impl FnOnce(u64, u64) -> Option<(u64, u64, u64, u64)>,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.config.paused,
+        ErrorCode::ProgramPaused
+    );
+
+    let mut stake_account = StakeAccount::load_open(
+        &ctx.accounts.stake_account,
+        stake_count,
+        ctx.accounts.counter.stake_count,
+    )?;
+    require_keys_eq!(
+        stake_account.user,
+        ctx.accounts.user.key(),
+        ErrorCode::Unauthorized
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    require!(
+        !ctx.accounts.global_stats.staking_paused,
+        ErrorCode::StakingPaused
+    );
+    // The boost vault isn't part of this flow, so the deposit would be stranded
+    require!(
+        stake_account.boost_amount == 0,
+        ErrorCode::BoostedStakeNotRestakable
+    );
+    require!(
+        !stake_account.is_frozen(current_time),
+        ErrorCode::StakeFrozen
+    );
+    require!(
+        is_stake_matured(stake_account.maturity_timestamp, current_time),
+        ErrorCode::StillLocked
+    );
+    // Part of the position is paid out, so the same two-phase rule as unstake applies
+    stake_account.check_unstake_delay(ctx.accounts.config.unstake_delay, current_time)?;
+    require!(
+        restake_duration >= MIN_LOCK_DURATION,
+        ErrorCode::DurationTooShort
+    );
+    require!(
+        restake_duration <= MAX_LOCK_DURATION,
+        ErrorCode::DurationTooLong
+    );
+
+    // Rewards become principal of the new position, so they must be DEVR
+    let config = &ctx.accounts.config;
+    require!(
+        config.reward_decimals == DEVR_DECIMALS
+            && RewardConfig::load_split(&ctx.accounts.reward_config, &config.mint)?.is_none(),
+        ErrorCode::RewardTokenMismatch
+    );
+
+    let staked_amount = stake_account.staked_amount;
+    let (rewards, apy_numerator, apy_denominator) =
+        position_rewards(&stake_account, current_time, RewardWindow::from_config(config))?;
+    let total_amount = staked_amount
+        .checked_add(rewards)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    require!(
+        ctx.accounts.vault.amount >= total_amount,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    let (withdrawn_principal, withdrawn_rewards, restake_principal, restake_rewards) =
+        split(staked_amount, rewards).ok_or(ErrorCode::InvalidWithdrawAmount)?;
+    let withdraw_amount = withdrawn_principal + withdrawn_rewards;
+    let restake_amount = restake_principal + restake_rewards;
+
+    // Only the withdrawn part leaves the vault, so only it is capped
+    require!(
+        within_unstake_cap(withdraw_amount, config.max_unstake_per_tx),
+        ErrorCode::UnstakeTooLarge
+    );
+    // Restaked rewards count toward the cap as much as withdrawn ones
+    ctx.accounts
+        .global_stats
+        .check_reward_cap(rewards, config.max_total_rewards)?;
+
+    require!(restake_amount >= MIN_STAKE_AMOUNT, ErrorCode::AmountTooSmall);
+    require!(restake_amount <= MAX_STAKE_AMOUNT, ErrorCode::AmountTooLarge);
+
+    let reward_duration = reward_duration(
+        &stake_account,
+        current_time,
+        RewardWindow::from_config(config),
+    );
+
+    // Settle the old position in full before any tokens move (see settle_unstake).
+    // Only the withdrawn part is booked as paid out; the rest stays in the vault.
+    let user = ctx.accounts.user.key();
+    ctx.accounts.user_stake_stats.init_if_new(user, ctx.bumps.user_stake_stats);
+    ctx.accounts.user_stats.init_if_new(user, ctx.bumps.user_stats);
+    let mut books = PositionBooks {
+        counter: &mut ctx.accounts.counter,
+        global_stats: &mut ctx.accounts.global_stats,
+        user_stake_stats: &mut ctx.accounts.user_stake_stats,
+        user_stats: &mut ctx.accounts.user_stats,
+    };
+    settle_rollover(&mut stake_account, &mut books, withdrawn_principal, withdrawn_rewards)?;
+    stake_account.try_serialize(
+        &mut &mut ctx.accounts.stake_account.try_borrow_mut_data()?[..],
+    )?;
+    books.counter.close_position();
+
+    // The restaked remainder never leaves the vault; it just becomes a new position.
+    // No bonus snapshots on a rollover.
+    let flags = FeatureFlags::load_flags(&ctx.accounts.feature_flags)?;
+    let new_stake_account = &mut ctx.accounts.new_stake_account;
+    open_position(
+        new_stake_account,
+        user,
+        ctx.bumps.new_stake_account,
+        &PositionTerms::plain(
+            restake_amount,
+            restake_duration,
+            ctx.accounts.config.apy_tiers,
+            is_feature_enabled(flags, FEATURE_INTERPOLATED_APY),
+            current_time,
+        ),
+        config,
+        &mut books,
+        // The withdrawn part is still in the vault until the transfer below
+        ctx.accounts.vault.amount - withdraw_amount,
+    )?;
+
+    // Only the withdrawn part leaves the vault, so only it counts toward the breaker
+    record_withdrawal(books.global_stats, config, flags, withdraw_amount, current_time)?;
+
+    if withdraw_amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let seeds = &[b"vault-authority".as_ref(), &[ctx.bumps.vault_authority]];
+        let signer = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
+            withdraw_amount,
+        )?;
+    }
+
+    StakeAccount::close(
+        ctx.accounts.stake_account.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+    )?;
+
+    emit!(UnstakeEvent {
+        user: stake_account.user,
+        stake_index: stake_count,
+        principal: withdrawn_principal,
+        rewards: withdrawn_rewards,
+        total_withdrawn: withdraw_amount,
+        lock_duration: stake_account.lock_duration,
+        reward_duration,
+        apy_numerator,
+        apy_denominator,
+        destination: ctx.accounts.user_token_account.key(),
+        boost_returned: 0,
+        remaining_principal: 0,
+        timestamp: current_time,
+    });
+
+    emit!(RestakeEvent {
+        user: stake_account.user,
+        old_stake_index: stake_count,
+        new_stake_index: new_stake_account.stake_index,
+        restaked_principal: restake_principal,
+        restaked_rewards: restake_rewards,
+        lock_duration: restake_duration,
+        apy_numerator: new_stake_account.apy_numerator,
+        apy_denominator: new_stake_account.apy_denominator,
+        timestamp: current_time,
+    });
+
+    msg!("✅ Unstaked and restaked!");
+    msg!("Withdrawn: {} DEVR", to_ui_amount(withdraw_amount, DEVR_DECIMALS));
+    msg!("Restaked: {} DEVR", to_ui_amount(restake_amount, DEVR_DECIMALS));
+    msg!("New stake index: #{}", new_stake_account.stake_index);
+    msg!("Duration: {} days", restake_duration / 86400);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRINCIPAL: u64 = 10_000_000_000_000; // 10,000 DEVR
+    const REWARDS: u64 = 82_191_780_821; // 30 days at 10%
+
+    #[test]
+    fn test_split_keeps_every_token_accounted_once() {
+        let withdraw = 4_000_000_000_000;
+        let (wp, wr, rp, rr) = split_payout(PRINCIPAL, REWARDS, withdraw).unwrap();
+
+        assert_eq!(wp + wr, withdraw);
+        assert_eq!(wp + rp, PRINCIPAL);
+        assert_eq!(wr + rr, REWARDS);
+    }
+
+    #[test]
+    fn test_split_is_pro_rata() {
+        // Withdrawing half of the payout takes (about) half of the rewards
+        let total = PRINCIPAL + REWARDS;
+        let (_, wr, _, rr) = split_payout(PRINCIPAL, REWARDS, total / 2).unwrap();
+        assert!(wr.abs_diff(rr) <= 1);
+    }
+
+    #[test]
+    fn test_full_rollover() {
+        assert_eq!(
+            split_payout(PRINCIPAL, REWARDS, 0),
+            Some((0, 0, PRINCIPAL, REWARDS))
+        );
+    }
+
+    #[test]
+    fn test_compound_split() {
+        // Whole payout: same as a full rollover
+        assert_eq!(
+            Some(compound_split(PRINCIPAL, REWARDS, false)),
+            split_payout(PRINCIPAL, REWARDS, 0)
+        );
+
+        // Rewards only: the principal is paid out untouched
+        let (wp, wr, rp, rr) = compound_split(PRINCIPAL, REWARDS, true);
+        assert_eq!((wp, wr), (PRINCIPAL, 0));
+        assert_eq!((rp, rr), (0, REWARDS));
+    }
+
+    #[test]
+    fn test_withdrawing_everything_is_rejected() {
+        assert_eq!(split_payout(PRINCIPAL, REWARDS, PRINCIPAL + REWARDS), None);
+    }
+}
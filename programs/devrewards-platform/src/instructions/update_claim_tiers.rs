@@ -0,0 +1,77 @@
+use crate::constants::*;
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ClaimTiersUpdatedEvent};
+use crate::state::{ClaimTier, TokenConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateClaimTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateClaimTiers>, claim_tiers: [ClaimTier; 3]) -> Result<()> {
+    for (i, tier) in claim_tiers.iter().enumerate() {
+        require!(
+            tier.multiplier_bps >= BASIS_POINTS && tier.multiplier_bps <= MAX_CLAIM_MULTIPLIER_BPS,
+            ErrorCode::InvalidClaimTiers
+        );
+
+        // Each tier must demand more stake and pay at least as much as the one below
+        if i > 0 {
+            let lower = &claim_tiers[i - 1];
+            require!(tier.min_staked > lower.min_staked, ErrorCode::InvalidClaimTiers);
+            require!(
+                tier.multiplier_bps >= lower.multiplier_bps,
+                ErrorCode::InvalidClaimTiers
+            );
+        }
+    }
+
+    let config = &mut ctx.accounts.config;
+    let old_tiers = config.claim_tiers;
+    config.claim_tiers = claim_tiers;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(ClaimTiersUpdatedEvent {
+        admin,
+        claim_tiers,
+        timestamp,
+    });
+    for (i, (old, new)) in old_tiers.iter().zip(claim_tiers.iter()).enumerate() {
+        emit_config_changed(
+            CONFIG_FIELD_CLAIM_TIER_MIN_STAKED[i],
+            old.min_staked,
+            new.min_staked,
+            admin,
+            timestamp,
+        );
+        emit_config_changed(
+            CONFIG_FIELD_CLAIM_TIER_MULTIPLIER_BPS[i],
+            old.multiplier_bps,
+            new.multiplier_bps,
+            admin,
+            timestamp,
+        );
+    }
+
+    msg!("✅ Claim tiers updated!");
+    for (i, tier) in claim_tiers.iter().enumerate() {
+        msg!(
+            "Tier {}: {} DEVR staked → {} bps",
+            i + 1,
+            to_ui_amount(tier.min_staked, DEVR_DECIMALS),
+            tier.multiplier_bps
+        );
+    }
+
+    Ok(())
+}
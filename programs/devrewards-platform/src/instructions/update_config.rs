@@ -0,0 +1,48 @@
+use crate::constants::{
+    to_ui_amount, CONFIG_FIELD_DAILY_CLAIM_AMOUNT, DEVR_DECIMALS, MAX_DAILY_CLAIM_AMOUNT,
+    MIN_DAILY_CLAIM_AMOUNT,
+};
+use crate::error::ErrorCode;
+use crate::events::{emit_config_changed, ConfigUpdatedEvent};
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the base faucet drip; claim tiers still multiply it
+pub fn handler(ctx: Context<UpdateConfig>, new_daily_claim_amount: u64) -> Result<()> {
+    require!(
+        (MIN_DAILY_CLAIM_AMOUNT..=MAX_DAILY_CLAIM_AMOUNT).contains(&new_daily_claim_amount),
+        ErrorCode::InvalidDailyClaimAmount
+    );
+
+    let config = &mut ctx.accounts.config;
+    let old_daily_claim_amount = config.daily_claim_amount;
+    config.daily_claim_amount = new_daily_claim_amount;
+
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+    emit!(ConfigUpdatedEvent {
+        admin,
+        old_daily_claim_amount,
+        new_daily_claim_amount,
+        timestamp,
+    });
+    emit_config_changed(CONFIG_FIELD_DAILY_CLAIM_AMOUNT, old_daily_claim_amount, new_daily_claim_amount, admin, timestamp);
+
+    msg!("✅ Config updated!");
+    msg!("Daily claim amount: {} DEVR", to_ui_amount(new_daily_claim_amount, DEVR_DECIMALS));
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use super::create_metadata::validate_metadata;
+use crate::error::ErrorCode;
+use crate::events::MetadataUpdatedEvent;
+use crate::state::TokenConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use mpl_token_metadata::instructions::{
+    UpdateMetadataAccountV2Cpi, UpdateMetadataAccountV2CpiAccounts,
+    UpdateMetadataAccountV2InstructionArgs,
+};
+use mpl_token_metadata::types::DataV2;
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    /// CHECK: Metaplex will validate this PDA
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    #[account(address = config.mint)]
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: PDA derived from seeds, the metadata's update authority
+    #[account(
+        seeds = [b"mint-authority"],
+        bump = config.mint_authority_bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    /// CHECK: This is the Metaplex Token Metadata Program
+    #[account(
+        address = mpl_token_metadata::ID
+    )]
+    pub token_metadata_program: UncheckedAccount<'info>,
+}
+
+/// Rewrites name, symbol and URI (e.g. after a rebrand or a new IPFS CID). The other
+/// fields keep the values create_metadata set, and the metadata stays mutable.
+pub fn handler(
+    ctx: Context<UpdateMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    validate_metadata(&name, &symbol, &uri)?;
+
+    msg!("Updating metadata for token: {}", ctx.accounts.mint.key());
+    msg!("Name: {}, Symbol: {}, URI: {}", name, symbol, uri);
+
+    let data_v2 = DataV2 {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    let update_metadata_args = UpdateMetadataAccountV2InstructionArgs {
+        data: Some(data_v2),
+        new_update_authority: None,
+        primary_sale_happened: None,
+        is_mutable: None,
+    };
+
+    let seeds = &[
+        b"mint-authority".as_ref(),
+        &[ctx.accounts.config.mint_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    UpdateMetadataAccountV2Cpi::new(
+        &ctx.accounts.token_metadata_program.to_account_info(),
+        UpdateMetadataAccountV2CpiAccounts {
+            metadata: &ctx.accounts.metadata.to_account_info(),
+            update_authority: &ctx.accounts.mint_authority.to_account_info(),
+        },
+        update_metadata_args,
+    )
+    .invoke_signed(signer)?;
+
+    emit!(MetadataUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        name,
+        symbol,
+        uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("✅ Metadata successfully updated!");
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::constants::*;
+use crate::events::StakeValidationEvent;
+use crate::instructions::unstake::{compute_rewards, RewardWindow};
+use crate::state::{GlobalStats, TokenConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct ValidateStake<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, TokenConfig>,
+
+    #[account(
+        seeds = [b"global-stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    #[account(
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Token account the stake would be funded from
+    #[account(token::mint = config.mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+}
+
+/// STAKE_CHECK_* bits for every `stake` precondition that would fail
+pub fn failed_stake_checks(paused: bool, amount: u64, lock_duration: i64, balance: u64) -> u32 {
+    let mut failed = 0;
+    if paused {
+        failed |= STAKE_CHECK_NOT_PAUSED;
+    }
+    if amount < MIN_STAKE_AMOUNT {
+        failed |= STAKE_CHECK_MIN_AMOUNT;
+    }
+    if amount > MAX_STAKE_AMOUNT {
+        failed |= STAKE_CHECK_MAX_AMOUNT;
+    }
+    if lock_duration < MIN_LOCK_DURATION {
+        failed |= STAKE_CHECK_MIN_DURATION;
+    }
+    if lock_duration > MAX_LOCK_DURATION {
+        failed |= STAKE_CHECK_MAX_DURATION;
+    }
+    if balance < amount {
+        failed |= STAKE_CHECK_BALANCE;
+    }
+    failed
+}
+
+/// Whether the vault holds enough beyond staked principal and already reserved rewards
+/// to reserve `projected_rewards` - the same test `stake` applies
+pub fn has_reward_liquidity(
+    vault_amount: u64,
+    total_staked: u64,
+    total_reward_liability: u64,
+    projected_rewards: u64,
+) -> bool {
+    vault_amount
+        .saturating_sub(total_staked)
+        .saturating_sub(total_reward_liability)
+        >= projected_rewards
+}
+
+pub fn handler(ctx: Context<ValidateStake>, amount: u64, lock_duration: i64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let mut failed_checks = failed_stake_checks(
+        ctx.accounts.global_stats.staking_paused,
+        amount,
+        lock_duration,
+        ctx.accounts.user_token_account.amount,
+    );
+    let is_valid = failed_checks == 0;
+
+    // Only project rewards for an in-range position; out-of-range inputs could never be staked
+    let projected_rewards = if is_valid {
+        let (apy_numerator, apy_denominator) = get_apy_for_duration(&default_apy_tiers(), lock_duration);
+        let reward_duration = RewardWindow::from_config(&ctx.accounts.config).apply(lock_duration);
+        compute_rewards(amount, apy_numerator, apy_denominator, reward_duration)?
+    } else {
+        0
+    };
+    if !has_reward_liquidity(
+        ctx.accounts.vault.amount,
+        ctx.accounts.global_stats.total_staked,
+        ctx.accounts.global_stats.total_reward_liability,
+        projected_rewards,
+    ) {
+        failed_checks |= STAKE_CHECK_REWARD_LIQUIDITY;
+    }
+
+    emit!(StakeValidationEvent {
+        amount,
+        lock_duration,
+        checks_run: ALL_STAKE_CHECKS,
+        failed_checks,
+        is_valid,
+        projected_rewards,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔍 Stake validation");
+    msg!("Valid: {}", is_valid);
+    msg!("Failed checks: {:#09b}", failed_checks);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BALANCE: u64 = 50_000_000_000; // 50 DEVR
+
+    #[test]
+    fn test_valid_stake_passes_every_check() {
+        assert_eq!(failed_stake_checks(false, 10_000_000_000, MIN_LOCK_DURATION, BALANCE), 0);
+    }
+
+    #[test]
+    fn test_invalid_combinations_report_each_failure() {
+        // Too small and too short
+        assert_eq!(
+            failed_stake_checks(false, 1, MIN_LOCK_DURATION - 1, BALANCE),
+            STAKE_CHECK_MIN_AMOUNT | STAKE_CHECK_MIN_DURATION
+        );
+
+        // Too large, too long and more than the balance
+        assert_eq!(
+            failed_stake_checks(false, MAX_STAKE_AMOUNT + 1, MAX_LOCK_DURATION + 1, BALANCE),
+            STAKE_CHECK_MAX_AMOUNT | STAKE_CHECK_MAX_DURATION | STAKE_CHECK_BALANCE
+        );
+
+        // Otherwise valid, but staking is paused
+        assert_eq!(
+            failed_stake_checks(true, 10_000_000_000, MIN_LOCK_DURATION, BALANCE),
+            STAKE_CHECK_NOT_PAUSED
+        );
+    }
+
+    #[test]
+    fn test_reward_liquidity() {
+        assert!(has_reward_liquidity(1_100, 1_000, 0, 100));
+        assert!(!has_reward_liquidity(1_099, 1_000, 0, 100));
+        // Rewards already reserved for open positions aren't spare
+        assert!(has_reward_liquidity(1_150, 1_000, 50, 100));
+        assert!(!has_reward_liquidity(1_149, 1_000, 50, 100));
+        // Under-collateralized vault never has spare liquidity
+        assert!(!has_reward_liquidity(900, 1_000, 0, 1));
+    }
+}
@@ -1,10 +1,17 @@
+// Only for Anchor 0.31's `#[program]` expansion: its generated `__private::__idl`
+// handlers call the deprecated `AccountInfo::realloc` and land at the crate root,
+// outside any item an attribute could be scoped to. Drop on the next Anchor bump.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 use instructions::*;
+use state::{ApyTier, ClaimTier};
 
 declare_id!("8PZ8EXjLqDxeRHUEL7o53eVceh5MgwPT6aJWZUu5AjTq");
 
 mod constants;
 mod error;
+mod governance;
 mod instructions;
 mod state;
 mod events;
@@ -17,12 +24,12 @@ pub mod devrewards_platform {
         instructions::initialize::handler(ctx)
     }
 
-    pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
-        instructions::claim_tokens::handler(ctx)
+    pub fn claim_tokens(ctx: Context<ClaimTokens>, expected_nonce: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::claim_tokens::handler(ctx, expected_nonce, proof)
     }
 
-    pub fn transfer(ctx: Context<TransferTokens>, amount: u64) -> Result<()> {
-        instructions::transfer_tokens::handler(ctx, amount)
+    pub fn transfer(ctx: Context<TransferTokens>, amount: u64, keep_reserve: u64) -> Result<()> {
+        instructions::transfer_tokens::handler(ctx, amount, keep_reserve)
     }
 
     pub fn approve_delegate(ctx: Context<ApproveDelegate>, amount: u64) -> Result<()> {
@@ -38,11 +45,20 @@ pub mod devrewards_platform {
     }
 
     pub fn stake(ctx: Context<Stake>, amount: u64, lock_duration: i64) -> Result<()> {
-        instructions::stake::handler(ctx, amount, lock_duration)
+        instructions::stake::handler(ctx, amount, lock_duration, 0)
+    }
+
+    pub fn stake_until(
+        ctx: Context<Stake>,
+        amount: u64,
+        end_timestamp: i64,
+        min_tier: u8,
+    ) -> Result<()> {
+        instructions::stake::stake_until_handler(ctx, amount, end_timestamp, min_tier)
     }
 
     pub fn unstake(ctx: Context<Unstake>, stake_count: u64) -> Result<()> {
-        instructions::unstake::handler(ctx, stake_count)
+        instructions::unstake::handler(ctx, stake_count, None)
     }
 
     pub fn create_metadata(
@@ -53,4 +69,399 @@ pub mod devrewards_platform {
     ) -> Result<()> {
         instructions::create_metadata::handler(ctx, name, symbol, uri)
     }
+
+    pub fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
+        instructions::close_token_account::handler(ctx)
+    }
+
+    pub fn emit_parameter_preview(ctx: Context<EmitParameterPreview>) -> Result<()> {
+        instructions::emit_parameter_preview::handler(ctx)
+    }
+
+    pub fn freeze_stake(ctx: Context<FreezeStake>, user: Pubkey, stake_count: u64) -> Result<()> {
+        instructions::freeze_stake::freeze_handler(ctx, user, stake_count)
+    }
+
+    pub fn unfreeze_stake(
+        ctx: Context<FreezeStake>,
+        user: Pubkey,
+        stake_count: u64,
+    ) -> Result<()> {
+        instructions::freeze_stake::unfreeze_handler(ctx, user, stake_count)
+    }
+
+    pub fn update_claim_tiers(
+        ctx: Context<UpdateClaimTiers>,
+        claim_tiers: [ClaimTier; 3],
+    ) -> Result<()> {
+        instructions::update_claim_tiers::handler(ctx, claim_tiers)
+    }
+
+    pub fn time_to_maturity(ctx: Context<TimeToMaturity>, stake_count: u64) -> Result<()> {
+        instructions::time_to_maturity::handler(ctx, stake_count)
+    }
+
+    pub fn set_delegation_cap(
+        ctx: Context<SetDelegationCap>,
+        max_total_delegation_bps: u64,
+    ) -> Result<()> {
+        instructions::set_delegation_cap::handler(ctx, max_total_delegation_bps)
+    }
+
+    pub fn set_circuit_breaker(
+        ctx: Context<SetCircuitBreaker>,
+        window: i64,
+        threshold: u64,
+    ) -> Result<()> {
+        instructions::circuit_breaker::set_handler(ctx, window, threshold)
+    }
+
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>) -> Result<()> {
+        instructions::circuit_breaker::reset_handler(ctx)
+    }
+
+    pub fn batch_add_to_allowlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchAddToAllowlist<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::batch_add_to_allowlist::handler(ctx, users)
+    }
+
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+        amounts: Vec<u64>,
+        reject_duplicates: bool,
+    ) -> Result<()> {
+        instructions::batch_transfer::handler(ctx, amounts, reject_duplicates)
+    }
+
+    pub fn set_feature_flags(ctx: Context<SetFeatureFlags>, flags: u64) -> Result<()> {
+        instructions::set_feature_flags::handler(ctx, flags)
+    }
+
+    pub fn fund_reward_vault(ctx: Context<FundRewardVault>, amount: u64) -> Result<()> {
+        instructions::lp_rewards::fund_handler(ctx, amount)
+    }
+
+    pub fn deposit_lp_fees(ctx: Context<DepositLpFees>, amount: u64) -> Result<()> {
+        instructions::lp_rewards::deposit_fees_handler(ctx, amount)
+    }
+
+    pub fn claim_lp_rewards(ctx: Context<ClaimLpRewards>) -> Result<()> {
+        instructions::lp_rewards::claim_handler(ctx)
+    }
+
+    pub fn init_apy_history(ctx: Context<InitApyHistory>) -> Result<()> {
+        instructions::init_apy_history::handler(ctx)
+    }
+
+    pub fn unstake_and_restake(
+        ctx: Context<UnstakeAndRestake>,
+        stake_count: u64,
+        withdraw_amount: u64,
+        restake_duration: i64,
+    ) -> Result<()> {
+        instructions::unstake_and_restake::handler(ctx, stake_count, withdraw_amount, restake_duration)
+    }
+
+    pub fn compound_unstake(
+        ctx: Context<UnstakeAndRestake>,
+        stake_count: u64,
+        rewards_only: bool,
+        restake_duration: i64,
+    ) -> Result<()> {
+        instructions::unstake_and_restake::compound_handler(ctx, stake_count, rewards_only, restake_duration)
+    }
+
+    pub fn reclaim_orphan_stake(
+        ctx: Context<ReclaimOrphanStake>,
+        user: Pubkey,
+        stake_count: u64,
+    ) -> Result<()> {
+        instructions::reclaim_orphan_stake::handler(ctx, user, stake_count)
+    }
+
+    pub fn set_epoch_config(
+        ctx: Context<SetEpochConfig>,
+        epoch_start: i64,
+        epoch_length: i64,
+    ) -> Result<()> {
+        instructions::set_epoch_config::handler(ctx, epoch_start, epoch_length)
+    }
+
+    pub fn compound_rewards_to_new_stake(
+        ctx: Context<CompoundRewardsToNewStake>,
+        stake_count: u64,
+        lock_duration: i64,
+    ) -> Result<()> {
+        instructions::compound_rewards_to_new_stake::handler(ctx, stake_count, lock_duration)
+    }
+
+    pub fn set_unstake_cap(ctx: Context<SetUnstakeCap>, max_unstake_per_tx: u64) -> Result<()> {
+        instructions::set_unstake_cap::handler(ctx, max_unstake_per_tx)
+    }
+
+    pub fn set_auto_claim(ctx: Context<SetAutoClaim>, enabled: bool) -> Result<()> {
+        instructions::auto_claim::set_handler(ctx, enabled)
+    }
+
+    pub fn process_auto_claims(
+        ctx: Context<ProcessAutoClaims>,
+        user: Pubkey,
+        stake_count: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::auto_claim::process_handler(ctx, user, stake_count, proof)
+    }
+
+    pub fn validate_stake(ctx: Context<ValidateStake>, amount: u64, lock_duration: i64) -> Result<()> {
+        instructions::validate_stake::handler(ctx, amount, lock_duration)
+    }
+
+    pub fn set_reward_duration_cap(
+        ctx: Context<SetRewardDurationCap>,
+        max_reward_eligible_duration: i64,
+    ) -> Result<()> {
+        instructions::set_reward_duration_cap::handler(ctx, max_reward_eligible_duration)
+    }
+
+    pub fn set_vault_authority(ctx: Context<SetVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_vault_authority::handler(ctx, new_authority)
+    }
+
+    pub fn set_reward_accrual_delay(
+        ctx: Context<SetRewardAccrualDelay>,
+        reward_accrual_delay: i64,
+    ) -> Result<()> {
+        instructions::set_reward_accrual_delay::handler(ctx, reward_accrual_delay)
+    }
+
+    pub fn set_min_reward_reserve(
+        ctx: Context<SetMinRewardReserve>,
+        min_reward_reserve: u64,
+    ) -> Result<()> {
+        instructions::set_min_reward_reserve::handler(ctx, min_reward_reserve)
+    }
+
+    pub fn sweep_excess_rewards(ctx: Context<SweepExcessRewards>, amount: u64) -> Result<()> {
+        instructions::sweep_excess_rewards::handler(ctx, amount)
+    }
+
+    pub fn add_to_stake(ctx: Context<AddToStake>, stake_count: u64, amount: u64) -> Result<()> {
+        instructions::add_to_stake::handler(ctx, stake_count, amount)
+    }
+
+    pub fn block_address(ctx: Context<BlockAddress>, address: Pubkey) -> Result<()> {
+        instructions::blocklist::block_handler(ctx, address)
+    }
+
+    pub fn unblock_address(ctx: Context<UnblockAddress>, address: Pubkey) -> Result<()> {
+        instructions::blocklist::unblock_handler(ctx, address)
+    }
+
+    pub fn init_insurance_fund(ctx: Context<InitInsuranceFund>) -> Result<()> {
+        instructions::insurance_fund::init_handler(ctx)
+    }
+
+    pub fn set_insurance_fee(ctx: Context<SetInsuranceFee>, insurance_fee_bps: u64) -> Result<()> {
+        instructions::insurance_fund::set_fee_handler(ctx, insurance_fee_bps)
+    }
+
+    pub fn migrate_all_stakes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateAllStakes<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_all_stakes::handler(ctx)
+    }
+
+    pub fn migrate_global_accounts(ctx: Context<MigrateGlobalAccounts>) -> Result<()> {
+        instructions::migrate_global_accounts::handler(ctx)
+    }
+
+    pub fn apy_curve(ctx: Context<ApyCurve>, amount: u64) -> Result<()> {
+        instructions::apy_curve::handler(ctx, amount)
+    }
+
+    pub fn set_delegation_cooldown(
+        ctx: Context<SetDelegationCooldown>,
+        delegation_cooldown: i64,
+    ) -> Result<()> {
+        instructions::set_delegation_cooldown::handler(ctx, delegation_cooldown)
+    }
+
+    pub fn set_payout_destination(
+        ctx: Context<SetPayoutDestination>,
+        destination: Pubkey,
+    ) -> Result<()> {
+        instructions::set_payout_destination::handler(ctx, destination)
+    }
+
+    pub fn init_boost_vault(ctx: Context<InitBoostVault>) -> Result<()> {
+        instructions::boost_vault::handler(ctx)
+    }
+
+    pub fn stake_with_boost(
+        ctx: Context<Stake>,
+        amount: u64,
+        lock_duration: i64,
+        boost_amount: u64,
+    ) -> Result<()> {
+        instructions::stake::handler(ctx, amount, lock_duration, boost_amount)
+    }
+
+    pub fn set_reward_basis(ctx: Context<SetRewardBasis>, reward_basis: u8) -> Result<()> {
+        instructions::set_reward_basis::handler(ctx, reward_basis)
+    }
+
+    pub fn dump_config(ctx: Context<DumpConfig>) -> Result<()> {
+        instructions::dump_config::handler(ctx)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, new_daily_claim_amount: u64) -> Result<()> {
+        instructions::update_config::handler(ctx, new_daily_claim_amount)
+    }
+
+    pub fn partial_unstake(ctx: Context<Unstake>, stake_count: u64, amount: u64) -> Result<()> {
+        instructions::unstake::handler(ctx, stake_count, Some(amount))
+    }
+
+    pub fn set_early_unstake_penalty(
+        ctx: Context<SetEarlyUnstakePenalty>,
+        early_unstake_penalty_bps: u64,
+    ) -> Result<()> {
+        instructions::set_early_unstake_penalty::handler(ctx, early_unstake_penalty_bps)
+    }
+
+    pub fn emergency_unstake(ctx: Context<EmergencyUnstake>, stake_count: u64) -> Result<()> {
+        instructions::emergency_unstake::handler(ctx, stake_count)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        instructions::set_pause::handler(ctx, paused)
+    }
+
+    pub fn set_apy_tiers(ctx: Context<SetApyTiers>, tiers: [ApyTier; 3]) -> Result<()> {
+        instructions::set_apy_tiers::handler(ctx, tiers)
+    }
+
+    pub fn fund_vault(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+        instructions::fund_vault::handler(ctx, amount)
+    }
+
+    pub fn extend_stake(ctx: Context<ExtendStake>, stake_count: u64, additional_duration: i64) -> Result<()> {
+        instructions::extend_stake::handler(ctx, stake_count, additional_duration)
+    }
+
+    pub fn set_max_streak_bonus(ctx: Context<SetMaxStreakBonus>, max_streak_bonus_bps: u64) -> Result<()> {
+        instructions::set_max_streak_bonus::handler(ctx, max_streak_bonus_bps)
+    }
+
+    pub fn get_pending_rewards(ctx: Context<GetPendingRewards>, stake_count: u64) -> Result<u64> {
+        instructions::get_pending_rewards::handler(ctx, stake_count)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::admin_transfer::transfer_handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::admin_transfer::accept_handler(ctx)
+    }
+
+    pub fn set_max_total_rewards(ctx: Context<SetMaxTotalRewards>, max_total_rewards: u64) -> Result<()> {
+        instructions::set_max_total_rewards::handler(ctx, max_total_rewards)
+    }
+
+    pub fn set_claim_cooldown(ctx: Context<SetClaimCooldown>, claim_cooldown_seconds: i64) -> Result<()> {
+        instructions::set_claim_cooldown::handler(ctx, claim_cooldown_seconds)
+    }
+
+    pub fn set_max_lifetime_claim(ctx: Context<SetMaxLifetimeClaim>, max_lifetime_claim: u64) -> Result<()> {
+        instructions::set_max_lifetime_claim::handler(ctx, max_lifetime_claim)
+    }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::update_metadata::handler(ctx, name, symbol, uri)
+    }
+
+    pub fn claim_and_stake(
+        ctx: Context<ClaimAndStake>,
+        expected_nonce: u64,
+        lock_duration: i64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_and_stake::handler(ctx, expected_nonce, lock_duration, proof)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::referral::register_handler(ctx)
+    }
+
+    pub fn set_referral_bonus(ctx: Context<SetReferralBonus>, referral_bonus: u64) -> Result<()> {
+        instructions::referral::set_bonus_handler(ctx, referral_bonus)
+    }
+
+    pub fn transfer_devr(ctx: Context<TransferDevr>, amount: u64, keep_reserve: u64) -> Result<()> {
+        instructions::transfer_tokens::devr_handler(ctx, amount, keep_reserve)
+    }
+
+    pub fn slash_stake(
+        ctx: Context<SlashStake>,
+        user: Pubkey,
+        stake_count: u64,
+        slash_amount: u64,
+        reason_code: u8,
+    ) -> Result<()> {
+        instructions::slash_stake::handler(ctx, user, stake_count, slash_amount, reason_code)
+    }
+
+    pub fn get_voting_power<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetVotingPower<'info>>,
+    ) -> Result<u64> {
+        instructions::get_voting_power::handler(ctx)
+    }
+
+    pub fn close_user_claim(ctx: Context<CloseUserClaim>) -> Result<()> {
+        instructions::close_user_claim::handler(ctx)
+    }
+
+    pub fn emergency_vault_withdraw(ctx: Context<EmergencyVaultWithdraw>, amount: u64) -> Result<()> {
+        instructions::emergency_vault_withdraw::handler(ctx, amount)
+    }
+
+    pub fn set_reward_config(ctx: Context<SetRewardConfig>) -> Result<()> {
+        instructions::set_reward_config::handler(ctx)
+    }
+
+    pub fn set_transfer_limits(ctx: Context<SetTransferLimits>, min_transfer: u64, max_transfer: u64) -> Result<()> {
+        instructions::set_transfer_limits::handler(ctx, min_transfer, max_transfer)
+    }
+
+    pub fn set_allowlist_root(ctx: Context<SetAllowlistRoot>, allowlist_root: [u8; 32]) -> Result<()> {
+        instructions::set_allowlist_root::handler(ctx, allowlist_root)
+    }
+
+    pub fn set_max_delegation_amount(ctx: Context<SetMaxDelegationAmount>, max_delegation_amount: u64) -> Result<()> {
+        instructions::set_max_delegation_amount::handler(ctx, max_delegation_amount)
+    }
+
+    pub fn get_unlock_info(ctx: Context<GetUnlockInfo>, stake_count: u64) -> Result<UnlockInfo> {
+        instructions::get_unlock_info::handler(ctx, stake_count)
+    }
+
+    pub fn snapshot_stats(ctx: Context<SnapshotStats>) -> Result<()> {
+        instructions::snapshot_stats::handler(ctx)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstake>, stake_count: u64) -> Result<()> {
+        instructions::request_unstake::handler(ctx, stake_count)
+    }
+
+    pub fn set_unstake_delay(ctx: Context<SetUnstakeDelay>, unstake_delay: i64) -> Result<()> {
+        instructions::set_unstake_delay::handler(ctx, unstake_delay)
+    }
 }
@@ -1,8 +1,22 @@
+use crate::constants::{
+    default_apy_tiers, default_claim_tiers, get_apy_for_lock, get_maturity_timestamp,
+    APY_HISTORY_LEN, CIRCUIT_BREAKER_BUCKETS, CLAIM_STREAK_WINDOW, CURRENT_ACCOUNT_VERSION,
+    DEFAULT_CIRCUIT_BREAKER_THRESHOLD, DEFAULT_CIRCUIT_BREAKER_WINDOW, DEFAULT_CLAIM_COOLDOWN,
+    DEFAULT_DAILY_CLAIM_AMOUNT, DEFAULT_EARLY_UNSTAKE_PENALTY_BPS, DEFAULT_FEATURE_FLAGS,
+    DEFAULT_MAX_REWARD_ELIGIBLE_DURATION, DEFAULT_MAX_TOTAL_DELEGATION_BPS,
+    DEFAULT_REFERRAL_BONUS, DEVR_DECIMALS, LP_FEE_PRECISION, MAX_FREEZE_DURATION,
+    MAX_STAKES_PER_USER, MAX_TRANSFER_AMOUNT, MAX_UNBONDING_PER_USER, MIN_TRANSFER_AMOUNT,
+    NO_DELEGATION_AMOUNT_CAP, NO_LIFETIME_CLAIM_CAP, NO_REWARD_CAP, NO_UNSTAKE_CAP,
+    OPEN_ALLOWLIST_ROOT, REWARD_BASIS_COMMITTED, SECONDS_PER_DAY,
+};
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 
 #[account]
 #[derive(InitSpace)]
 pub struct TokenConfig {
+    pub version: u8, // Account layout version
     pub mint: Pubkey,
     pub mint_authority: Pubkey,
     pub admin: Pubkey,
@@ -14,61 +28,1978 @@ pub struct TokenConfig {
     pub mint_bump: u8,
     pub vault_bump: u8,
     pub vault_authority_bump: u8,
-    pub global_stats_bump: u8
+    pub global_stats_bump: u8,
+    pub claim_tiers: [ClaimTier; 3], // Faucet multipliers by active stake, lowest tier first
+    pub max_total_delegation_bps: u64, // Cap on delegated allowance as a share of balance
+    pub reward_decimals: u8, // Decimals of the token rewards are paid in
+    pub circuit_breaker_window: i64, // Rolling window (seconds) for unstake volume
+    pub circuit_breaker_threshold: u64, // Max unstaked per window before pausing (0 = off)
+    pub epoch_start: i64,  // Start of program epoch 0
+    pub epoch_length: i64, // Seconds per epoch (0 = no epoch bonus)
+    pub max_unstake_per_tx: u64, // Max principal + rewards per withdrawal (u64::MAX = off)
+    pub max_reward_eligible_duration: i64, // Cap on lock seconds that earn rewards
+    pub reward_accrual_delay: i64, // Warm-up after staking before rewards accrue (0 = none)
+    pub min_reward_reserve: u64, // Vault balance a treasury sweep must never go below
+    pub insurance_fee_bps: u64, // Share of unstake rewards paid into the insurance fund (0 = off)
+    pub delegation_cooldown: i64, // Min seconds from a revoke to the next approve (0 = off)
+    pub reward_basis: u8, // REWARD_BASIS_COMMITTED or REWARD_BASIS_ELAPSED
+    pub early_unstake_penalty_bps: u64, // Principal share forfeited by emergency_unstake
+    pub paused: bool, // Admin kill switch: stakes, claims and unstakes are rejected while set
+    pub max_streak_bonus_bps: u64, // Cap on the claim streak bonus (0 = no bonus)
+    pub pending_admin: Pubkey, // Proposed by transfer_admin, takes over on accept_admin (default = none)
+    pub max_total_rewards: u64, // Lifetime cap on GlobalStats.total_rewards_paid (u64::MAX = off)
+    pub claim_cooldown_seconds: i64, // Minimum time between faucet claims
+    pub max_lifetime_claim: u64, // Cap on UserClaim.total_claimed per wallet (0 = unlimited)
+    pub referral_bonus: u64, // Minted to the referrer on each referred claim (0 = off)
+    pub min_transfer: u64, // Smallest amount transfer, delegated_transfer and batch_transfer move
+    pub max_transfer: u64, // Largest amount those transfers move in one go
+    pub allowlist_root: [u8; 32], // Merkle root of wallets allowed to claim (all zero = open)
+    pub max_delegation_amount: u64, // Largest allowance approve_delegate grants (0 = no cap)
+    pub unstake_delay: i64, // Seconds from request_unstake to unstake (0 = one-step unstake)
+    pub apy_tiers: [ApyTier; 3], // Schedule new stakes are quoted at; changes are logged in ApyHistory
 }
 
 impl TokenConfig {
     pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Every setting at the value a fresh deployment starts with; keys and bumps are
+    /// left for `initialize` to fill in. A config written before a setting existed
+    /// grows into that setting's value here.
+    pub fn with_defaults() -> Self {
+        Self {
+            version: CURRENT_ACCOUNT_VERSION,
+            mint: Pubkey::default(),
+            mint_authority: Pubkey::default(),
+            admin: Pubkey::default(),
+            vault: Pubkey::default(),
+            vault_authority: Pubkey::default(),
+            daily_claim_amount: DEFAULT_DAILY_CLAIM_AMOUNT,
+            config_bump: 0,
+            mint_authority_bump: 0,
+            mint_bump: 0,
+            vault_bump: 0,
+            vault_authority_bump: 0,
+            global_stats_bump: 0,
+            claim_tiers: default_claim_tiers(),
+            max_total_delegation_bps: DEFAULT_MAX_TOTAL_DELEGATION_BPS,
+            reward_decimals: DEVR_DECIMALS, // Rewards paid in DEVR by default
+            circuit_breaker_window: DEFAULT_CIRCUIT_BREAKER_WINDOW,
+            circuit_breaker_threshold: DEFAULT_CIRCUIT_BREAKER_THRESHOLD,
+            epoch_start: 0,
+            epoch_length: 0, // Epoch bonus off until an admin configures it
+            max_unstake_per_tx: NO_UNSTAKE_CAP,
+            max_reward_eligible_duration: DEFAULT_MAX_REWARD_ELIGIBLE_DURATION,
+            reward_accrual_delay: 0,
+            min_reward_reserve: 0,
+            insurance_fee_bps: 0, // No fees into the insurance fund until an admin sets one
+            delegation_cooldown: 0, // Re-approval right after a revoke is allowed by default
+            reward_basis: REWARD_BASIS_COMMITTED,
+            early_unstake_penalty_bps: DEFAULT_EARLY_UNSTAKE_PENALTY_BPS,
+            paused: false,
+            max_streak_bonus_bps: 0, // Streaks are tracked, but earn no bonus until an admin sets a cap
+            pending_admin: Pubkey::default(),
+            max_total_rewards: NO_REWARD_CAP,
+            claim_cooldown_seconds: DEFAULT_CLAIM_COOLDOWN,
+            max_lifetime_claim: NO_LIFETIME_CLAIM_CAP,
+            referral_bonus: DEFAULT_REFERRAL_BONUS,
+            min_transfer: MIN_TRANSFER_AMOUNT,
+            max_transfer: MAX_TRANSFER_AMOUNT,
+            allowlist_root: OPEN_ALLOWLIST_ROOT, // Faucet open until an admin sets a root
+            max_delegation_amount: NO_DELEGATION_AMOUNT_CAP,
+            unstake_delay: 0, // One-step unstake until an admin sets a delay
+            apy_tiers: default_apy_tiers(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ClaimTier {
+    pub min_staked: u64,     // Minimum active stake to qualify
+    pub multiplier_bps: u64, // 10_000 = 1x daily_claim_amount
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug, InitSpace)]
+pub struct ApyTier {
+    pub min_duration: i64,    // Shortest lock that earns this rate (seconds)
+    pub apy_numerator: u64,
+    pub apy_denominator: u64,
 }
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct UserClaim {
+    pub version: u8, // Account layout version
     pub user: Pubkey,         // Kis user ne claim kiya
     pub last_claim_time: i64, // Unix timestamp
     pub total_claimed: u64,   // Kitna total claim kiya (analytics ke liye)
     pub bump: u8,             // PDA bump
+    pub auto_claim_to_stake: bool, // Keeper may mint accrued claims straight into a stake
+    pub claim_nonce: u64, // Bumped on every claim so a claim tx binds to one state
+    pub current_streak: u32, // Consecutive daily claims, including the latest
+    pub longest_streak: u32,
 }
 
 impl UserClaim {
     pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Checks the caller saw the current claim state, then moves to the next nonce
+    pub fn consume_nonce(&mut self, expected_nonce: u64) -> Result<()> {
+        require!(
+            self.claim_nonce == expected_nonce,
+            ErrorCode::NonceMismatch
+        );
+        self.claim_nonce = self
+            .claim_nonce
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Call before `last_claim_time` moves. A claim within a day or two of the last
+    /// one continues the streak; anything else starts a new one.
+    pub fn record_streak(&mut self, now: i64) {
+        let gap = now.saturating_sub(self.last_claim_time);
+        let continues = self.last_claim_time != 0
+            && (SECONDS_PER_DAY..=CLAIM_STREAK_WINDOW).contains(&gap);
+        self.current_streak = if continues {
+            self.current_streak.saturating_add(1)
+        } else {
+            1
+        };
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+    }
+
+    /// Rejects a claim that would take this wallet past `max_lifetime_claim`.
+    /// Landing exactly on the cap is allowed; 0 means unlimited.
+    pub fn check_lifetime_cap(&self, amount: u64, max_lifetime_claim: u64) -> Result<()> {
+        if max_lifetime_claim == NO_LIFETIME_CLAIM_CAP {
+            return Ok(());
+        }
+        let total = self
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(total <= max_lifetime_claim, ErrorCode::ClaimCapReached);
+        Ok(())
+    }
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct StakeAccount {
+    pub version: u8, // Account layout version
     pub user: Pubkey,
     pub staked_amount: u64,
     pub staked_at: i64,
     pub lock_duration: i64,
+    pub maturity_timestamp: i64, // staked_at + lock_duration, kept in sync on lock changes
     pub stake_index: u64,
     pub bump: u8,
+    pub frozen: bool,    // Held for admin review - blocks unstake
+    pub frozen_at: i64,  // When the review hold started (0 = never frozen)
+    pub unfrozen_at: i64, // When the hold was cleared (0 = not cleared by admin)
+    pub loyalty_bonus_bps: u64, // Extra APY snapshotted from the holder's liquid balance at stake time
+    pub epoch_bonus_bps: u64, // Extra APY snapshotted when the lock covers a full program epoch
+    pub last_compound_at: i64, // Last rewards-to-stake compound (0 = never)
+    pub compounded_rewards: u64, // Rewards already moved into new positions, deducted at unstake
+    pub weighted_staked_seconds: u128, // Sum of each add_to_stake tranche x lock left at deposit (0 = single deposit)
+    pub boost_amount: u64, // Boost tokens held in the boost vault, returned at unstake
+    pub boost_multiplier_bps: u64, // Extra share of the base APY snapshotted from the boost at stake time
+    pub apy_numerator: u64,   // Base APY locked in when the position opened
+    pub apy_denominator: u64,
+    pub reward_liability: u64, // Rewards reserved in GlobalStats.total_reward_liability for this position
+    pub is_withdrawn: bool, // Set before the payout transfers of a full unstake; blocks a second one
+    pub unstake_requested_at: i64, // When request_unstake started the unstake delay (0 = not requested)
 }
 
 impl StakeAccount {
     pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Reads an open position, telling apart a closed one (index already used)
+    /// from one that was never created
+    pub fn load_open(info: &AccountInfo, stake_count: u64, next_index: u64) -> Result<Self> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            if stake_count < next_index {
+                return err!(ErrorCode::StakeAlreadyClosed);
+            }
+            return err!(ErrorCode::StakeNotFound);
+        }
+
+        let data = info.try_borrow_data()?;
+        Self::try_deserialize(&mut &data[..])
+    }
+
+    /// Closes the position, sending its rent to `destination`
+    pub fn close<'info>(info: AccountInfo<'info>, destination: AccountInfo<'info>) -> Result<()> {
+        let rent = info.lamports();
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(rent)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        **info.try_borrow_mut_lamports()? = 0;
+
+        info.assign(&system_program::ID);
+        info.resize(0)?;
+        Ok(())
+    }
+
+    /// A just-created position is all zeroes. Once an owner is recorded the index
+    /// is taken, so opening another position there is a collision.
+    pub fn is_unused(&self) -> bool {
+        self.user == Pubkey::default()
+    }
+
+    /// Sets the lock and its canonical maturity together; extensions go through here.
+    /// The only writer of `lock_duration`, so it is where locks are kept monotonic.
+    pub fn set_lock_duration(&mut self, lock_duration: i64) -> Result<()> {
+        assert_lock_not_shortened(self.lock_duration, lock_duration)?;
+        self.maturity_timestamp = get_maturity_timestamp(self.staked_at, lock_duration)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.lock_duration = lock_duration;
+        Ok(())
+    }
+
+    /// Locks in the base APY `tiers` give the current lock, so later schedule
+    /// changes never reach an open position. `interpolated` follows
+    /// FEATURE_INTERPOLATED_APY at the time of the lock change.
+    pub fn lock_in_apy(&mut self, tiers: &[ApyTier; 3], interpolated: bool) {
+        (self.apy_numerator, self.apy_denominator) =
+            get_apy_for_lock(tiers, self.lock_duration, interpolated);
+    }
+
+    /// Flags the position as paid out. Called before any tokens move, so a
+    /// reentrant unstake of the same position fails on the flag.
+    pub fn mark_withdrawn(&mut self) -> Result<()> {
+        require!(!self.is_withdrawn, ErrorCode::AlreadyWithdrawn);
+        self.is_withdrawn = true;
+        Ok(())
+    }
+
+    /// Starts the unstake delay. One request at a time; the next unstake clears it.
+    pub fn request_unstake(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.unstake_requested_at == 0,
+            ErrorCode::UnstakeAlreadyRequested
+        );
+        self.unstake_requested_at = now;
+        Ok(())
+    }
+
+    /// Second phase of a two-phase unstake: the position must have been requested
+    /// at least `unstake_delay` seconds ago. A zero delay keeps unstake one-step.
+    pub fn check_unstake_delay(&self, unstake_delay: i64, now: i64) -> Result<()> {
+        if unstake_delay == 0 {
+            return Ok(());
+        }
+        require!(
+            self.unstake_requested_at != 0,
+            ErrorCode::UnstakeNotRequested
+        );
+        require!(
+            now >= self.unstake_requested_at.saturating_add(unstake_delay),
+            ErrorCode::UnstakeDelayActive
+        );
+        Ok(())
+    }
+
+    /// A freeze lapses on its own once MAX_FREEZE_DURATION has passed
+    pub fn is_frozen(&self, now: i64) -> bool {
+        self.frozen && now < self.frozen_at.saturating_add(MAX_FREEZE_DURATION)
+    }
+
+    /// Seconds of the committed lock spent under a review hold. The hold ends at
+    /// the admin unfreeze, or when it lapses, whichever came first.
+    pub fn frozen_seconds(&self, now: i64) -> i64 {
+        if self.frozen_at == 0 {
+            return 0;
+        }
+
+        let lapse_at = self.frozen_at.saturating_add(MAX_FREEZE_DURATION);
+        let hold_end = if self.unfrozen_at != 0 {
+            self.unfrozen_at.min(lapse_at)
+        } else {
+            now.min(lapse_at)
+        };
+
+        // Only the part of the hold that overlaps the lock period counts
+        let start = self.frozen_at.max(self.staked_at);
+        let end = hold_end.min(self.maturity_timestamp);
+        end.saturating_sub(start).max(0)
+    }
+
+    /// Lock duration that earns rewards: the frozen interval is excluded
+    pub fn reward_eligible_duration(&self, now: i64) -> i64 {
+        self.lock_duration
+            .saturating_sub(self.frozen_seconds(now))
+            .max(0)
+    }
+
+    /// Reward-eligible seconds accrued so far: elapsed lock time, capped at maturity,
+    /// minus any review hold. Equals `reward_eligible_duration` once matured.
+    pub fn accrued_eligible_duration(&self, now: i64) -> i64 {
+        now.min(self.maturity_timestamp)
+            .saturating_sub(self.staked_at)
+            .saturating_sub(self.frozen_seconds(now))
+            .max(0)
+    }
+
+    /// Principal that earns rewards over the full lock. Tranches added mid-lock
+    /// count pro rata to the lock left when they came in, so a late top-up isn't
+    /// paid the whole lock's worth of APY.
+    pub fn reward_principal(&self) -> u64 {
+        if self.weighted_staked_seconds == 0 || self.lock_duration <= 0 {
+            return self.staked_amount;
+        }
+
+        (self.weighted_staked_seconds / self.lock_duration as u128)
+            .min(self.staked_amount as u128) as u64
+    }
+
+    /// Pushes maturity out by `additional_duration`. Every tranche earns the extra
+    /// seconds, so blended tranches keep their weighting.
+    pub fn extend_lock(&mut self, additional_duration: i64) -> Result<()> {
+        let lock_duration = self
+            .lock_duration
+            .checked_add(additional_duration)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.set_lock_duration(lock_duration)?;
+
+        if self.weighted_staked_seconds != 0 {
+            self.weighted_staked_seconds = (self.staked_amount as u128)
+                .checked_mul(additional_duration as u128)
+                .and_then(|extra| self.weighted_staked_seconds.checked_add(extra))
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Adds a tranche to a still-locked position, weighting it by the lock left
+    pub fn add_tranche(&mut self, amount: u64, now: i64) -> Result<()> {
+        let remaining_lock = self.maturity_timestamp.saturating_sub(now);
+        require!(remaining_lock > 0, ErrorCode::StakeMatured);
+
+        // First top-up: the original deposit earns over the whole lock
+        let weighted = if self.weighted_staked_seconds == 0 {
+            (self.staked_amount as u128)
+                .checked_mul(self.lock_duration as u128)
+                .ok_or(ErrorCode::ArithmeticOverflow)?
+        } else {
+            self.weighted_staked_seconds
+        };
+
+        self.weighted_staked_seconds = (amount as u128)
+            .checked_mul(remaining_lock as u128)
+            .and_then(|tranche| weighted.checked_add(tranche))
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.staked_amount = self
+            .staked_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Takes `amount` of principal out of a position that stays open. The tranche
+    /// weighting and compounded rewards shrink in step, so the rest keeps its
+    /// pro-rata share of what the position earns and has already been paid.
+    pub fn withdraw_principal(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.staked_amount,
+            ErrorCode::UnstakeAmountExceedsStake
+        );
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let whole = self.staked_amount as u128;
+        let remaining = self.staked_amount - amount;
+        self.weighted_staked_seconds = self
+            .weighted_staked_seconds
+            .checked_mul(remaining as u128)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / whole;
+        // Floor the share taken out, so the rest is never paid more than it's owed
+        let compounded_taken = (self.compounded_rewards as u128 * amount as u128 / whole) as u64;
+        self.compounded_rewards -= compounded_taken;
+        self.staked_amount = remaining;
+        Ok(())
+    }
 }
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct StakeCounter {
+    pub version: u8, // Account layout version
     pub stake_count: u64, // 8 bytes - Total stakes created
     pub bump: u8,         // 1 byte - PDA bump
+    pub unbonding_count: u64, // Positions currently in the two-phase unstake queue
+    pub active_stakes: u64, // Positions currently open (stake_count only ever grows)
 }
 
 impl StakeCounter {
-    pub const LEN: usize = 8 + Self::INIT_SPACE; // discriminator + stake_count + bump
+    pub const LEN: usize = 8 + Self::INIT_SPACE; // discriminator + stake_count + bump + unbonding_count + active_stakes
+
+    /// Takes the next stake index for a new position, up to MAX_STAKES_PER_USER open at once
+    pub fn open_position(&mut self) -> Result<()> {
+        require!(
+            self.active_stakes < MAX_STAKES_PER_USER,
+            ErrorCode::TooManyStakes
+        );
+        self.stake_count = self
+            .stake_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.active_stakes += 1;
+        Ok(())
+    }
+
+    /// Frees the slot once a position is closed. Saturates for counters that
+    /// predate active_stakes and start at 0.
+    pub fn close_position(&mut self) {
+        self.active_stakes = self.active_stakes.saturating_sub(1);
+    }
+
+    /// Takes an unbonding slot when a position requests unstake
+    pub fn begin_unbonding(&mut self) -> Result<()> {
+        require!(
+            self.unbonding_count < MAX_UNBONDING_PER_USER,
+            ErrorCode::TooManyUnbonding
+        );
+        self.unbonding_count += 1;
+        Ok(())
+    }
+
+    /// Frees the slot once the unstake completes (or the request is cancelled)
+    pub fn finish_unbonding(&mut self) {
+        self.unbonding_count = self.unbonding_count.saturating_sub(1);
+    }
 }
 
 #[account]
-#[derive(InitSpace)]
+#[derive(InitSpace, Default)]
 pub struct GlobalStats {
+    pub version: u8, // Account layout version
     pub total_staked: u64,       // Total DEVR staked across all users
     pub total_stakes: u64,       // Total stake positions
     pub total_rewards_paid: u64, // Total rewards distributed
     pub bump: u8,
+    pub total_unstaked_principal: u64, // Lifetime principal returned to users
+    pub staking_paused: bool, // Set by the circuit breaker, cleared by admin
+    pub unstake_buckets: [u64; CIRCUIT_BREAKER_BUCKETS], // Rolling unstake volume per bucket
+    pub unstake_bucket_epoch: i64, // Bucket number (now / bucket_len) of the latest unstake
+    pub peak_total_staked: u64, // All-time high of total_staked; never lowered by unstakes
+    pub insurance_fund_balance: u64, // Fees routed into the insurance fund, less what it has paid out
+    pub insurance_fund_used: u64, // Lifetime reward-vault shortfalls the fund has covered
+    pub total_penalties_collected: u64, // Lifetime penalties kept by the protocol (not fees)
+    pub total_rewards_funded: u64, // Lifetime DEVR deposited into the vault to back rewards
+    pub total_reward_liability: u64, // Most the open positions can still earn, reserved at stake time
+    pub total_minted: u64, // Lifetime DEVR minted by the faucet (claims and auto-claims)
 }
 
 impl GlobalStats {
     pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Raises the all-time-high watermark if total_staked has passed it
+    pub fn update_peak(&mut self) {
+        if self.total_staked > self.peak_total_staked {
+            self.peak_total_staked = self.total_staked;
+        }
+    }
+
+    /// Records an unstake fee paid into the insurance fund
+    pub fn record_insurance_fee(&mut self, fee: u64) -> Result<()> {
+        self.insurance_fund_balance = self
+            .insurance_fund_balance
+            .checked_add(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records a shortfall covered by the insurance fund
+    pub fn record_insurance_draw(&mut self, amount: u64) -> Result<()> {
+        // Saturating: the fund can also be topped up directly, outside the fee path
+        self.insurance_fund_balance = self.insurance_fund_balance.saturating_sub(amount);
+        self.insurance_fund_used = self
+            .insurance_fund_used
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records a deposit into the vault that backs rewards rather than a stake
+    pub fn record_rewards_funded(&mut self, amount: u64) -> Result<()> {
+        self.total_rewards_funded = self
+            .total_rewards_funded
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records fresh supply minted by the faucet
+    pub fn record_minted(&mut self, amount: u64) -> Result<()> {
+        self.total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// The vault must always hold every staked principal plus every reservation.
+    /// Faucet claims mint straight to the claimer and never touch the vault or these
+    /// totals, so a claim can't break this and reserve accounting can't block a claim;
+    /// operators reconcile that supply through `total_minted` instead.
+    pub fn check_vault_backing(&self, vault_balance: u64) -> Result<()> {
+        require!(
+            vault_balance >= self.total_staked,
+            ErrorCode::InsufficientVaultBalance
+        );
+        require!(
+            vault_balance - self.total_staked >= self.total_reward_liability,
+            ErrorCode::InsufficientRewardReserve
+        );
+        Ok(())
+    }
+
+    /// Reserves the most a new position can earn. The vault must hold it on top of
+    /// every staked principal and earlier reservation.
+    pub fn reserve_rewards(&mut self, rewards: u64, vault_balance: u64) -> Result<()> {
+        let liability = self
+            .total_reward_liability
+            .checked_add(rewards)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        let obligations = self
+            .total_staked
+            .checked_add(liability)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            vault_balance >= obligations,
+            ErrorCode::InsufficientRewardReserve
+        );
+        self.total_reward_liability = liability;
+        Ok(())
+    }
+
+    /// Releases a reservation once its position is paid out or forfeited
+    pub fn release_rewards(&mut self, rewards: u64) -> Result<()> {
+        self.total_reward_liability = self
+            .total_reward_liability
+            .checked_sub(rewards)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records a penalty retained in the vault or treasury. Every penalty-producing
+    /// instruction reports here, so this is the protocol's penalty take.
+    pub fn record_penalty(&mut self, penalty: u64) -> Result<()> {
+        self.total_penalties_collected = self
+            .total_penalties_collected
+            .checked_add(penalty)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Rejects a payout that would take lifetime rewards past `max_total_rewards`.
+    /// A zero-reward (principal-only) withdrawal always fits.
+    pub fn check_reward_cap(&self, rewards: u64, max_total_rewards: u64) -> Result<()> {
+        let total = self
+            .total_rewards_paid
+            .checked_add(rewards)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        require!(
+            rewards == 0 || total <= max_total_rewards,
+            ErrorCode::RewardCapReached
+        );
+        Ok(())
+    }
+
+    /// Moves a closed position's principal out of the staked total
+    pub fn record_unstake(&mut self, principal: u64, rewards: u64) -> Result<()> {
+        self.total_staked = self
+            .total_staked
+            .checked_sub(principal)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_rewards_paid = self
+            .total_rewards_paid
+            .checked_add(rewards)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_unstaked_principal = self
+            .total_unstaked_principal
+            .checked_add(principal)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Takes a rolled-over position's principal out of the staked total. It stays in
+    /// the vault, so unlike `record_unstake` it isn't counted as returned.
+    pub fn record_rollover(&mut self, principal: u64) -> Result<()> {
+        self.total_staked = self
+            .total_staked
+            .checked_sub(principal)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Adds an unstake to the rolling window and trips the circuit breaker when
+    /// the window's volume exceeds `threshold`. Returns true if this call tripped it.
+    pub fn record_unstake_volume(
+        &mut self,
+        amount: u64,
+        now: i64,
+        window: i64,
+        threshold: u64,
+    ) -> Result<bool> {
+        if threshold == 0 || window <= 0 {
+            return Ok(false);
+        }
+
+        let bucket_count = CIRCUIT_BREAKER_BUCKETS as i64;
+        let bucket_len = (window / bucket_count).max(1);
+        let current = now / bucket_len;
+
+        // Expire buckets that slid out of the window since the last unstake
+        let elapsed = current.saturating_sub(self.unstake_bucket_epoch).max(0);
+        if elapsed >= bucket_count {
+            self.unstake_buckets = [0; CIRCUIT_BREAKER_BUCKETS];
+        } else {
+            for step in 1..=elapsed {
+                let idx = (self.unstake_bucket_epoch + step).rem_euclid(bucket_count) as usize;
+                self.unstake_buckets[idx] = 0;
+            }
+        }
+        self.unstake_bucket_epoch = self.unstake_bucket_epoch.max(current);
+
+        let idx = current.rem_euclid(bucket_count) as usize;
+        self.unstake_buckets[idx] = self.unstake_buckets[idx]
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if self.staking_paused || self.window_unstake_volume()? <= threshold {
+            return Ok(false);
+        }
+
+        self.staking_paused = true;
+        Ok(true)
+    }
+
+    /// Total unstaked across the buckets currently inside the window
+    pub fn window_unstake_volume(&self) -> Result<u64> {
+        self.unstake_buckets
+            .iter()
+            .try_fold(0u64, |acc, v| acc.checked_add(*v))
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+    }
+}
+
+#[account]
+#[derive(InitSpace, Default)]
+pub struct UserStakeStats {
+    pub version: u8, // Account layout version
+    pub user: Pubkey,
+    pub total_active_staked: u64, // Principal currently locked across all positions
+    pub bump: u8,
+}
+
+impl UserStakeStats {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Fills in the header the first time an `init_if_needed` account is used
+    pub fn init_if_new(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.version = CURRENT_ACCOUNT_VERSION;
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+}
+
+/// Lifetime per-user rollups for dashboards and leaderboards, so a client can
+/// rank a wallet with one account read instead of scanning its positions
+#[account]
+#[derive(InitSpace)]
+pub struct UserStats {
+    pub version: u8, // Account layout version
+    pub user: Pubkey,
+    pub total_ever_staked: u64,    // Principal ever staked; unstakes never reduce it
+    pub total_rewards_earned: u64, // Rewards paid out across all unstakes
+    pub stakes_opened: u64,
+    pub stakes_closed: u64, // Fully unstaked positions; partial unstakes leave it alone
+    pub bump: u8,
+}
+
+impl UserStats {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Fills in the header the first time an `init_if_needed` account is used
+    pub fn init_if_new(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.version = CURRENT_ACCOUNT_VERSION;
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    pub fn record_stake(&mut self, amount: u64) -> Result<()> {
+        self.total_ever_staked = self
+            .total_ever_staked
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.stakes_opened = self
+            .stakes_opened
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Adds a top-up of an open position; unlike `record_stake` no position is opened
+    pub fn record_deposit(&mut self, amount: u64) -> Result<()> {
+        self.total_ever_staked = self
+            .total_ever_staked
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Adds an unstake's rewards; the position only counts as closed once
+    /// its last principal is out
+    pub fn record_unstake(&mut self, rewards: u64, closes_position: bool) -> Result<()> {
+        self.total_rewards_earned = self
+            .total_rewards_earned
+            .checked_add(rewards)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        if closes_position {
+            self.stakes_closed = self
+                .stakes_closed
+                .checked_add(1)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub version: u8, // Account layout version
+    pub referrer: Pubkey,
+    pub total_referred: u64,   // Claims made naming this referrer
+    pub referral_rewards: u64, // Lifetime bonus minted to the referrer
+    pub bump: u8,
+}
+
+impl Referral {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Credits one referred claim and the bonus it paid
+    pub fn record_referral(&mut self, bonus: u64) -> Result<()> {
+        self.total_referred = self
+            .total_referred
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.referral_rewards = self
+            .referral_rewards
+            .checked_add(bonus)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeatureFlags {
+    pub version: u8, // Account layout version
+    pub flags: u64,  // Bitfield of FEATURE_* constants
+    pub bump: u8,
+}
+
+impl FeatureFlags {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Reads the flags, falling back to DEFAULT_FEATURE_FLAGS before the PDA exists
+    pub fn load_flags(info: &AccountInfo) -> Result<u64> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(DEFAULT_FEATURE_FLAGS);
+        }
+
+        let data = info.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.flags)
+    }
+}
+
+/// Invariant for every lock change: a position's lock may grow, never shrink
+pub fn assert_lock_not_shortened(old_lock_duration: i64, new_lock_duration: i64) -> Result<()> {
+    require!(
+        new_lock_duration >= old_lock_duration,
+        ErrorCode::LockCannotShorten
+    );
+    Ok(())
+}
+
+pub fn is_feature_enabled(flags: u64, feature: u64) -> bool {
+    flags & feature == feature
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LpPool {
+    pub version: u8, // Account layout version
+    pub total_contributed: u64,     // DEVR funded into the reward vault by LPs
+    pub acc_fees_per_share: u128,   // Fees per contributed token, scaled by LP_FEE_PRECISION
+    pub total_fees_deposited: u64,  // Lifetime fees routed to LPs
+    pub bump: u8,
+    pub treasury_bump: u8,
+}
+
+impl LpPool {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Spreads `amount` of fees across every contributed token
+    pub fn distribute(&mut self, amount: u64) -> Result<()> {
+        require!(self.total_contributed > 0, ErrorCode::NoLiquidityProviders);
+
+        let per_share = (amount as u128)
+            .checked_mul(LP_FEE_PRECISION)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / self.total_contributed as u128;
+        self.acc_fees_per_share = self
+            .acc_fees_per_share
+            .checked_add(per_share)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.total_fees_deposited = self
+            .total_fees_deposited
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityProvider {
+    pub version: u8, // Account layout version
+    pub provider: Pubkey,
+    pub contributed: u64,    // DEVR this provider funded into the reward vault
+    pub fee_debt: u128,      // contributed * acc_fees_per_share at last settlement
+    pub unclaimed_fees: u64, // Settled but not yet withdrawn
+    pub total_claimed: u64,
+    pub bump: u8,
+}
+
+impl LiquidityProvider {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    fn accrued(&self, acc_fees_per_share: u128) -> Result<u128> {
+        (self.contributed as u128)
+            .checked_mul(acc_fees_per_share)
+            .ok_or_else(|| error!(ErrorCode::ArithmeticOverflow))
+    }
+
+    /// Moves fees earned since the last settlement into `unclaimed_fees`
+    pub fn settle(&mut self, acc_fees_per_share: u128) -> Result<()> {
+        let pending = self
+            .accrued(acc_fees_per_share)?
+            .checked_sub(self.fee_debt)
+            .ok_or(ErrorCode::ArithmeticOverflow)?
+            / LP_FEE_PRECISION;
+        self.unclaimed_fees = self
+            .unclaimed_fees
+            .checked_add(pending as u64)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.fee_debt = self.accrued(acc_fees_per_share)?;
+        Ok(())
+    }
+
+    /// Settles, then adds a new contribution so it only earns future fees
+    pub fn add_contribution(&mut self, amount: u64, acc_fees_per_share: u128) -> Result<()> {
+        self.settle(acc_fees_per_share)?;
+        self.contributed = self
+            .contributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        self.fee_debt = self.accrued(acc_fees_per_share)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug, InitSpace)]
+pub struct ApyHistoryEntry {
+    pub timestamp: i64,
+    pub slot: u64,
+    pub tiers: [ApyTier; 3],
+}
+
+/// Fixed-size ring buffer of APY schedule changes, oldest overwritten first. Only
+/// a log: the schedule in effect is TokenConfig.apy_tiers, and each position pays
+/// the rate it locked in, so a mid-lock change is never integrated into a payout.
+/// The log answers which schedule was in effect when an older position opened.
+#[account]
+#[derive(InitSpace)]
+pub struct ApyHistory {
+    pub version: u8, // Account layout version
+    pub head: u8,    // Index the next entry is written to
+    pub count: u8,   // Valid entries (saturates at APY_HISTORY_LEN)
+    pub total_recorded: u64, // Lifetime entries, including overwritten ones
+    pub entries: [ApyHistoryEntry; APY_HISTORY_LEN],
+    pub bump: u8,
+}
+
+impl ApyHistory {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Appends an entry, returning the slot index it was written to
+    pub fn record(&mut self, entry: ApyHistoryEntry) -> Result<u8> {
+        let index = self.head;
+        self.entries[index as usize] = entry;
+        self.head = ((index as usize + 1) % APY_HISTORY_LEN) as u8;
+        if (self.count as usize) < APY_HISTORY_LEN {
+            self.count += 1;
+        }
+        self.total_recorded = self
+            .total_recorded
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(index)
+    }
+
+    /// Valid entries from oldest to newest
+    pub fn entries_in_order(&self) -> Vec<ApyHistoryEntry> {
+        let start = (self.head as usize + APY_HISTORY_LEN - self.count as usize) % APY_HISTORY_LEN;
+        (0..self.count as usize)
+            .map(|i| self.entries[(start + i) % APY_HISTORY_LEN])
+            .collect()
+    }
+
+    pub fn latest(&self) -> Option<ApyHistoryEntry> {
+        self.entries_in_order().last().copied()
+    }
+
+    /// Checkpoint in effect at `timestamp`, or None if that predates the log. Once
+    /// the oldest entries are overwritten, the oldest retained one stands in for them.
+    pub fn entry_at(&self, timestamp: i64) -> Option<ApyHistoryEntry> {
+        let entries = self.entries_in_order();
+        match entries.iter().rev().find(|entry| entry.timestamp <= timestamp) {
+            Some(entry) => Some(*entry),
+            None if self.total_recorded > self.count as u64 => entries.first().copied(),
+            None => None,
+        }
+    }
+
+    /// Schedule in effect at `timestamp`. Before the log existed, schedules couldn't
+    /// be changed, so that's the compiled-in tiers.
+    pub fn tiers_at(&self, timestamp: i64) -> [ApyTier; 3] {
+        self.entry_at(timestamp)
+            .map_or_else(default_apy_tiers, |entry| entry.tiers)
+    }
+
+    /// Reads the log, or None before an admin has created (and seeded) it
+    pub fn load_history(info: &AccountInfo) -> Result<Option<ApyHistory>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        let history = Self::try_deserialize(&mut &data[..])?;
+        Ok((history.count > 0).then_some(history))
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub version: u8, // Account layout version
+    pub user: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Blocklist {
+    pub version: u8, // Account layout version
+    pub address: Pubkey, // Owner whose token accounts can't receive program transfers
+    pub blocked_at: i64,
+    pub bump: u8,
+}
+
+impl Blocklist {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// An address is blocked while its entry exists; unblocking closes it
+    pub fn is_blocked(info: &AccountInfo) -> bool {
+        info.owner == &crate::ID && !info.data_is_empty()
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct DelegationRecord {
+    pub version: u8, // Account layout version
+    pub token_account: Pubkey,
+    pub last_revoked_at: i64,
+    pub bump: u8,
+}
+
+impl DelegationRecord {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Last revoke time, or 0 before the account has ever been revoked
+    pub fn load_last_revoked_at(info: &AccountInfo) -> Result<i64> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(0);
+        }
+
+        let data = info.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.last_revoked_at)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserPreferences {
+    pub version: u8, // Account layout version
+    pub user: Pubkey,
+    pub payout_destination: Pubkey, // Token account unstakes pay into (default = unset)
+    pub bump: u8,
+}
+
+impl UserPreferences {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// Stored payout destination, or None before the user has set one
+    pub fn load_payout_destination(info: &AccountInfo) -> Result<Option<Pubkey>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        let destination = Self::try_deserialize(&mut &data[..])?.payout_destination;
+        Ok((destination != Pubkey::default()).then_some(destination))
+    }
+}
+
+/// Where staking rewards are paid from. Without it (or when `reward_mint` is the
+/// stake mint) rewards come out of the stake vault alongside principal.
+#[account]
+#[derive(InitSpace)]
+pub struct RewardConfig {
+    pub version: u8, // Account layout version
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey, // Token account of reward_mint, owned by the vault authority
+    pub bump: u8,
+}
+
+impl RewardConfig {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    /// True when rewards are a different token from the staked one
+    pub fn is_split(&self, stake_mint: &Pubkey) -> bool {
+        self.reward_mint != *stake_mint
+    }
+
+    /// The separate reward route, or None while rewards are paid in the stake mint
+    /// (including before an admin has created the account)
+    pub fn load_split(info: &AccountInfo, stake_mint: &Pubkey) -> Result<Option<RewardConfig>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        let reward_config = Self::try_deserialize(&mut &data[..])?;
+        Ok(reward_config.is_split(stake_mint).then_some(reward_config))
+    }
+}
+
+/// Test fixture: an open 10,000 DEVR position at the default tier rate for its
+/// lock. Tests override the rest with struct-update syntax.
+#[cfg(test)]
+pub(crate) fn test_stake_account(staked_at: i64, lock_duration: i64) -> StakeAccount {
+    let (apy_numerator, apy_denominator) =
+        crate::constants::get_apy_for_duration(&default_apy_tiers(), lock_duration);
+    StakeAccount {
+        version: crate::constants::STAKE_ACCOUNT_VERSION,
+        user: Pubkey::new_unique(),
+        staked_amount: 10_000_000_000_000,
+        staked_at,
+        lock_duration,
+        maturity_timestamp: staked_at + lock_duration,
+        stake_index: 0,
+        bump: 255,
+        frozen: false,
+        frozen_at: 0,
+        unfrozen_at: 0,
+        loyalty_bonus_bps: 0,
+        epoch_bonus_bps: 0,
+        last_compound_at: 0,
+        compounded_rewards: 0,
+        weighted_staked_seconds: 0,
+        boost_amount: 0,
+        boost_multiplier_bps: 0,
+        apy_numerator,
+        apy_denominator,
+        reward_liability: 0,
+        is_withdrawn: false,
+        unstake_requested_at: 0,
+    }
+}
+
+/// Test fixture: program-wide stats holding `total_staked` and nothing else
+#[cfg(test)]
+pub(crate) fn test_global_stats(total_staked: u64) -> GlobalStats {
+    GlobalStats {
+        version: CURRENT_ACCOUNT_VERSION,
+        total_staked,
+        total_stakes: 1,
+        bump: 255,
+        peak_total_staked: total_staked,
+        ..GlobalStats::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{DEFAULT_REFERRAL_BONUS, NO_REWARD_CAP};
+
+    fn closed_stake_info<'a>(key: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, &system_program::ID, false, 0)
+    }
+
+    #[test]
+    fn test_load_open_reports_closed_stake() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = closed_stake_info(&key, &mut lamports, &mut data);
+
+        // Index 0 was already handed out (counter is at 1), so the position was closed
+        let result = StakeAccount::load_open(&info, 0, 1);
+        assert_eq!(result.err(), Some(error!(ErrorCode::StakeAlreadyClosed)));
+    }
+
+    #[test]
+    fn test_zeroed_stake_is_unused() {
+        let data = [0u8; StakeAccount::LEN];
+        let mut stake_account = StakeAccount::try_deserialize_unchecked(&mut &data[..]).unwrap();
+        assert!(stake_account.is_unused());
+
+        stake_account.user = Pubkey::new_unique();
+        assert!(!stake_account.is_unused());
+    }
+
+    #[test]
+    fn test_reward_config_split_only_for_another_mint() {
+        let key = Pubkey::new_unique();
+        let stake_mint = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = closed_stake_info(&key, &mut lamports, &mut data);
+        // Never configured: single-mint
+        assert!(RewardConfig::load_split(&info, &stake_mint).unwrap().is_none());
+
+        let mut reward_config = RewardConfig {
+            version: 1,
+            reward_mint: stake_mint,
+            reward_vault: Pubkey::new_unique(),
+            bump: 255,
+        };
+        assert!(!reward_config.is_split(&stake_mint));
+
+        reward_config.reward_mint = Pubkey::new_unique();
+        let mut data = Vec::new();
+        reward_config.try_serialize(&mut data).unwrap();
+        let mut lamports = 1;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        let split = RewardConfig::load_split(&info, &stake_mint).unwrap().unwrap();
+        assert_eq!(split.reward_vault, reward_config.reward_vault);
+    }
+
+    #[test]
+    fn test_feature_flags_default_before_init() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = closed_stake_info(&key, &mut lamports, &mut data);
+
+        assert_eq!(FeatureFlags::load_flags(&info).unwrap(), DEFAULT_FEATURE_FLAGS);
+    }
+
+    #[test]
+    fn test_blocklist_entry_presence() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = closed_stake_info(&key, &mut lamports, &mut data);
+        // Never blocked, or unblocked (entry closed)
+        assert!(!Blocklist::is_blocked(&info));
+
+        let mut lamports = 1;
+        let mut data = [0u8; Blocklist::LEN];
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert!(Blocklist::is_blocked(&info));
+    }
+
+    #[test]
+    fn test_feature_bits() {
+        use crate::constants::{ALL_FEATURES, FEATURE_CIRCUIT_BREAKER, FEATURE_LOYALTY_BONUS};
+
+        let flags = ALL_FEATURES & !FEATURE_LOYALTY_BONUS;
+        assert!(!is_feature_enabled(flags, FEATURE_LOYALTY_BONUS));
+        assert!(is_feature_enabled(flags, FEATURE_CIRCUIT_BREAKER));
+        assert!(!is_feature_enabled(0, FEATURE_CIRCUIT_BREAKER));
+    }
+
+    #[test]
+    fn test_load_open_reports_missing_stake() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = closed_stake_info(&key, &mut lamports, &mut data);
+
+        // Index 1 was never created
+        let result = StakeAccount::load_open(&info, 1, 1);
+        assert_eq!(result.err(), Some(error!(ErrorCode::StakeNotFound)));
+    }
+
+    fn apy_history() -> ApyHistory {
+        ApyHistory {
+            version: 1,
+            head: 0,
+            count: 0,
+            total_recorded: 0,
+            entries: [ApyHistoryEntry::default(); APY_HISTORY_LEN],
+            bump: 255,
+        }
+    }
+
+    fn history_entry(timestamp: i64) -> ApyHistoryEntry {
+        ApyHistoryEntry {
+            timestamp,
+            slot: timestamp as u64,
+            tiers: crate::constants::default_apy_tiers(),
+        }
+    }
+
+    #[test]
+    fn test_apy_history_records_in_order() {
+        let mut history = apy_history();
+        for t in 1..=3 {
+            history.record(history_entry(t)).unwrap();
+        }
+
+        let timestamps: Vec<i64> = history.entries_in_order().iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![1, 2, 3]);
+        assert_eq!(history.latest().unwrap().timestamp, 3);
+    }
+
+    #[test]
+    fn test_apy_history_wraps_and_keeps_newest() {
+        let mut history = apy_history();
+        let total = APY_HISTORY_LEN as i64 + 3;
+        for t in 1..=total {
+            history.record(history_entry(t)).unwrap();
+        }
+
+        let entries = history.entries_in_order();
+        assert_eq!(entries.len(), APY_HISTORY_LEN);
+        // The three oldest were overwritten
+        assert_eq!(entries.first().unwrap().timestamp, 4);
+        assert_eq!(entries.last().unwrap().timestamp, total);
+        assert_eq!(history.total_recorded, total as u64);
+        assert_eq!(history.head, 3);
+    }
+
+    #[test]
+    fn test_apy_history_entry_at() {
+        let mut history = apy_history();
+        let mut raised = history_entry(200);
+        raised.tiers[2].apy_numerator = 30;
+        history.record(history_entry(100)).unwrap();
+        history.record(raised).unwrap();
+
+        // Before the log was created: the compiled-in schedule
+        assert!(history.entry_at(99).is_none());
+        assert_eq!(history.tiers_at(99), crate::constants::default_apy_tiers());
+        assert_eq!(history.entry_at(100).unwrap().timestamp, 100);
+        assert_eq!(history.entry_at(199).unwrap().timestamp, 100);
+        assert_eq!(history.tiers_at(200)[2].apy_numerator, 30);
+        assert_eq!(history.tiers_at(i64::MAX)[2].apy_numerator, 30);
+    }
+
+    #[test]
+    fn test_apy_history_entry_at_after_wrap() {
+        let mut history = apy_history();
+        let total = APY_HISTORY_LEN as i64 + 3;
+        for t in 1..=total {
+            history.record(history_entry(t * 100)).unwrap();
+        }
+
+        // Entries 1-3 were overwritten, so the oldest retained one covers them
+        assert_eq!(history.entry_at(150).unwrap().timestamp, 400);
+        assert_eq!(history.entry_at(450).unwrap().timestamp, 400);
+    }
+
+    fn lp_pool() -> LpPool {
+        LpPool {
+            version: 1,
+            total_contributed: 0,
+            acc_fees_per_share: 0,
+            total_fees_deposited: 0,
+            bump: 255,
+            treasury_bump: 255,
+        }
+    }
+
+    fn lp() -> LiquidityProvider {
+        LiquidityProvider {
+            version: 1,
+            provider: Pubkey::new_unique(),
+            contributed: 0,
+            fee_debt: 0,
+            unclaimed_fees: 0,
+            total_claimed: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_two_lps_split_fees_pro_rata() {
+        let mut pool = lp_pool();
+        let mut alice = lp();
+        let mut bob = lp();
+
+        // 1:3 contributions
+        alice.add_contribution(100_000_000_000, pool.acc_fees_per_share).unwrap();
+        bob.add_contribution(300_000_000_000, pool.acc_fees_per_share).unwrap();
+        pool.total_contributed = 400_000_000_000;
+
+        pool.distribute(40_000_000_000).unwrap();
+        alice.settle(pool.acc_fees_per_share).unwrap();
+        bob.settle(pool.acc_fees_per_share).unwrap();
+
+        assert_eq!(alice.unclaimed_fees, 10_000_000_000);
+        assert_eq!(bob.unclaimed_fees, 30_000_000_000);
+    }
+
+    #[test]
+    fn test_late_contribution_only_earns_future_fees() {
+        let mut pool = lp_pool();
+        let mut early = lp();
+        let mut late = lp();
+
+        early.add_contribution(100_000_000_000, pool.acc_fees_per_share).unwrap();
+        pool.total_contributed = 100_000_000_000;
+        pool.distribute(10_000_000_000).unwrap();
+
+        late.add_contribution(100_000_000_000, pool.acc_fees_per_share).unwrap();
+        pool.total_contributed = 200_000_000_000;
+        pool.distribute(10_000_000_000).unwrap();
+
+        early.settle(pool.acc_fees_per_share).unwrap();
+        late.settle(pool.acc_fees_per_share).unwrap();
+        assert_eq!(early.unclaimed_fees, 15_000_000_000);
+        assert_eq!(late.unclaimed_fees, 5_000_000_000);
+    }
+
+    #[test]
+    fn test_distribute_without_lps_fails() {
+        let mut pool = lp_pool();
+        assert_eq!(
+            pool.distribute(1).err(),
+            Some(error!(ErrorCode::NoLiquidityProviders))
+        );
+    }
+
+    const DAY: i64 = 86_400;
+
+    #[test]
+    fn test_set_lock_duration_moves_maturity() {
+        let mut position = test_stake_account(START, 30 * DAY);
+        assert_eq!(position.maturity_timestamp, position.staked_at + position.lock_duration);
+
+        // Extending the lock moves the stored maturity with it
+        position.set_lock_duration(90 * DAY).unwrap();
+        assert_eq!(position.lock_duration, 90 * DAY);
+        assert_eq!(position.maturity_timestamp, START + 90 * DAY);
+    }
+
+    #[test]
+    fn test_lock_can_never_shorten() {
+        assert!(assert_lock_not_shortened(30 * DAY, 30 * DAY).is_ok());
+        assert!(assert_lock_not_shortened(30 * DAY, 90 * DAY).is_ok());
+        assert_eq!(
+            assert_lock_not_shortened(30 * DAY, 30 * DAY - 1).err(),
+            Some(error!(ErrorCode::LockCannotShorten))
+        );
+
+        // Both mutators go through the check and leave the position untouched
+        let mut position = test_stake_account(START, 30 * DAY);
+        assert!(position.set_lock_duration(7 * DAY).is_err());
+        assert!(position.extend_lock(-DAY).is_err());
+        assert_eq!(position.lock_duration, 30 * DAY);
+        assert_eq!(position.maturity_timestamp, START + 30 * DAY);
+    }
+
+    #[test]
+    fn test_mark_withdrawn_only_once() {
+        let mut position = test_stake_account(START, 30 * DAY);
+        position.mark_withdrawn().unwrap();
+        assert!(position.is_withdrawn);
+
+        // The flag survives the write-back, so a second unstake is rejected
+        let mut data = Vec::new();
+        position.try_serialize(&mut data).unwrap();
+        let mut reloaded = StakeAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(
+            reloaded.mark_withdrawn().err(),
+            Some(error!(ErrorCode::AlreadyWithdrawn))
+        );
+    }
+
+    #[test]
+    fn test_zero_unstake_delay_keeps_one_step_unstake() {
+        let position = test_stake_account(START, 30 * DAY);
+        assert!(position.check_unstake_delay(0, START + 30 * DAY).is_ok());
+    }
+
+    #[test]
+    fn test_unstake_delay_enforced_after_request() {
+        let delay = 2 * DAY;
+        let matured = START + 30 * DAY;
+        let mut position = test_stake_account(START, 30 * DAY);
+
+        // Not requested: no amount of waiting is enough
+        assert_eq!(
+            position.check_unstake_delay(delay, matured + 365 * DAY).err(),
+            Some(error!(ErrorCode::UnstakeNotRequested))
+        );
+
+        position.request_unstake(matured).unwrap();
+        assert_eq!(
+            position.check_unstake_delay(delay, matured + delay - 1).err(),
+            Some(error!(ErrorCode::UnstakeDelayActive))
+        );
+        assert!(position.check_unstake_delay(delay, matured + delay).is_ok());
+
+        // A second request can't restart (or double-count) the delay
+        assert_eq!(
+            position.request_unstake(matured + DAY).err(),
+            Some(error!(ErrorCode::UnstakeAlreadyRequested))
+        );
+        assert_eq!(position.unstake_requested_at, matured);
+    }
+
+    #[test]
+    fn test_extend_lock_keeps_tranche_weighting() {
+        let mut position = test_stake_account(START, 30 * DAY);
+        position.extend_lock(60 * DAY).unwrap();
+        assert_eq!(position.lock_duration, 90 * DAY);
+        assert_eq!(position.maturity_timestamp, START + 90 * DAY);
+        assert_eq!(position.reward_principal(), position.staked_amount);
+
+        // Half of the principal added with 10 of 30 days left earns a third of the lock...
+        let mut tranched = test_stake_account(START, 30 * DAY);
+        tranched.staked_amount = 1_000;
+        tranched.add_tranche(1_000, START + 20 * DAY).unwrap();
+        assert_eq!(tranched.reward_principal(), 1_333);
+
+        // ...and both tranches earn the extra 60 days in full
+        tranched.extend_lock(60 * DAY).unwrap();
+        assert_eq!(tranched.reward_principal(), 1_777);
+    }
+
+    #[test]
+    fn test_never_frozen_earns_full_lock() {
+        let position = test_stake_account(START, 30 * DAY);
+        assert_eq!(position.reward_eligible_duration(START + 60 * DAY), 30 * DAY);
+    }
+
+    #[test]
+    fn test_cleared_freeze_is_excluded() {
+        let mut position = test_stake_account(START, 30 * DAY);
+        position.frozen_at = START + 10 * DAY;
+        position.unfrozen_at = START + 20 * DAY;
+
+        assert_eq!(position.frozen_seconds(START + 60 * DAY), 10 * DAY);
+        assert_eq!(position.reward_eligible_duration(START + 60 * DAY), 20 * DAY);
+    }
+
+    #[test]
+    fn test_lapsed_freeze_stops_at_max_duration() {
+        let mut position = test_stake_account(START, 90 * DAY);
+        position.frozen = true;
+        position.frozen_at = START + DAY;
+
+        // Never cleared - the hold only counts until it lapsed
+        assert_eq!(position.frozen_seconds(START + 90 * DAY), MAX_FREEZE_DURATION);
+    }
+
+    #[test]
+    fn test_freeze_after_lock_end_costs_nothing() {
+        let mut position = test_stake_account(START, 7 * DAY);
+        position.frozen_at = START + 10 * DAY;
+        position.unfrozen_at = START + 12 * DAY;
+
+        assert_eq!(position.reward_eligible_duration(START + 12 * DAY), 7 * DAY);
+    }
+
+    #[test]
+    fn test_claim_nonce_binds_each_claim() {
+        let mut user_claim = UserClaim {
+            version: 1,
+            user: Pubkey::new_unique(),
+            last_claim_time: 0,
+            total_claimed: 0,
+            bump: 255,
+            auto_claim_to_stake: false,
+            claim_nonce: 0,
+            current_streak: 0,
+            longest_streak: 0,
+        };
+
+        // Correct nonce is accepted and advances
+        user_claim.consume_nonce(0).unwrap();
+        assert_eq!(user_claim.claim_nonce, 1);
+
+        // Replaying the same (now stale) nonce fails and changes nothing
+        assert_eq!(
+            user_claim.consume_nonce(0).unwrap_err(),
+            error!(ErrorCode::NonceMismatch)
+        );
+        assert_eq!(user_claim.claim_nonce, 1);
+
+        // So does jumping ahead
+        assert!(user_claim.consume_nonce(5).is_err());
+        user_claim.consume_nonce(1).unwrap();
+        assert_eq!(user_claim.claim_nonce, 2);
+    }
+
+    #[test]
+    fn test_lifetime_claim_cap() {
+        const DAILY: u64 = 100;
+        let mut user_claim = UserClaim {
+            version: 1,
+            user: Pubkey::new_unique(),
+            last_claim_time: 0,
+            total_claimed: 0,
+            bump: 255,
+            auto_claim_to_stake: false,
+            claim_nonce: 0,
+            current_streak: 0,
+            longest_streak: 0,
+        };
+
+        // A cap of three claims: the third lands exactly on it
+        for _ in 0..3 {
+            user_claim.check_lifetime_cap(DAILY, 3 * DAILY).unwrap();
+            user_claim.total_claimed += DAILY;
+        }
+        assert_eq!(
+            user_claim.check_lifetime_cap(DAILY, 3 * DAILY).unwrap_err(),
+            error!(ErrorCode::ClaimCapReached)
+        );
+
+        // One unit over is already too much
+        user_claim.total_claimed = 3 * DAILY - DAILY + 1;
+        assert!(user_claim.check_lifetime_cap(DAILY, 3 * DAILY).is_err());
+
+        // 0 keeps the old unlimited behavior
+        user_claim.total_claimed = u64::MAX - DAILY;
+        user_claim.check_lifetime_cap(DAILY, NO_LIFETIME_CLAIM_CAP).unwrap();
+    }
+
+    #[test]
+    fn test_referral_bonus_accrues_per_referred_claim() {
+        let mut referral = Referral {
+            version: 1,
+            referrer: Pubkey::new_unique(),
+            total_referred: 0,
+            referral_rewards: 0,
+            bump: 255,
+        };
+
+        for _ in 0..3 {
+            referral.record_referral(DEFAULT_REFERRAL_BONUS).unwrap();
+        }
+        assert_eq!(referral.total_referred, 3);
+        assert_eq!(referral.referral_rewards, 3 * DEFAULT_REFERRAL_BONUS);
+
+        // With the bonus switched off, referrals still count
+        referral.record_referral(0).unwrap();
+        assert_eq!(referral.total_referred, 4);
+        assert_eq!(referral.referral_rewards, 3 * DEFAULT_REFERRAL_BONUS);
+    }
+
+    #[test]
+    fn test_claim_streaks() {
+        let mut user_claim = UserClaim {
+            version: 1,
+            user: Pubkey::new_unique(),
+            last_claim_time: 0,
+            total_claimed: 0,
+            bump: 255,
+            auto_claim_to_stake: false,
+            claim_nonce: 0,
+            current_streak: 0,
+            longest_streak: 0,
+        };
+        let claim_at = |user_claim: &mut UserClaim, now: i64| {
+            user_claim.record_streak(now);
+            user_claim.last_claim_time = now;
+        };
+
+        // First claim starts a streak; claims 24-48h apart continue it
+        claim_at(&mut user_claim, START);
+        assert_eq!(user_claim.current_streak, 1);
+        claim_at(&mut user_claim, START + DAY);
+        claim_at(&mut user_claim, START + 3 * DAY);
+        assert_eq!((user_claim.current_streak, user_claim.longest_streak), (3, 3));
+
+        // One second past the window breaks it, but the record stays
+        claim_at(&mut user_claim, START + 5 * DAY + 1);
+        assert_eq!((user_claim.current_streak, user_claim.longest_streak), (1, 3));
+        claim_at(&mut user_claim, START + 6 * DAY + 1);
+        assert_eq!((user_claim.current_streak, user_claim.longest_streak), (2, 3));
+    }
+
+    #[test]
+    fn test_peak_total_staked_only_rises() {
+        let mut global_stats = test_global_stats(10_000_000_000);
+
+        // Staking to a new high moves the watermark
+        global_stats.total_staked += 5_000_000_000;
+        global_stats.update_peak();
+        assert_eq!(global_stats.peak_total_staked, 15_000_000_000);
+
+        // Unstaking leaves it where it was
+        global_stats.record_unstake(8_000_000_000, 100_000_000).unwrap();
+        global_stats.update_peak();
+        assert_eq!(global_stats.total_staked, 7_000_000_000);
+        assert_eq!(global_stats.peak_total_staked, 15_000_000_000);
+
+        // Climbing back below the old high doesn't change it either
+        global_stats.total_staked += 1_000_000_000;
+        global_stats.update_peak();
+        assert_eq!(global_stats.peak_total_staked, 15_000_000_000);
+    }
+
+    #[test]
+    fn test_unbonding_beyond_cap_is_rejected() {
+        let mut counter = StakeCounter {
+            version: 1,
+            stake_count: MAX_UNBONDING_PER_USER + 1,
+            bump: 255,
+            unbonding_count: 0,
+            active_stakes: 0,
+        };
+
+        for _ in 0..MAX_UNBONDING_PER_USER {
+            counter.begin_unbonding().unwrap();
+        }
+        assert_eq!(
+            counter.begin_unbonding().unwrap_err(),
+            error!(ErrorCode::TooManyUnbonding)
+        );
+
+        // Completing an unstake frees a slot
+        counter.finish_unbonding();
+        counter.begin_unbonding().unwrap();
+        assert_eq!(counter.unbonding_count, MAX_UNBONDING_PER_USER);
+    }
+
+    #[test]
+    fn test_active_stakes_track_open_positions() {
+        let mut counter = StakeCounter {
+            version: 1,
+            stake_count: 0,
+            bump: 255,
+            unbonding_count: 0,
+            active_stakes: 0,
+        };
+
+        // Stake three, close one, stake again, close two
+        for _ in 0..3 {
+            counter.open_position().unwrap();
+        }
+        counter.close_position();
+        assert_eq!((counter.stake_count, counter.active_stakes), (3, 2));
+
+        counter.open_position().unwrap();
+        counter.close_position();
+        counter.close_position();
+        assert_eq!((counter.stake_count, counter.active_stakes), (4, 1));
+
+        // A rollover closes then opens, leaving the count unchanged
+        counter.close_position();
+        counter.open_position().unwrap();
+        assert_eq!((counter.stake_count, counter.active_stakes), (5, 1));
+
+        // Indexes keep growing after every position is closed
+        counter.close_position();
+        counter.close_position();
+        assert_eq!(counter.active_stakes, 0);
+        counter.open_position().unwrap();
+        assert_eq!((counter.stake_count, counter.active_stakes), (6, 1));
+    }
+
+    #[test]
+    fn test_open_positions_beyond_cap_are_rejected() {
+        let mut counter = StakeCounter {
+            version: 1,
+            stake_count: 0,
+            bump: 255,
+            unbonding_count: 0,
+            active_stakes: 0,
+        };
+
+        for _ in 0..MAX_STAKES_PER_USER {
+            counter.open_position().unwrap();
+        }
+        assert_eq!(
+            counter.open_position().unwrap_err(),
+            error!(ErrorCode::TooManyStakes)
+        );
+        assert_eq!(counter.stake_count, MAX_STAKES_PER_USER);
+
+        // Closing a position makes room again
+        counter.close_position();
+        counter.open_position().unwrap();
+        assert_eq!(counter.active_stakes, MAX_STAKES_PER_USER);
+    }
+
+    #[test]
+    fn test_penalties_accumulate_with_checked_math() {
+        let mut global_stats = test_global_stats(0);
+        global_stats.record_penalty(5_000_000_000).unwrap();
+        global_stats.record_penalty(0).unwrap();
+        global_stats.record_penalty(2_500_000_000).unwrap();
+        assert_eq!(global_stats.total_penalties_collected, 7_500_000_000);
+    }
+
+    #[test]
+    fn test_rewards_funded_accumulates() {
+        let mut global_stats = test_global_stats(0);
+        global_stats.record_rewards_funded(1_000_000_000_000).unwrap();
+        global_stats.record_rewards_funded(500_000_000_000).unwrap();
+        assert_eq!(global_stats.total_rewards_funded, 1_500_000_000_000);
+        assert_eq!(global_stats.total_staked, 0);
+
+        global_stats.total_rewards_funded = u64::MAX;
+        assert_eq!(
+            global_stats.record_rewards_funded(1).err(),
+            Some(error!(ErrorCode::ArithmeticOverflow))
+        );
+        // Penalties are tracked apart from insurance fees
+        assert_eq!(global_stats.insurance_fund_balance, 0);
+
+        global_stats.total_penalties_collected = u64::MAX;
+        assert_eq!(
+            global_stats.record_penalty(1).unwrap_err(),
+            error!(ErrorCode::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_reward_cap_rejects_the_unstake_that_crosses_it() {
+        const CAP: u64 = 1_000;
+        let mut global_stats = test_global_stats(10_000);
+
+        // Three unstakes paying 400 each: the first two fit, the third would cross
+        for _ in 0..2 {
+            global_stats.check_reward_cap(400, CAP).unwrap();
+            global_stats.record_unstake(1_000, 400).unwrap();
+        }
+        assert_eq!(
+            global_stats.check_reward_cap(400, CAP).unwrap_err(),
+            error!(ErrorCode::RewardCapReached)
+        );
+
+        // Landing exactly on the cap is fine, after which no reward fits
+        global_stats.check_reward_cap(200, CAP).unwrap();
+        global_stats.record_unstake(1_000, 200).unwrap();
+        assert!(global_stats.check_reward_cap(1, CAP).is_err());
+
+        // Principal-only withdrawals are never blocked by the cap
+        global_stats.check_reward_cap(0, CAP).unwrap();
+
+        // u64::MAX is effectively no cap
+        global_stats.check_reward_cap(u64::MAX - CAP, NO_REWARD_CAP).unwrap();
+    }
+
+    #[test]
+    fn test_claims_never_touch_vault_backing() {
+        const CLAIM: u64 = 100;
+        let mut global_stats = test_global_stats(0);
+        let mut vault_balance = 0;
+
+        // Claim, then stake 1,000 with the vault funded for exactly its reservation
+        global_stats.record_minted(CLAIM).unwrap();
+        vault_balance += 1_000 + 150;
+        global_stats.total_staked += 1_000;
+        global_stats.reserve_rewards(150, vault_balance).unwrap();
+        global_stats.check_vault_backing(vault_balance).unwrap();
+
+        // With the vault fully reserved, claims still go through and leave it backed
+        let reserved = (global_stats.total_staked, global_stats.total_reward_liability);
+        for _ in 0..3 {
+            global_stats.record_minted(CLAIM).unwrap();
+        }
+        assert_eq!(global_stats.total_minted, 4 * CLAIM);
+        assert_eq!((global_stats.total_staked, global_stats.total_reward_liability), reserved);
+        global_stats.check_vault_backing(vault_balance).unwrap();
+
+        // Only a vault short of principal or reservations fails
+        assert_eq!(
+            global_stats.check_vault_backing(vault_balance - 1).unwrap_err(),
+            error!(ErrorCode::InsufficientRewardReserve)
+        );
+        assert_eq!(
+            global_stats.check_vault_backing(999).unwrap_err(),
+            error!(ErrorCode::InsufficientVaultBalance)
+        );
+    }
+
+    #[test]
+    fn test_reserve_rewards_requires_spare_vault_liquidity() {
+        // 1,000 staked; the vault holds 1,150
+        let mut global_stats = test_global_stats(1_000);
+        global_stats.reserve_rewards(100, 1_150).unwrap();
+        assert_eq!(global_stats.total_reward_liability, 100);
+
+        // Only 50 is left unreserved
+        assert_eq!(
+            global_stats.reserve_rewards(51, 1_150).unwrap_err(),
+            error!(ErrorCode::InsufficientRewardReserve)
+        );
+        assert_eq!(global_stats.total_reward_liability, 100);
+        global_stats.reserve_rewards(50, 1_150).unwrap();
+
+        // Paying out a position frees its reservation for the next staker
+        global_stats.release_rewards(100).unwrap();
+        assert_eq!(global_stats.total_reward_liability, 50);
+        global_stats.reserve_rewards(100, 1_150).unwrap();
+
+        assert_eq!(
+            global_stats.release_rewards(u64::MAX).unwrap_err(),
+            error!(ErrorCode::ArithmeticOverflow)
+        );
+    }
+
+    const WINDOW: i64 = 3_600;
+    const THRESHOLD: u64 = 100_000_000_000_000; // 100,000 DEVR per window
+    const START: i64 = 1_700_000_000;
+
+    #[test]
+    fn test_circuit_breaker_trips_on_rapid_unstakes() {
+        let mut global_stats = test_global_stats(0);
+
+        // Two large unstakes a minute apart stay under the threshold
+        assert!(!global_stats.record_unstake_volume(50_000_000_000_000, START, WINDOW, THRESHOLD).unwrap());
+        assert!(!global_stats.record_unstake_volume(50_000_000_000_000, START + 60, WINDOW, THRESHOLD).unwrap());
+        assert!(!global_stats.staking_paused);
+
+        // The third pushes the window over and trips the breaker
+        assert!(global_stats.record_unstake_volume(1_000_000_000, START + 120, WINDOW, THRESHOLD).unwrap());
+        assert!(global_stats.staking_paused);
+
+        // Already tripped - later unstakes don't report a new trip
+        assert!(!global_stats.record_unstake_volume(1_000_000_000, START + 180, WINDOW, THRESHOLD).unwrap());
+    }
+
+    #[test]
+    fn test_circuit_breaker_window_rolls_off() {
+        let mut global_stats = test_global_stats(0);
+
+        global_stats.record_unstake_volume(80_000_000_000_000, START, WINDOW, THRESHOLD).unwrap();
+
+        // A full window later the earlier volume has expired
+        let tripped = global_stats
+            .record_unstake_volume(80_000_000_000_000, START + WINDOW + 1, WINDOW, THRESHOLD)
+            .unwrap();
+        assert!(!tripped);
+        assert_eq!(global_stats.window_unstake_volume().unwrap(), 80_000_000_000_000);
+    }
+
+    #[test]
+    fn test_circuit_breaker_partial_roll_off() {
+        let mut global_stats = test_global_stats(0);
+        let bucket_len = WINDOW / CIRCUIT_BREAKER_BUCKETS as i64;
+
+        global_stats.record_unstake_volume(60_000_000_000_000, START, WINDOW, THRESHOLD).unwrap();
+        global_stats
+            .record_unstake_volume(30_000_000_000_000, START + bucket_len * 3, WINDOW, THRESHOLD)
+            .unwrap();
+
+        // The first unstake's bucket slides out; only the second remains
+        global_stats
+            .record_unstake_volume(0, START + WINDOW, WINDOW, THRESHOLD)
+            .unwrap();
+        assert_eq!(global_stats.window_unstake_volume().unwrap(), 30_000_000_000_000);
+    }
+
+    #[test]
+    fn test_circuit_breaker_disabled() {
+        let mut global_stats = test_global_stats(0);
+        assert!(!global_stats.record_unstake_volume(u64::MAX, START, WINDOW, 0).unwrap());
+        assert!(!global_stats.staking_paused);
+    }
+
+    #[test]
+    fn test_record_unstake_tracks_principal() {
+        let mut global_stats = test_global_stats(150_000_000_000);
+
+        global_stats.record_unstake(50_000_000_000, 479_452_054).unwrap();
+
+        assert_eq!(global_stats.total_staked, 100_000_000_000);
+        assert_eq!(global_stats.total_unstaked_principal, 50_000_000_000);
+        assert_eq!(global_stats.total_rewards_paid, 479_452_054);
+    }
+
+    #[test]
+    fn test_record_unstake_accumulates() {
+        let mut global_stats = test_global_stats(150_000_000_000);
+
+        global_stats.record_unstake(50_000_000_000, 0).unwrap();
+        global_stats.record_unstake(100_000_000_000, 0).unwrap();
+
+        assert_eq!(global_stats.total_staked, 0);
+        assert_eq!(global_stats.total_unstaked_principal, 150_000_000_000);
+    }
+
+    #[test]
+    fn test_record_unstake_rejects_underflow() {
+        let mut global_stats = test_global_stats(10);
+        assert!(global_stats.record_unstake(11, 0).is_err());
+    }
+
+    #[test]
+    fn test_user_stats_follow_stake_and_unstake_sequence() {
+        let mut user_stats = UserStats {
+            version: 1,
+            user: Pubkey::new_unique(),
+            total_ever_staked: 0,
+            total_rewards_earned: 0,
+            stakes_opened: 0,
+            stakes_closed: 0,
+            bump: 255,
+        };
+
+        // Two positions, one unstaked in two parts, the other in one go
+        user_stats.record_stake(100_000_000_000).unwrap();
+        user_stats.record_stake(50_000_000_000).unwrap();
+        user_stats.record_unstake(1_000_000, false).unwrap();
+        assert_eq!(user_stats.stakes_closed, 0);
+        user_stats.record_unstake(1_000_000, true).unwrap();
+        user_stats.record_unstake(479_452_054, true).unwrap();
+        // Restaking adds to the lifetime total instead of replacing it
+        user_stats.record_stake(100_000_000_000).unwrap();
+        // A top-up is principal staked, but no new position
+        user_stats.record_deposit(25_000_000_000).unwrap();
+
+        assert_eq!(user_stats.total_ever_staked, 275_000_000_000);
+        assert_eq!(user_stats.total_rewards_earned, 481_452_054);
+        assert_eq!(user_stats.stakes_opened, 3);
+        assert_eq!(user_stats.stakes_closed, 2);
+    }
 }
@@ -0,0 +1,113 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  setupInitializedProgram,
+  createAndFundUserWithoutTokens,
+} from "./utils/test-helpers";
+
+describe("Admin Transfer Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda } = deriveProgramPDAs(program);
+
+  let newAdmin: Keypair;
+
+  // Omitting the signer means the provider wallet, which signs every transaction
+  const transferAdmin = (proposed: PublicKey, signer?: Keypair) => {
+    const builder = program.methods
+      .transferAdmin(proposed)
+      .accounts({ config: configPda, admin: (signer ?? admin).publicKey });
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  const acceptAdmin = (signer?: Keypair) => {
+    const builder = program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, newAdmin: (signer ?? admin).publicKey });
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    newAdmin = await createAndFundUserWithoutTokens(provider, 1);
+  });
+
+  after(async () => {
+    // Every other suite signs admin setters with the provider wallet
+    const config = await program.account.tokenConfig.fetch(configPda);
+    if (!config.admin.equals(admin.publicKey)) {
+      await transferAdmin(admin.publicKey, newAdmin).rpc();
+      await acceptAdmin().rpc();
+    }
+  });
+
+  it("should reject a handover proposed by a non-admin", async () => {
+    const attacker = await createAndFundUserWithoutTokens(provider, 1);
+
+    try {
+      await transferAdmin(attacker.publicKey, attacker).rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should keep the current admin until the handover is accepted", async () => {
+    const { events } = await transferAdmin(newAdmin.publicKey).simulate();
+    const proposed = events.find((e) => e.name === "adminTransferProposedEvent")?.data as any;
+    expect(proposed.pendingAdmin.toString()).to.equal(newAdmin.publicKey.toString());
+
+    await transferAdmin(newAdmin.publicKey).rpc();
+
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.admin.toString()).to.equal(admin.publicKey.toString());
+    expect(config.pendingAdmin.toString()).to.equal(newAdmin.publicKey.toString());
+  });
+
+  it("should reject an accept from anyone but the pending admin", async () => {
+    const impostor = await createAndFundUserWithoutTokens(provider, 1);
+
+    try {
+      await acceptAdmin(impostor).rpc();
+      expect.fail("Should have thrown NotPendingAdmin error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6071); // NotPendingAdmin
+    }
+  });
+
+  it("should hand over once the pending admin accepts", async () => {
+    const { events } = await acceptAdmin(newAdmin).simulate();
+    const transferred = events.find((e) => e.name === "adminTransferredEvent")?.data as any;
+    expect(transferred.oldAdmin.toString()).to.equal(admin.publicKey.toString());
+    expect(transferred.newAdmin.toString()).to.equal(newAdmin.publicKey.toString());
+
+    await acceptAdmin(newAdmin).rpc();
+
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.admin.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(config.pendingAdmin.toString()).to.equal(PublicKey.default.toString());
+  });
+
+  it("should lock the old admin out of admin setters", async () => {
+    try {
+      await program.methods
+        .setPause(true)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should let the admin cancel a pending handover", async () => {
+    const stranger = Keypair.generate();
+    await transferAdmin(stranger.publicKey, newAdmin).rpc();
+    await transferAdmin(PublicKey.default, newAdmin).rpc();
+
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.pendingAdmin.toString()).to.equal(PublicKey.default.toString());
+  });
+});
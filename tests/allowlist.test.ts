@@ -0,0 +1,215 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddress, getAccount } from "@solana/spl-token";
+import { createHash } from "crypto";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  setupInitializedProgram,
+  createAndFundUserWithoutTokens,
+} from "./utils/test-helpers";
+
+describe("Allowlist Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda } = deriveProgramPDAs(program);
+
+  const deriveEntryPda = (user: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("allowlist"), user.toBuffer()], program.programId)[0];
+
+  const entryAccounts = (users: PublicKey[]) =>
+    users.map((user) => ({ pubkey: deriveEntryPda(user), isWritable: true, isSigner: false }));
+
+  const batchAdd = (users: PublicKey[], signer?: Keypair) => {
+    const builder = program.methods
+      .batchAddToAllowlist(users)
+      .accounts({ config: configPda, admin: (signer ?? admin).publicKey })
+      .remainingAccounts(entryAccounts(users));
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  const cohort = Array.from({ length: 4 }, () => Keypair.generate().publicKey);
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+  });
+
+  it("should create an entry for every user in the batch", async () => {
+    await batchAdd(cohort.slice(0, 2)).rpc();
+
+    for (const user of cohort.slice(0, 2)) {
+      const entry = await program.account.allowlistEntry.fetch(deriveEntryPda(user));
+      expect(entry.user.toString()).to.equal(user.toString());
+      expect(entry.addedAt.toNumber()).to.be.greaterThan(0);
+    }
+  });
+
+  it("should skip users already on the allowlist", async () => {
+    const existing = await program.account.allowlistEntry.fetch(deriveEntryPda(cohort[0]));
+
+    const simulation = await batchAdd(cohort).simulate();
+    const event = simulation.events.find((e) => e.name === "batchAllowlistEvent");
+    expect(event?.data.requested.toNumber()).to.equal(4);
+    expect(event?.data.added.toNumber()).to.equal(2);
+
+    await batchAdd(cohort).rpc();
+
+    for (const user of cohort) {
+      await program.account.allowlistEntry.fetch(deriveEntryPda(user));
+    }
+
+    // Pre-existing entry untouched
+    const after = await program.account.allowlistEntry.fetch(deriveEntryPda(cohort[0]));
+    expect(after.addedAt.toNumber()).to.equal(existing.addedAt.toNumber());
+  });
+
+  it("should reject a batch whose entry accounts don't match the users", async () => {
+    try {
+      await program.methods
+        .batchAddToAllowlist([Keypair.generate().publicKey])
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .remainingAccounts([])
+        .rpc();
+
+      expect.fail("Should have thrown InvalidAllowlistBatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6031); // InvalidAllowlistBatch
+    }
+  });
+
+  it("should reject an oversized batch", async () => {
+    const users = Array.from({ length: 11 }, () => Keypair.generate().publicKey);
+    try {
+      await batchAdd(users).rpc();
+      expect.fail("Should have thrown InvalidAllowlistBatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6031); // InvalidAllowlistBatch
+    }
+  });
+
+  it("should reject an entry account that isn't the user's PDA", async () => {
+    const user = Keypair.generate().publicKey;
+    try {
+      await program.methods
+        .batchAddToAllowlist([user])
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .remainingAccounts(entryAccounts([Keypair.generate().publicKey]))
+        .rpc();
+
+      expect.fail("Should have thrown AllowlistAccountMismatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6032); // AllowlistAccountMismatch
+    }
+  });
+
+  it("should reject a batch from a non-admin", async () => {
+    const outsider = await createAndFundUserWithoutTokens(provider);
+    try {
+      await batchAdd([Keypair.generate().publicKey], outsider).rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  describe("Faucet Merkle Gate", () => {
+    // Mirrors claim_tokens.rs: prefixed SHA-256 leaves, sorted-pair inner nodes
+    const sha256 = (...parts: Buffer[]) => createHash("sha256").update(Buffer.concat(parts)).digest();
+    const leaf = (user: PublicKey) => sha256(Buffer.from([0]), user.toBuffer());
+    const node = (a: Buffer, b: Buffer) =>
+      Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+    const OPEN_ROOT = Array(32).fill(0);
+
+    let member: Keypair;
+    let otherMember: Keypair;
+    let outsider: Keypair;
+    let root: Buffer;
+
+    const claim = (user: Keypair, proof: Buffer[]) =>
+      program.methods
+        .claimTokens(new anchor.BN(0), proof.map((hash) => Array.from(hash)))
+        .accounts({ mint: mintPda, user: user.publicKey })
+        .signers([user])
+        .rpc();
+
+    before(async () => {
+      member = await createAndFundUserWithoutTokens(provider);
+      otherMember = await createAndFundUserWithoutTokens(provider);
+      outsider = await createAndFundUserWithoutTokens(provider);
+      root = node(leaf(member.publicKey), leaf(otherMember.publicKey));
+    });
+
+    after(async () => {
+      // Re-open the faucet for the other suites
+      await program.methods.setAllowlistRoot(OPEN_ROOT).rpc();
+    });
+
+    it("should let anyone claim without a proof while the root is open", async () => {
+      const config = await program.account.tokenConfig.fetch(configPda);
+      expect(config.allowlistRoot).to.deep.equal(OPEN_ROOT);
+
+      const walkIn = await createAndFundUserWithoutTokens(provider);
+      await claim(walkIn, []);
+
+      const balance = await getAccount(
+        provider.connection,
+        await getAssociatedTokenAddress(mintPda, walkIn.publicKey)
+      );
+      expect(Number(balance.amount)).to.be.greaterThan(0);
+    });
+
+    it("should reject an allowlist root update from a non-admin", async () => {
+      try {
+        await program.methods
+          .setAllowlistRoot(Array.from(root))
+          .accounts({ admin: outsider.publicKey })
+          .signers([outsider])
+          .rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should emit an AllowlistRootUpdatedEvent", async () => {
+      const { events } = await program.methods.setAllowlistRoot(Array.from(root)).simulate();
+      const updated = events.find((e) => e.name === "allowlistRootUpdatedEvent")?.data as any;
+      expect(updated.previousRoot).to.deep.equal(OPEN_ROOT);
+      expect(updated.allowlistRoot).to.deep.equal(Array.from(root));
+
+      const change = events.find((e) => e.name === "configPubkeyChangedEvent")?.data as any;
+      expect(change.field).to.equal(45); // CONFIG_FIELD_ALLOWLIST_ROOT
+      expect(Array.from(change.newValue.toBytes())).to.deep.equal(Array.from(root));
+
+      await program.methods.setAllowlistRoot(Array.from(root)).rpc();
+    });
+
+    it("should let a member claim with a valid proof", async () => {
+      await claim(member, [leaf(otherMember.publicKey)]);
+
+      const balance = await getAccount(
+        provider.connection,
+        await getAssociatedTokenAddress(mintPda, member.publicKey)
+      );
+      expect(Number(balance.amount)).to.be.greaterThan(0);
+    });
+
+    it("should reject an outsider presenting a member's proof", async () => {
+      try {
+        await claim(outsider, [leaf(otherMember.publicKey)]);
+        expect.fail("Should have thrown NotAllowlisted error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6087); // NotAllowlisted
+      }
+    });
+
+    it("should reject a member claiming without a proof", async () => {
+      try {
+        await claim(otherMember, []);
+        expect.fail("Should have thrown NotAllowlisted error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6087); // NotAllowlisted
+      }
+    });
+  });
+});
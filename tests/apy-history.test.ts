@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  setupInitializedProgram,
+  createAndFundUser,
+  createAndFundUserWithoutTokens,
+  SECONDS_PER_DAY,
+} from "./utils/test-helpers";
+
+const tier = (days: number, numerator: number, denominator = 100) => ({
+  minDuration: new anchor.BN(days * SECONDS_PER_DAY),
+  apyNumerator: new anchor.BN(numerator),
+  apyDenominator: new anchor.BN(denominator),
+});
+const DEFAULT_TIERS = [tier(7, 5), tier(30, 10), tier(90, 20)];
+
+// Ring-buffer ordering and wrap-around are unit-tested in `state.rs`
+describe("APY History Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda } = deriveProgramPDAs(program);
+  const [apyHistoryPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("apy-history")],
+    program.programId
+  );
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+  });
+
+  it("should reject initialization from a non-admin", async () => {
+    const outsider = await createAndFundUserWithoutTokens(provider);
+    try {
+      await program.methods
+        .initApyHistory()
+        .accounts({ config: configPda, admin: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should seed the log with the schedule currently in effect", async () => {
+    await program.methods
+      .initApyHistory()
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .rpc();
+
+    const history = await program.account.apyHistory.fetch(apyHistoryPda);
+    expect(history.count).to.equal(1);
+    expect(history.head).to.equal(1);
+    expect(history.totalRecorded.toNumber()).to.equal(1);
+
+    const [entry] = history.entries;
+    expect(entry.timestamp.toNumber()).to.be.greaterThan(0);
+    expect(entry.tiers.map((t: any) => t.minDuration.toNumber())).to.deep.equal([
+      7 * SECONDS_PER_DAY,
+      30 * SECONDS_PER_DAY,
+      90 * SECONDS_PER_DAY,
+    ]);
+    expect(entry.tiers.map((t: any) => t.apyNumerator.toNumber())).to.deep.equal([5, 10, 20]);
+  });
+
+  it("should not initialize the log twice", async () => {
+    try {
+      await program.methods
+        .initApyHistory()
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+      expect.fail("Should have failed to re-initialize");
+    } catch (error: any) {
+      expect(error).to.exist;
+    }
+  });
+
+  describe("set_apy_tiers", () => {
+    // Other suites share the validator, so leave the compiled-in schedule behind
+    after(async () => {
+      await program.methods
+        .setApyTiers(DEFAULT_TIERS)
+        .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+        .rpc();
+    });
+
+    it("should reject a schedule change from a non-admin", async () => {
+      const outsider = await createAndFundUserWithoutTokens(provider);
+      try {
+        await program.methods
+          .setApyTiers([tier(7, 6), tier(30, 12), tier(90, 25)])
+          .accounts({ admin: outsider.publicKey, apyHistory: apyHistoryPda })
+          .signers([outsider])
+          .rpc();
+
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should reject thresholds that don't ascend", async () => {
+      try {
+        await program.methods
+          .setApyTiers([tier(7, 5), tier(90, 10), tier(30, 20)])
+          .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidApyTiers error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6067); // InvalidApyTiers
+      }
+    });
+
+    it("should reject a rate above 100%", async () => {
+      try {
+        await program.methods
+          .setApyTiers([tier(7, 5), tier(30, 10), tier(90, 101)])
+          .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidApyTiers error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6067); // InvalidApyTiers
+      }
+    });
+
+    it("should report every tier field as a config change", async () => {
+      const { apyTiers: current } = await program.account.tokenConfig.fetch(configPda);
+      const tiers = [tier(14, 6), tier(60, 12), tier(180, 25)];
+
+      const { events } = await program.methods
+        .setApyTiers(tiers)
+        .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+        .simulate();
+
+      // CONFIG_FIELD_APY_TIER_{MIN_DURATION,NUMERATOR,DENOMINATOR}, three codes per tier
+      const changes = events.filter((e) => e.name === "configChangedEvent").map((e) => e.data as any);
+      expect(changes.map((c) => c.field)).to.deep.equal([34, 35, 36, 37, 38, 39, 40, 41, 42]);
+      current.forEach((old: any, i: number) => {
+        expect(changes[i * 3].oldValue.toString()).to.equal(old.minDuration.toString());
+        expect(changes[i * 3].newValue.toString()).to.equal(tiers[i].minDuration.toString());
+        expect(changes[i * 3 + 1].oldValue.toString()).to.equal(old.apyNumerator.toString());
+        expect(changes[i * 3 + 1].newValue.toString()).to.equal(tiers[i].apyNumerator.toString());
+      });
+    });
+
+    it("should append the new schedule to the log", async () => {
+      const before = await program.account.apyHistory.fetch(apyHistoryPda);
+
+      await program.methods
+        .setApyTiers([tier(14, 6), tier(60, 12), tier(180, 25)])
+        .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+        .rpc();
+
+      const history = await program.account.apyHistory.fetch(apyHistoryPda);
+      expect(history.count).to.equal(before.count + 1);
+      expect(history.totalRecorded.toNumber()).to.equal(before.totalRecorded.toNumber() + 1);
+
+      const latest = history.entries[before.head];
+      expect(latest.tiers.map((t: any) => t.minDuration.toNumber())).to.deep.equal([
+        14 * SECONDS_PER_DAY,
+        60 * SECONDS_PER_DAY,
+        180 * SECONDS_PER_DAY,
+      ]);
+      expect(latest.tiers.map((t: any) => t.apyNumerator.toNumber())).to.deep.equal([6, 12, 25]);
+    });
+
+    it("should update the schedule without logging when no log is passed", async () => {
+      const before = await program.account.apyHistory.fetch(apyHistoryPda);
+
+      await program.methods
+        .setApyTiers([tier(14, 6), tier(60, 12), tier(180, 25)])
+        .accounts({ admin: admin.publicKey, apyHistory: null })
+        .rpc();
+
+      const config = await program.account.tokenConfig.fetch(configPda);
+      expect(config.apyTiers.map((t: any) => t.apyNumerator.toNumber())).to.deep.equal([6, 12, 25]);
+      const history = await program.account.apyHistory.fetch(apyHistoryPda);
+      expect(history.totalRecorded.toNumber()).to.equal(before.totalRecorded.toNumber());
+    });
+
+    it("should quote new stakes at the latest schedule", async () => {
+      const staker = await createAndFundUser(provider, program, mintPda);
+      const userTokenAccount = getAssociatedTokenAddressSync(mintPda, staker.publicKey);
+
+      // 90 days was tier 3 (20%) by default; under the new schedule it's tier 2 (12%)
+      const simulation = await program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(90 * SECONDS_PER_DAY))
+        .accounts({
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          userTokenAccount,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .simulate();
+
+      const event = simulation.events.find((e) => e.name === "stakeEvent")?.data as any;
+      expect(event.apyNumerator.toNumber()).to.equal(12);
+      expect(event.apyDenominator.toNumber()).to.equal(100);
+    });
+
+    it("should keep a position's rate when the schedule changes again", async () => {
+      const staker = await createAndFundUser(provider, program, mintPda);
+      const stakeAccountPda = deriveStakePda(staker.publicKey, 0, program);
+
+      await program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(90 * SECONDS_PER_DAY))
+        .accounts({
+          stakeAccount: stakeAccountPda,
+          userTokenAccount: getAssociatedTokenAddressSync(mintPda, staker.publicKey),
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+
+      let position = await program.account.stakeAccount.fetch(stakeAccountPda);
+      expect(position.apyNumerator.toNumber()).to.equal(12);
+      expect(position.apyDenominator.toNumber()).to.equal(100);
+
+      // Back to the default schedule - 90 days would be 20% for a new stake now
+      await program.methods
+        .setApyTiers(DEFAULT_TIERS)
+        .accounts({ admin: admin.publicKey, apyHistory: apyHistoryPda })
+        .rpc();
+
+      position = await program.account.stakeAccount.fetch(stakeAccountPda);
+      expect(position.apyNumerator.toNumber()).to.equal(12);
+    });
+  });
+});
@@ -0,0 +1,99 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  setupInitializedProgram,
+  SECONDS_PER_DAY,
+} from "./utils/test-helpers";
+
+// Field codes mirror CONFIG_FIELD_* in constants.rs
+const CONFIG_FIELD_CLAIM_TIER_MIN_STAKED = [0, 2, 4];
+const CONFIG_FIELD_CLAIM_TIER_MULTIPLIER_BPS = [1, 3, 5];
+const CONFIG_FIELD_EPOCH_START = 11;
+const CONFIG_FIELD_EPOCH_LENGTH = 12;
+const CONFIG_FIELD_MAX_UNSTAKE_PER_TX = 13;
+const CONFIG_FIELD_REWARD_ACCRUAL_DELAY = 16;
+
+describe("Config Change Events Tests", () => {
+  const { program, admin } = getTestContext();
+  const { configPda } = deriveProgramPDAs(program);
+
+  // Setters are simulated, so no config actually changes
+  const configChanges = (events: any[]) =>
+    events.filter((e) => e.name === "configChangedEvent").map((e) => e.data as any);
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+  });
+
+  it("should report the old and new unstake cap", async () => {
+    const config = await program.account.tokenConfig.fetch(configPda);
+
+    const { events } = await program.methods
+      .setUnstakeCap(new anchor.BN(5_000_000_000_000))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .simulate();
+
+    const [change] = configChanges(events);
+    expect(change.field).to.equal(CONFIG_FIELD_MAX_UNSTAKE_PER_TX);
+    expect(change.oldValue.toString()).to.equal(config.maxUnstakePerTx.toString());
+    expect(change.newValue.toNumber()).to.equal(5_000_000_000_000);
+    expect(change.admin.toString()).to.equal(admin.publicKey.toString());
+  });
+
+  it("should encode i64 settings as their value", async () => {
+    const config = await program.account.tokenConfig.fetch(configPda);
+
+    const { events } = await program.methods
+      .setRewardAccrualDelay(new anchor.BN(SECONDS_PER_DAY))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .simulate();
+
+    const [change] = configChanges(events);
+    expect(change.field).to.equal(CONFIG_FIELD_REWARD_ACCRUAL_DELAY);
+    expect(change.oldValue.toNumber()).to.equal(config.rewardAccrualDelay.toNumber());
+    expect(change.newValue.toNumber()).to.equal(SECONDS_PER_DAY);
+  });
+
+  it("should emit one event per field for multi-field setters", async () => {
+    const config = await program.account.tokenConfig.fetch(configPda);
+
+    const { events } = await program.methods
+      .setEpochConfig(new anchor.BN(1_000), new anchor.BN(30 * SECONDS_PER_DAY))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .simulate();
+
+    const changes = configChanges(events);
+    expect(changes.map((c) => c.field)).to.deep.equal([CONFIG_FIELD_EPOCH_START, CONFIG_FIELD_EPOCH_LENGTH]);
+    expect(changes[0].oldValue.toNumber()).to.equal(config.epochStart.toNumber());
+    expect(changes[0].newValue.toNumber()).to.equal(1_000);
+    expect(changes[1].oldValue.toNumber()).to.equal(config.epochLength.toNumber());
+    expect(changes[1].newValue.toNumber()).to.equal(30 * SECONDS_PER_DAY);
+  });
+
+  it("should report every claim tier field", async () => {
+    const config = await program.account.tokenConfig.fetch(configPda);
+    const tiers = config.claimTiers.map((tier: any, i: number) => ({
+      minStaked: tier.minStaked.add(new anchor.BN(i + 1)),
+      multiplierBps: tier.multiplierBps,
+    }));
+
+    const { events } = await program.methods
+      .updateClaimTiers(tiers)
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .simulate();
+
+    const changes = configChanges(events);
+    expect(changes).to.have.length(6);
+    config.claimTiers.forEach((tier: any, i: number) => {
+      const minStaked = changes.find((c) => c.field === CONFIG_FIELD_CLAIM_TIER_MIN_STAKED[i]);
+      expect(minStaked.oldValue.toString()).to.equal(tier.minStaked.toString());
+      expect(minStaked.newValue.toString()).to.equal(tiers[i].minStaked.toString());
+
+      const multiplier = changes.find((c) => c.field === CONFIG_FIELD_CLAIM_TIER_MULTIPLIER_BPS[i]);
+      expect(multiplier.oldValue.toString()).to.equal(tier.multiplierBps.toString());
+      expect(multiplier.newValue.toString()).to.equal(tier.multiplierBps.toString());
+    });
+  });
+});
@@ -0,0 +1,448 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddress } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  setupInitializedProgram,
+  createAndFundUser,
+  SECONDS_PER_DAY,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+const SECONDS_PER_YEAR = 31_536_000n;
+
+// Mirrors `compute_rewards` in the unstake instruction
+function expectedRewards(amount: bigint, apyPercent: bigint, lockDuration: bigint): bigint {
+  const amountWithApy = (amount * apyPercent) / 100n;
+  return (amountWithApy * lockDuration) / SECONDS_PER_YEAR;
+}
+
+describe("Diagnostics Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+  });
+
+  describe("Parameter Preview", () => {
+    it("should emit previewed rewards matching the reward formula", async () => {
+      const { events } = await program.methods
+        .emitParameterPreview()
+        .accounts({
+          config: configPda,
+        })
+        .simulate();
+
+      const preview = events.find((e) => e.name === "parameterPreviewEvent")?.data as any;
+      expect(preview).to.exist;
+
+      const sampleAmount = BigInt(preview.sampleAmount.toString());
+      expect(sampleAmount).to.equal(1_000_000_000_000n); // 1,000 DEVR
+
+      expect(preview.tier1Duration.toNumber()).to.equal(7 * SECONDS_PER_DAY);
+      expect(preview.tier2Duration.toNumber()).to.equal(30 * SECONDS_PER_DAY);
+      expect(preview.tier3Duration.toNumber()).to.equal(90 * SECONDS_PER_DAY);
+
+      expect(BigInt(preview.tier1Rewards.toString())).to.equal(
+        expectedRewards(sampleAmount, 5n, BigInt(7 * SECONDS_PER_DAY))
+      );
+      expect(BigInt(preview.tier2Rewards.toString())).to.equal(
+        expectedRewards(sampleAmount, 10n, BigInt(30 * SECONDS_PER_DAY))
+      );
+      expect(BigInt(preview.tier3Rewards.toString())).to.equal(
+        expectedRewards(sampleAmount, 20n, BigInt(90 * SECONDS_PER_DAY))
+      );
+    });
+
+    it("should not modify config when previewing", async () => {
+      const configBefore = await program.account.tokenConfig.fetch(configPda);
+
+      await program.methods
+        .emitParameterPreview()
+        .accounts({
+          config: configPda,
+        })
+        .rpc();
+
+      const configAfter = await program.account.tokenConfig.fetch(configPda);
+      expect(configAfter.dailyClaimAmount.toString()).to.equal(configBefore.dailyClaimAmount.toString());
+    });
+  });
+
+  describe("APY Curve", () => {
+    const SAMPLE_DAYS = [7, 30, 90, 180, 365];
+    // Mirrors `get_apy_for_duration`
+    const apyPercentFor = (days: number) => (days >= 90 ? 20n : days >= 30 ? 10n : 5n);
+
+    it("should emit the tier APY and reward at each sample duration", async () => {
+      const amount = 2_500_000_000_000n; // 2,500 DEVR
+      const { events } = await program.methods
+        .apyCurve(new anchor.BN(amount.toString()))
+        .accounts({ config: configPda })
+        .simulate();
+
+      const curve = events.find((e) => e.name === "apyCurveEvent")?.data as any;
+      expect(curve).to.exist;
+      expect(BigInt(curve.amount.toString())).to.equal(amount);
+      expect(curve.points.length).to.equal(SAMPLE_DAYS.length);
+
+      curve.points.forEach((point: any, i: number) => {
+        const lockDuration = BigInt(SAMPLE_DAYS[i] * SECONDS_PER_DAY);
+        const apyPercent = apyPercentFor(SAMPLE_DAYS[i]);
+        expect(BigInt(point.lockDuration.toString())).to.equal(lockDuration);
+        expect(point.apyNumerator.toNumber()).to.equal(Number(apyPercent));
+        expect(point.apyDenominator.toNumber()).to.equal(100);
+        expect(BigInt(point.projectedRewards.toString())).to.equal(
+          expectedRewards(amount, apyPercent, lockDuration)
+        );
+      });
+    });
+
+    it("should reject an amount below the minimum stake", async () => {
+      try {
+        await program.methods
+          .apyCurve(new anchor.BN(1))
+          .accounts({ config: configPda })
+          .rpc();
+        expect.fail("Should have thrown AmountTooSmall error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6001); // AmountTooSmall
+      }
+    });
+  });
+
+  describe("Time To Maturity", () => {
+    let staker: Keypair;
+
+    before(async () => {
+      staker = await createAndFundUser(provider, program, mintPda, 2);
+
+      await program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(staker.publicKey, program),
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          userTokenAccount: await getAssociatedTokenAddress(mintPda, staker.publicKey),
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+    });
+
+    it("should report the full lock remaining for a freshly-staked position", async () => {
+      const { events } = await program.methods
+        .timeToMaturity(new anchor.BN(0))
+        .accounts({
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          user: staker.publicKey,
+        })
+        .simulate();
+
+      const maturity = events.find((e) => e.name === "maturityEvent")?.data as any;
+      expect(maturity).to.exist;
+
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        deriveStakePda(staker.publicKey, 0, program)
+      );
+      const maturityTimestamp = stakeAccount.stakedAt.toNumber() + stakeAccount.lockDuration.toNumber();
+      const now = Math.floor(Date.now() / 1000);
+
+      expect(maturity.isMatured).to.be.false;
+      expect(maturity.secondsRemaining.toNumber()).to.be.greaterThan(0);
+      expect(maturity.secondsRemaining.toNumber()).to.be.at.most(MIN_LOCK_DURATION);
+      // Matches staked_at + lock_duration - now (allowing for validator clock drift)
+      expect(Math.abs(maturity.secondsRemaining.toNumber() - (maturityTimestamp - now))).to.be.lessThan(60);
+    });
+
+    // Matured positions (clamped to zero once the unstake gate opens) are covered
+    // by the `get_seconds_to_unlock` unit tests in constants.rs, since a
+    // localnet validator cannot fast-forward a 7-day lock.
+
+    it("should return the unlock countdown via get_unlock_info", async () => {
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        deriveStakePda(staker.publicKey, 0, program)
+      );
+
+      const info = await program.methods
+        .getUnlockInfo(new anchor.BN(0))
+        .accounts({
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          user: staker.publicKey,
+        })
+        .view();
+
+      const now = Math.floor(Date.now() / 1000);
+      expect(info.unlockTimestamp.toNumber()).to.equal(stakeAccount.maturityTimestamp.toNumber());
+      expect(info.isUnlocked).to.be.false;
+      expect(info.secondsRemaining.toNumber()).to.be.greaterThan(0);
+      expect(info.secondsRemaining.toNumber()).to.be.at.most(MIN_LOCK_DURATION);
+      // Counts down to the unstake gate, UNSTAKE_SKEW_TOLERANCE (30s) before unlockTimestamp,
+      // allowing for validator clock drift
+      expect(Math.abs(info.secondsRemaining.toNumber() - (info.unlockTimestamp.toNumber() - 30 - now))).to.be.lessThan(60);
+      // Past the lock (seconds_remaining == 0, is_unlocked) is unit-tested in get_unlock_info.rs
+    });
+
+    it("should quote pending rewards without touching the position", async () => {
+      const stakePda = deriveStakePda(staker.publicKey, 0, program);
+      const before = await program.account.stakeAccount.fetch(stakePda);
+
+      const pending: anchor.BN = await program.methods
+        .getPendingRewards(new anchor.BN(0))
+        .accounts({
+          stakeAccount: stakePda,
+          user: staker.publicKey,
+        })
+        .view();
+
+      // The committed basis quotes the whole lock at the locked-in rate (plus any bonus)
+      const secondsPerYear = 365 * 24 * 60 * 60;
+      const baseRewards = before.stakedAmount
+        .mul(before.apyNumerator)
+        .div(before.apyDenominator)
+        .mul(before.lockDuration)
+        .div(new anchor.BN(secondsPerYear));
+      expect(pending.gte(baseRewards)).to.be.true;
+      expect(pending.toNumber()).to.be.greaterThan(0);
+
+      const after = await program.account.stakeAccount.fetch(stakePda);
+      expect(after.stakedAmount.toString()).to.equal(before.stakedAmount.toString());
+      expect(after.compoundedRewards.toString()).to.equal(before.compoundedRewards.toString());
+    });
+  });
+
+  describe("Stake Validation", () => {
+    const STAKE_CHECK_MIN_AMOUNT = 1 << 1;
+    const STAKE_CHECK_MIN_DURATION = 1 << 3;
+    const STAKE_CHECK_MAX_DURATION = 1 << 4;
+    const STAKE_CHECK_BALANCE = 1 << 5;
+    const STAKE_CHECK_REWARD_LIQUIDITY = 1 << 6;
+    const BLOCKING_CHECKS = STAKE_CHECK_REWARD_LIQUIDITY - 1;
+
+    let userTokenAccount: anchor.web3.PublicKey;
+
+    before(async () => {
+      const user = await createAndFundUser(provider, program, mintPda, 2);
+      userTokenAccount = await getAssociatedTokenAddress(mintPda, user.publicKey);
+    });
+
+    async function validate(amount: anchor.BN, lockDuration: number) {
+      const { events } = await program.methods
+        .validateStake(amount, new anchor.BN(lockDuration))
+        .accounts({
+          config: configPda,
+          globalStats: globalStatsPda,
+          vault: vaultPda,
+          userTokenAccount,
+        })
+        .simulate();
+
+      const validation = events.find((e) => e.name === "stakeValidationEvent")?.data as any;
+      expect(validation).to.exist;
+      return validation;
+    }
+
+    it("should pass every blocking check for a valid stake", async () => {
+      const validation = await validate(new anchor.BN(10_000_000_000), MIN_LOCK_DURATION);
+
+      expect(validation.isValid).to.be.true;
+      expect(validation.failedChecks & BLOCKING_CHECKS).to.equal(0);
+      expect(validation.projectedRewards.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("should flag an amount and duration that are both too small", async () => {
+      const validation = await validate(new anchor.BN(1), SECONDS_PER_DAY);
+
+      expect(validation.isValid).to.be.false;
+      expect(validation.failedChecks & BLOCKING_CHECKS).to.equal(
+        STAKE_CHECK_MIN_AMOUNT | STAKE_CHECK_MIN_DURATION
+      );
+    });
+
+    it("should flag a stake above the balance with a lock above the maximum", async () => {
+      // 1,000 DEVR against a 100 DEVR balance, locked for 11 years
+      const validation = await validate(new anchor.BN(1_000_000_000_000), 11 * 365 * SECONDS_PER_DAY);
+
+      expect(validation.isValid).to.be.false;
+      expect(validation.failedChecks & BLOCKING_CHECKS).to.equal(
+        STAKE_CHECK_BALANCE | STAKE_CHECK_MAX_DURATION
+      );
+      expect(validation.projectedRewards.toNumber()).to.equal(0);
+    });
+
+    it("should not change any state", async () => {
+      const before = await program.account.globalStats.fetch(globalStatsPda);
+      await validate(new anchor.BN(10_000_000_000), MIN_LOCK_DURATION);
+      const after = await program.account.globalStats.fetch(globalStatsPda);
+
+      expect(after.totalStaked.toString()).to.equal(before.totalStaked.toString());
+      expect(after.totalStakes.toString()).to.equal(before.totalStakes.toString());
+    });
+  });
+
+  describe("Voting Power", () => {
+    let voter: Keypair;
+    const stakePdas: anchor.web3.PublicKey[] = [];
+
+    const votingPower = (stakeAccounts: anchor.web3.PublicKey[], user = voter.publicKey) =>
+      program.methods
+        .getVotingPower()
+        .accounts({ user })
+        .remainingAccounts(
+          stakeAccounts.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .view();
+
+    before(async () => {
+      voter = await createAndFundUser(provider, program, mintPda, 2);
+
+      // 10 DEVR at 1x (7 days) and 10 DEVR at 1.5x (30 days)
+      for (const [index, lockDuration] of [MIN_LOCK_DURATION, 30 * SECONDS_PER_DAY].entries()) {
+        const stakePda = deriveStakePda(voter.publicKey, index, program);
+        await program.methods
+          .stake(new anchor.BN(10_000_000_000), new anchor.BN(lockDuration))
+          .accounts({
+            config: configPda,
+            counter: deriveCounterPda(voter.publicKey, program),
+            stakeAccount: stakePda,
+            userTokenAccount: await getAssociatedTokenAddress(mintPda, voter.publicKey),
+            vault: vaultPda,
+            globalStats: globalStatsPda,
+            user: voter.publicKey,
+          })
+          .signers([voter])
+          .rpc();
+        stakePdas.push(stakePda);
+      }
+    });
+
+    it("should sum duration-weighted principal across positions", async () => {
+      const power: anchor.BN = await votingPower(stakePdas);
+      expect(power.toNumber()).to.equal(10_000_000_000 + 15_000_000_000);
+    });
+
+    it("should report zero with no positions", async () => {
+      const power: anchor.BN = await votingPower([]);
+      expect(power.toNumber()).to.equal(0);
+    });
+
+    it("should reject a position passed twice", async () => {
+      try {
+        await votingPower([stakePdas[0], stakePdas[0]]);
+        expect.fail("Should have thrown DuplicateStakeAccount error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("DuplicateStakeAccount");
+      }
+    });
+
+    it("should reject another user's positions", async () => {
+      try {
+        await votingPower(stakePdas, admin.publicKey);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+    });
+  });
+
+  describe("Config Dump", () => {
+    let original: any;
+
+    const dump = async () => {
+      const { events } = await program.methods
+        .dumpConfig()
+        .accounts({
+          config: configPda,
+          vault: vaultPda,
+        })
+        .simulate();
+
+      const configDump = events.find((e) => e.name === "configDumpEvent")?.data as any;
+      expect(configDump).to.exist;
+      return configDump;
+    };
+
+    before(async () => {
+      original = await program.account.tokenConfig.fetch(configPda);
+    });
+
+    after(async () => {
+      await program.methods
+        .setUnstakeCap(original.maxUnstakePerTx)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .setRewardAccrualDelay(original.rewardAccrualDelay)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .setDelegationCooldown(original.delegationCooldown)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+    });
+
+    it("should reflect the stored config after several setters run", async () => {
+      await program.methods
+        .setUnstakeCap(new anchor.BN(5_000_000_000_000))
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .setRewardAccrualDelay(new anchor.BN(SECONDS_PER_DAY))
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+      await program.methods
+        .setDelegationCooldown(new anchor.BN(60))
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+      const config = await program.account.tokenConfig.fetch(configPda);
+      const configDump = await dump();
+
+      expect(configDump.maxUnstakePerTx.toNumber()).to.equal(5_000_000_000_000);
+      expect(configDump.rewardAccrualDelay.toNumber()).to.equal(SECONDS_PER_DAY);
+      expect(configDump.delegationCooldown.toNumber()).to.equal(60);
+
+      // Every stored field comes through unchanged
+      for (const [field, value] of Object.entries(config)) {
+        expect(JSON.stringify(configDump[field]), field).to.equal(JSON.stringify(value));
+      }
+    });
+
+    it("should report the current vault balance", async () => {
+      const configDump = await dump();
+      const vault = await getAccount(provider.connection, vaultPda);
+
+      expect(configDump.vaultBalance.toString()).to.equal(vault.amount.toString());
+    });
+  });
+
+  describe("Stats Snapshot", () => {
+    it("should snapshot global stats and the live vault balance", async () => {
+      const { events } = await program.methods
+        .snapshotStats()
+        .accounts({
+          globalStats: globalStatsPda,
+          vault: vaultPda,
+        })
+        .simulate();
+
+      const snapshot = events.find((e) => e.name === "statsSnapshotEvent")?.data as any;
+      expect(snapshot).to.exist;
+
+      const stats = await program.account.globalStats.fetch(globalStatsPda);
+      const vault = await getAccount(provider.connection, vaultPda);
+      expect(snapshot.vaultBalance.toString()).to.equal(vault.amount.toString());
+      expect(snapshot.totalStaked.toString()).to.equal(stats.totalStaked.toString());
+      expect(snapshot.totalStakes.toString()).to.equal(stats.totalStakes.toString());
+      expect(snapshot.totalRewardsPaid.toString()).to.equal(stats.totalRewardsPaid.toString());
+      expect(snapshot.timestamp.toNumber()).to.be.greaterThan(0);
+    });
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  setupInitializedProgram,
+  createAndFundUser,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+// Bit assignments mirror constants.rs
+const FEATURE_LOYALTY_BONUS = 1 << 0;
+const FEATURE_CIRCUIT_BREAKER = 1 << 1;
+const FEATURE_STAKE_TIERED_CLAIMS = 1 << 2;
+const FEATURE_INTERPOLATED_APY = 1 << 3;
+const ALL_FEATURES =
+  FEATURE_LOYALTY_BONUS | FEATURE_CIRCUIT_BREAKER | FEATURE_STAKE_TIERED_CLAIMS | FEATURE_INTERPOLATED_APY;
+const DEFAULT_FEATURE_FLAGS = ALL_FEATURES & ~FEATURE_INTERPOLATED_APY; // Interpolation is opt-in
+
+describe("Feature Flags Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+  const [featureFlagsPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("feature-flags")],
+    program.programId
+  );
+
+  const setFlags = (flags: number) =>
+    program.methods
+      .setFeatureFlags(new anchor.BN(flags))
+      .accounts({ config: configPda, admin: admin.publicKey })
+      .rpc();
+
+  // A user holding 200 DEVR qualifies for the loyalty bonus after staking 10
+  const createHolder = async () => {
+    const holder = await createAndFundUser(provider, program, mintPda, 5);
+    const donor = await createAndFundUser(provider, program, mintPda, 5);
+    const holderTokenAccount = await getAssociatedTokenAddress(mintPda, holder.publicKey);
+
+    await program.methods
+      .transfer(new anchor.BN(100_000_000_000), new anchor.BN(0))
+      .accounts({
+        fromTokenAccount: await getAssociatedTokenAddress(mintPda, donor.publicKey),
+        toTokenAccount: holderTokenAccount,
+        authority: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc();
+
+    return { holder, holderTokenAccount };
+  };
+
+  const stakeFor = async (user: Keypair, tokenAccount: PublicKey, lockDuration = MIN_LOCK_DURATION) => {
+    const stakePda = deriveStakePda(user.publicKey, 0, program);
+    await program.methods
+      .stake(new anchor.BN(10_000_000_000), new anchor.BN(lockDuration))
+      .accounts({
+        config: configPda,
+        counter: deriveCounterPda(user.publicKey, program),
+        stakeAccount: stakePda,
+        userTokenAccount: tokenAccount,
+        vault: vaultPda,
+        globalStats: globalStatsPda,
+        user: user.publicKey,
+      })
+      .signers([user])
+      .rpc();
+    return program.account.stakeAccount.fetch(stakePda);
+  };
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+  });
+
+  after(async () => {
+    await setFlags(DEFAULT_FEATURE_FLAGS);
+  });
+
+  it("should let the admin set the flags", async () => {
+    await setFlags(DEFAULT_FEATURE_FLAGS & ~FEATURE_LOYALTY_BONUS);
+
+    const featureFlags = await program.account.featureFlags.fetch(featureFlagsPda);
+    expect(featureFlags.flags.toNumber()).to.equal(DEFAULT_FEATURE_FLAGS & ~FEATURE_LOYALTY_BONUS);
+  });
+
+  it("should skip the loyalty bonus while its flag is off", async () => {
+    const { holder, holderTokenAccount } = await createHolder();
+    const stakeAccount = await stakeFor(holder, holderTokenAccount);
+    expect(stakeAccount.loyaltyBonusBps.toNumber()).to.equal(0);
+  });
+
+  it("should apply the loyalty bonus again once the flag is back on", async () => {
+    await setFlags(DEFAULT_FEATURE_FLAGS);
+
+    const { holder, holderTokenAccount } = await createHolder();
+    const stakeAccount = await stakeFor(holder, holderTokenAccount);
+    expect(stakeAccount.loyaltyBonusBps.toNumber()).to.equal(100);
+  });
+
+  it("should lock in the tier step while interpolation is off", async () => {
+    const { holder, holderTokenAccount } = await createHolder();
+    const stakeAccount = await stakeFor(holder, holderTokenAccount, 60 * 86_400);
+    expect(stakeAccount.apyNumerator.toNumber()).to.equal(10); // Tier 2: 10/100
+    expect(stakeAccount.apyDenominator.toNumber()).to.equal(100);
+  });
+
+  it("should lock in the interpolated APY while its flag is on", async () => {
+    await setFlags(ALL_FEATURES);
+
+    // 60 days sits halfway between the 30-day (10%) and 90-day (20%) tiers
+    const { holder, holderTokenAccount } = await createHolder();
+    const stakeAccount = await stakeFor(holder, holderTokenAccount, 60 * 86_400);
+    expect(stakeAccount.apyNumerator.toNumber()).to.equal(150_000_000);
+    expect(stakeAccount.apyDenominator.toNumber()).to.equal(1_000_000_000); // APY_INTERPOLATION_PRECISION
+
+    await setFlags(DEFAULT_FEATURE_FLAGS);
+  });
+
+  it("should reject unknown bits", async () => {
+    try {
+      await setFlags(1 << 20);
+      expect.fail("Should have thrown InvalidFeatureFlags error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6035); // InvalidFeatureFlags
+    }
+  });
+
+  it("should reject updates from a non-admin", async () => {
+    const outsider = await createAndFundUser(provider, program, mintPda, 2);
+    try {
+      await program.methods
+        .setFeatureFlags(new anchor.BN(0))
+        .accounts({ config: configPda, admin: outsider.publicKey })
+        .signers([outsider])
+        .rpc();
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+});
@@ -1,14 +1,18 @@
-import { getMint } from "@solana/spl-token";
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { getAccount, getMint } from "@solana/spl-token";
 import { expect } from "chai";
 import {
   getTestContext,
   deriveProgramPDAs,
   setupInitializedProgram,
+  CURRENT_ACCOUNT_VERSION,
 } from "./utils/test-helpers";
 
 describe("Initialization Tests", () => {
   const { program, provider, admin } = getTestContext();
-  const { configPda, mintAuthorityPda, mintPda } = deriveProgramPDAs(program);
+  const { configPda, mintAuthorityPda, mintPda, vaultPda, vaultAuthorityPda, globalStatsPda } =
+    deriveProgramPDAs(program);
 
   describe("Program Initialization", () => {
     it("should initialize program with correct config", async () => {
@@ -20,6 +24,7 @@ describe("Initialization Tests", () => {
       expect(configAccount.mintAuthority.toString()).to.equal(mintAuthorityPda.toString());
       expect(configAccount.admin.toString()).to.equal(admin.publicKey.toString());
       expect(configAccount.dailyClaimAmount.toString()).to.equal("100000000000");
+      expect(configAccount.rewardDecimals).to.equal(9);
     });
 
     it("should create mint with correct properties", async () => {
@@ -45,4 +50,69 @@ describe("Initialization Tests", () => {
       }
     });
   });
+
+  describe("Account Versioning", () => {
+    it("should create config and global stats at the current version", async () => {
+      await setupInitializedProgram(program, configPda);
+
+      const configAccount = await program.account.tokenConfig.fetch(configPda);
+      const globalStats = await program.account.globalStats.fetch(globalStatsPda);
+
+      expect(configAccount.version).to.equal(CURRENT_ACCOUNT_VERSION);
+      expect(globalStats.version).to.equal(CURRENT_ACCOUNT_VERSION);
+    });
+  });
+
+  describe("Vault Authority Migration", () => {
+    // A successful handover would strand the shared localnet vault for every other
+    // test file, so only the guards are exercised here
+    const setVaultAuthority = (newAuthority: anchor.web3.PublicKey, signer?: Keypair) => {
+      const builder = program.methods.setVaultAuthority(newAuthority).accounts({
+        config: configPda,
+        vault: vaultPda,
+        admin: (signer ?? admin).publicKey,
+      });
+      return signer ? builder.signers([signer]) : builder;
+    };
+
+    before(async () => {
+      await setupInitializedProgram(program, configPda);
+    });
+
+    it("should reject a handover from a non-admin", async () => {
+      const stranger = Keypair.generate();
+      const airdrop = await provider.connection.requestAirdrop(
+        stranger.publicKey,
+        anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction({
+        signature: airdrop,
+        ...(await provider.connection.getLatestBlockhash()),
+      });
+
+      try {
+        await setVaultAuthority(stranger.publicKey, stranger).rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should reject handing the vault to its current authority", async () => {
+      try {
+        await setVaultAuthority(vaultAuthorityPda).rpc();
+        expect.fail("Should have thrown InvalidVaultAuthority error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6048); // InvalidVaultAuthority
+      }
+    });
+
+    it("should leave the vault owned by the program PDA", async () => {
+      const vault = await getAccount(provider.connection, vaultPda);
+      const config = await program.account.tokenConfig.fetch(configPda);
+
+      expect(vault.owner.toString()).to.equal(vaultAuthorityPda.toString());
+      expect(config.vaultAuthority.toString()).to.equal(vaultAuthorityPda.toString());
+    });
+  });
 });
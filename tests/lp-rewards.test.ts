@@ -0,0 +1,220 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddress, getAccount } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  setupInitializedProgram,
+  createAndFundUser,
+} from "./utils/test-helpers";
+
+describe("Liquidity Provider Rewards Tests", () => {
+  const { program, provider } = getTestContext();
+  const { configPda, mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+  const [lpPoolPda] = PublicKey.findProgramAddressSync([Buffer.from("lp-pool")], program.programId);
+
+  const deriveLpPda = (user: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("lp"), user.toBuffer()], program.programId)[0];
+
+  let lpA: Keypair;
+  let lpB: Keypair;
+  let feePayer: Keypair;
+
+  const tokenAccountOf = (user: Keypair) => getAssociatedTokenAddress(mintPda, user.publicKey);
+
+  const fund = async (funder: Keypair, amount: number) =>
+    program.methods
+      .fundRewardVault(new anchor.BN(amount))
+      .accounts({
+        config: configPda,
+        funderTokenAccount: await tokenAccountOf(funder),
+        vault: vaultPda,
+        funder: funder.publicKey,
+      })
+      .signers([funder])
+      .rpc();
+
+  const claim = async (lp: Keypair) =>
+    program.methods
+      .claimLpRewards()
+      .accounts({
+        config: configPda,
+        providerTokenAccount: await tokenAccountOf(lp),
+        provider: lp.publicKey,
+      })
+      .signers([lp])
+      .rpc();
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    lpA = await createAndFundUser(provider, program, mintPda, 2);
+    lpB = await createAndFundUser(provider, program, mintPda, 2);
+    feePayer = await createAndFundUser(provider, program, mintPda, 2);
+  });
+
+  it("should record each funder's contribution", async () => {
+    const poolBefore = await program.account.lpPool.fetchNullable(lpPoolPda);
+    const totalBefore = poolBefore ? poolBefore.totalContributed.toNumber() : 0;
+
+    await fund(lpA, 10_000_000_000); // 10 DEVR
+    await fund(lpB, 30_000_000_000); // 30 DEVR
+
+    const a = await program.account.liquidityProvider.fetch(deriveLpPda(lpA.publicKey));
+    const b = await program.account.liquidityProvider.fetch(deriveLpPda(lpB.publicKey));
+    expect(a.contributed.toNumber()).to.equal(10_000_000_000);
+    expect(b.contributed.toNumber()).to.equal(30_000_000_000);
+
+    const pool = await program.account.lpPool.fetch(lpPoolPda);
+    expect(pool.totalContributed.toNumber()).to.equal(totalBefore + 40_000_000_000);
+  });
+
+  it("should split deposited fees between two LPs in proportion to contribution", async () => {
+    await program.methods
+      .depositLpFees(new anchor.BN(8_000_000_000)) // 8 DEVR
+      .accounts({
+        config: configPda,
+        mint: mintPda,
+        depositorTokenAccount: await tokenAccountOf(feePayer),
+        depositor: feePayer.publicKey,
+      })
+      .signers([feePayer])
+      .rpc();
+
+    const aBefore = await getAccount(provider.connection, await tokenAccountOf(lpA));
+    const bBefore = await getAccount(provider.connection, await tokenAccountOf(lpB));
+
+    await claim(lpA);
+    await claim(lpB);
+
+    const aAfter = await getAccount(provider.connection, await tokenAccountOf(lpA));
+    const bAfter = await getAccount(provider.connection, await tokenAccountOf(lpB));
+    const aGain = Number(aAfter.amount) - Number(aBefore.amount);
+    const bGain = Number(bAfter.amount) - Number(bBefore.amount);
+
+    // 1:3 contributions -> 1:3 fee split
+    expect(aGain).to.be.greaterThan(0);
+    expect(bGain).to.equal(aGain * 3);
+  });
+
+  it("should reject a claim with nothing accrued", async () => {
+    try {
+      await claim(lpA);
+      expect.fail("Should have thrown NoLpFeesToClaim error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6038); // NoLpFeesToClaim
+    }
+  });
+
+  describe("Excess Reward Sweep", () => {
+    const { admin } = getTestContext();
+
+    const setReserve = (reserve: anchor.BN, signer?: Keypair) => {
+      const builder = program.methods
+        .setMinRewardReserve(reserve)
+        .accounts({
+          config: configPda,
+          admin: signer ? signer.publicKey : admin.publicKey,
+        });
+      return signer ? builder.signers([signer]).rpc() : builder.rpc();
+    };
+
+    const sweep = async (amount: anchor.BN) =>
+      program.methods
+        .sweepExcessRewards(amount)
+        .accounts({
+          config: configPda,
+          destination: await tokenAccountOf(feePayer),
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+    const vaultBalance = async () => new anchor.BN((await getAccount(provider.connection, vaultPda)).amount.toString());
+
+    after(async () => {
+      await setReserve(new anchor.BN(0));
+    });
+
+    it("should reject reserve updates from non-admin", async () => {
+      try {
+        await setReserve(new anchor.BN(1_000_000_000), lpA);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should sweep down to, but not below, the reserve", async () => {
+      const oneDevr = new anchor.BN(1_000_000_000);
+      const before = await vaultBalance();
+
+      // LP funding above leaves the vault holding more than staked principal
+      await setReserve(before.sub(oneDevr));
+      await sweep(oneDevr);
+
+      const after = await vaultBalance();
+      expect(after.toString()).to.equal(before.sub(oneDevr).toString());
+      const config = await program.account.tokenConfig.fetch(configPda);
+      expect(config.minRewardReserve.toString()).to.equal(after.toString());
+    });
+
+    it("should reject a sweep that would breach the reserve", async () => {
+      const before = await vaultBalance();
+      await setReserve(before);
+
+      try {
+        await sweep(new anchor.BN(1));
+        expect.fail("Should have thrown ReserveBreach error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6051); // ReserveBreach
+      }
+
+      expect((await vaultBalance()).toString()).to.equal(before.toString());
+    });
+  });
+
+  describe("Fund Vault", () => {
+    const fundVault = async (funder: Keypair, amount: anchor.BN) =>
+      program.methods
+        .fundVault(amount)
+        .accounts({
+          funderTokenAccount: await tokenAccountOf(funder),
+          funder: funder.publicKey,
+        })
+        .signers([funder]);
+
+    it("should add reward liquidity without registering an LP share", async () => {
+      const amount = new anchor.BN(5_000_000_000); // 5 DEVR
+      const statsBefore = await program.account.globalStats.fetch(globalStatsPda);
+      const vaultBefore = (await getAccount(provider.connection, vaultPda)).amount;
+
+      const simulation = await (await fundVault(feePayer, amount)).simulate();
+      const event = simulation.events.find((e) => e.name === "vaultFundedEvent")?.data as any;
+      expect(event.funder.toString()).to.equal(feePayer.publicKey.toString());
+      expect(event.amount.toString()).to.equal(amount.toString());
+      expect(event.vaultBalance.toString()).to.equal((vaultBefore + BigInt(5_000_000_000)).toString());
+
+      await (await fundVault(feePayer, amount)).rpc();
+
+      const stats = await program.account.globalStats.fetch(globalStatsPda);
+      expect(stats.totalRewardsFunded.sub(statsBefore.totalRewardsFunded).toString()).to.equal(
+        amount.toString()
+      );
+      expect(stats.totalStaked.toString()).to.equal(statsBefore.totalStaked.toString());
+      expect((await getAccount(provider.connection, vaultPda)).amount).to.equal(
+        vaultBefore + BigInt(5_000_000_000)
+      );
+      expect(await program.account.liquidityProvider.fetchNullable(deriveLpPda(feePayer.publicKey))).to.be
+        .null;
+    });
+
+    it("should reject an empty deposit", async () => {
+      try {
+        await (await fundVault(feePayer, new anchor.BN(0))).rpc();
+        expect.fail("Should have thrown AmountTooSmall error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6001); // AmountTooSmall
+      }
+    });
+  });
+});
@@ -4,6 +4,7 @@ import { expect } from "chai";
 import {
   getTestContext,
   deriveProgramPDAs,
+  getClaimNonce,
   setupInitializedProgram,
 } from "./utils/test-helpers";
 
@@ -227,7 +228,7 @@ describe("Metaplex Token Metadata Tests", () => {
 
       // Should be able to claim tokens normally
       await program.methods
-        .claimTokens()
+        .claimTokens(await getClaimNonce(program, testUser.publicKey), [])
         .accounts({
           mint: mintPda,
           user: testUser.publicKey,
@@ -564,4 +565,59 @@ describe("Metaplex Token Metadata Tests", () => {
       expect(metadataAccount!.data.length).to.be.greaterThan(0);
     });
   });
+
+  describe("Update Token Metadata", () => {
+    const tokenName = "DevRewards Token";
+    const tokenSymbol = "DEVR";
+    const originalUri = "https://raw.githubusercontent.com/solana-labs/token-list/main/assets/mainnet/devr.json";
+    const newUri = "ipfs://bafybeigdyrztq4xwnmhvf3ijrfsvzwkpzmzxsgtdwgbjwrdrmzsphzg4ee/devr.json";
+
+    const updateMetadata = (uri: string, signer?: anchor.web3.Keypair) => {
+      const builder = program.methods
+        .updateMetadata(tokenName, tokenSymbol, uri)
+        .accounts({
+          config: configPda,
+          metadata: metadataPda,
+          mint: mintPda,
+          mintAuthority: mintAuthorityPda,
+          admin: (signer ?? admin).publicKey,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        });
+      return signer ? builder.signers([signer]) : builder;
+    };
+
+    after(async () => {
+      await updateMetadata(originalUri).rpc();
+    });
+
+    it("should update the URI and read back the new value", async () => {
+      await updateMetadata(newUri).rpc();
+
+      const metadataAccount = await provider.connection.getAccountInfo(metadataPda);
+      const metadataString = metadataAccount!.data.toString();
+      expect(metadataString).to.include(newUri);
+      expect(metadataString).to.not.include(originalUri);
+      expect(metadataString).to.include(tokenName);
+    });
+
+    it("should apply the create_metadata validations", async () => {
+      try {
+        await updateMetadata("ftp://example.com/devr.json").rpc();
+        expect.fail("Should have thrown InvalidUriFormat error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6016); // InvalidUriFormat
+      }
+    });
+
+    it("should reject updates from a non-admin", async () => {
+      const attacker = anchor.web3.Keypair.generate();
+
+      try {
+        await updateMetadata(newUri, attacker).rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+  });
 });
@@ -0,0 +1,183 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  transfer,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  getClaimNonce,
+  setupInitializedProgram,
+  createAndFundUser,
+  createAndFundUserWithoutTokens,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+describe("Program Pause Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  let staker: Keypair;
+  let stakerTokenAccount: PublicKey;
+
+  const setPause = (paused: boolean, signer?: Keypair) => {
+    const builder = program.methods
+      .setPause(paused)
+      .accounts({ config: configPda, admin: (signer ?? admin).publicKey });
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  const stake = async () => {
+    const counterPda = deriveCounterPda(staker.publicKey, program);
+    const counter = await program.account.stakeCounter.fetchNullable(counterPda);
+    const index = counter ? counter.stakeCount.toNumber() : 0;
+
+    return program.methods
+      .stake(new anchor.BN(1_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+      .accounts({
+        config: configPda,
+        counter: counterPda,
+        stakeAccount: deriveStakePda(staker.publicKey, index, program),
+        userTokenAccount: stakerTokenAccount,
+        vault: vaultPda,
+        globalStats: globalStatsPda,
+        user: staker.publicKey,
+      })
+      .signers([staker])
+      .rpc();
+  };
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    staker = await createAndFundUser(provider, program, mintPda, 5);
+    stakerTokenAccount = await getAssociatedTokenAddress(mintPda, staker.publicKey);
+  });
+
+  after(async () => {
+    await setPause(false).rpc();
+  });
+
+  it("should start unpaused", async () => {
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.paused).to.be.false;
+  });
+
+  it("should reject pausing from a non-admin", async () => {
+    try {
+      await setPause(true, staker).rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should reject stakes while paused", async () => {
+    await setPause(true).rpc();
+
+    try {
+      await stake();
+      expect.fail("Should have thrown ProgramPaused error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6066); // ProgramPaused
+    }
+  });
+
+  it("should reject claims while paused", async () => {
+    const claimer = await createAndFundUserWithoutTokens(provider, 2);
+
+    try {
+      await program.methods
+        .claimTokens(await getClaimNonce(program, claimer.publicKey), [])
+        .accounts({
+          mint: mintPda,
+          user: claimer.publicKey,
+        })
+        .signers([claimer])
+        .rpc();
+      expect.fail("Should have thrown ProgramPaused error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6066); // ProgramPaused
+    }
+  });
+
+  it("should let the admin unpause and reopen staking", async () => {
+    await setPause(false).rpc();
+
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.paused).to.be.false;
+
+    await stake();
+  });
+
+  describe("Emergency Vault Withdraw", () => {
+    const AMOUNT = 1_000_000_000; // 1 DEVR
+    let adminTokenAccount: PublicKey;
+
+    const withdraw = (recipient: PublicKey, signer?: Keypair) => {
+      const builder = program.methods
+        .emergencyVaultWithdraw(new anchor.BN(AMOUNT))
+        .accounts({
+          config: configPda,
+          vault: vaultPda,
+          recipient,
+          admin: (signer ?? admin).publicKey,
+        });
+      return signer ? builder.signers([signer]) : builder;
+    };
+
+    before(async () => {
+      adminTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, admin.payer, mintPda, admin.publicKey)
+      ).address;
+    });
+
+    after(async () => {
+      await setPause(false).rpc();
+    });
+
+    it("should reject a withdrawal while the program is running", async () => {
+      try {
+        await withdraw(adminTokenAccount).rpc();
+        expect.fail("Should have thrown ProgramNotPaused error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6082); // ProgramNotPaused
+      }
+    });
+
+    it("should reject a withdrawal from a non-admin", async () => {
+      await setPause(true).rpc();
+
+      try {
+        await withdraw(stakerTokenAccount, staker).rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should move vault funds to the admin's account while paused", async () => {
+      const { events } = await withdraw(adminTokenAccount).simulate();
+      const withdrawal = events.find((e) => e.name === "emergencyWithdrawEvent")?.data as any;
+      expect(withdrawal.amount.toNumber()).to.equal(AMOUNT);
+      expect(withdrawal.recipient.toString()).to.equal(adminTokenAccount.toString());
+
+      const vaultBefore = await getAccount(provider.connection, vaultPda);
+      const adminBefore = await getAccount(provider.connection, adminTokenAccount);
+      await withdraw(adminTokenAccount).rpc();
+      const vaultAfter = await getAccount(provider.connection, vaultPda);
+      const adminAfter = await getAccount(provider.connection, adminTokenAccount);
+
+      expect(Number(vaultBefore.amount - vaultAfter.amount)).to.equal(AMOUNT);
+      expect(Number(adminAfter.amount - adminBefore.amount)).to.equal(AMOUNT);
+
+      // Put it back so the vault still backs every position for later tests
+      await transfer(provider.connection, admin.payer, adminTokenAccount, vaultPda, admin.payer, AMOUNT);
+    });
+  });
+});
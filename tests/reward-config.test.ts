@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+  getAssociatedTokenAddress,
+  getOrCreateAssociatedTokenAccount,
+  createMint,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  setupInitializedProgram,
+  createAndFundUser,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+describe("Reward Config Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, vaultAuthorityPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  let staker: Keypair;
+  let stakerTokenAccount: PublicKey;
+  let rewardMint: PublicKey;
+  let rewardVault: PublicKey;
+
+  const setRewardConfig = (mint: PublicKey, vault: PublicKey, signer?: Keypair) => {
+    const builder = program.methods.setRewardConfig().accounts({
+      config: configPda,
+      rewardMint: mint,
+      rewardVault: vault,
+      admin: (signer ?? admin).publicKey,
+    });
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    staker = await createAndFundUser(provider, program, mintPda, 5);
+    stakerTokenAccount = await getAssociatedTokenAddress(mintPda, staker.publicKey);
+
+    await program.methods
+      .stake(new anchor.BN(50_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+      .accounts({
+        config: configPda,
+        counter: deriveCounterPda(staker.publicKey, program),
+        stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+        userTokenAccount: stakerTokenAccount,
+        vault: vaultPda,
+        globalStats: globalStatsPda,
+        user: staker.publicKey,
+      })
+      .signers([staker])
+      .rpc();
+
+    // Same decimals as DEVR, so only the mint tells the two tokens apart
+    rewardMint = await createMint(provider.connection, admin.payer, admin.publicKey, null, 9);
+    rewardVault = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        admin.payer,
+        rewardMint,
+        vaultAuthorityPda,
+        true
+      )
+    ).address;
+  });
+
+  after(async () => {
+    // Back to single-mint payouts for the other suites
+    await setRewardConfig(mintPda, vaultPda).rpc();
+  });
+
+  it("should reject reward config updates from a non-admin", async () => {
+    try {
+      await setRewardConfig(rewardMint, rewardVault, staker).rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should keep single-mint payouts when the reward mint is the stake mint", async () => {
+    const { events } = await setRewardConfig(mintPda, vaultPda).simulate();
+    const updated = events.find((e) => e.name === "rewardConfigUpdatedEvent")?.data as any;
+    expect(updated.split).to.be.false;
+    expect(updated.rewardVault.toString()).to.equal(vaultPda.toString());
+
+    await setRewardConfig(mintPda, vaultPda).rpc();
+
+    const config = await program.account.tokenConfig.fetch(configPda);
+    expect(config.rewardDecimals).to.equal(9);
+  });
+
+  it("should reject a same-mint config that isn't the stake vault", async () => {
+    const otherDevrAccount = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, admin.payer, mintPda, vaultAuthorityPda, true)
+    ).address;
+
+    try {
+      await setRewardConfig(mintPda, otherDevrAccount).rpc();
+      expect.fail("Should have thrown RewardAccountMismatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6085); // RewardAccountMismatch
+    }
+  });
+
+  it("should split rewards out to a separate mint and vault", async () => {
+    const { events } = await setRewardConfig(rewardMint, rewardVault).simulate();
+    const updated = events.find((e) => e.name === "rewardConfigUpdatedEvent")?.data as any;
+    expect(updated.split).to.be.true;
+    expect(updated.rewardMint.toString()).to.equal(rewardMint.toString());
+
+    const mintChange = events.find((e) => e.name === "configPubkeyChangedEvent")?.data as any;
+    expect(mintChange.field).to.equal(44); // CONFIG_FIELD_REWARD_MINT
+    expect(mintChange.oldValue.toString()).to.equal(mintPda.toString());
+    expect(mintChange.newValue.toString()).to.equal(rewardMint.toString());
+    const decimalsChange = events.find((e) => e.name === "configChangedEvent")?.data as any;
+    expect(decimalsChange.field).to.equal(43); // CONFIG_FIELD_REWARD_DECIMALS
+
+    await setRewardConfig(rewardMint, rewardVault).rpc();
+  });
+
+  it("should reject an unstake without the reward accounts while split", async () => {
+    try {
+      await program.methods
+        .unstake(new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(staker.publicKey, program),
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          userTokenAccount: stakerTokenAccount,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthorityPda,
+          globalStats: globalStatsPda,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+      expect.fail("Should have thrown RewardAccountMismatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6085); // RewardAccountMismatch
+    }
+  });
+
+  it("should reject compounding rewards of another mint into DEVR principal", async () => {
+    try {
+      await program.methods
+        .compoundRewardsToNewStake(new anchor.BN(0), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(staker.publicKey, program),
+          stakeAccount: deriveStakePda(staker.publicKey, 0, program),
+          newStakeAccount: deriveStakePda(staker.publicKey, 1, program),
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+      expect.fail("Should have thrown RewardTokenMismatch error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6040); // RewardTokenMismatch
+    }
+  });
+
+  // Split payouts (principal from the stake vault, rewards from the reward vault)
+  // need a matured stake; the routing is unit-tested in unstake.rs
+});
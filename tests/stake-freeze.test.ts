@@ -0,0 +1,179 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  setupInitializedProgram,
+  createAndFundUser,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+describe("Stake Review Freeze Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, vaultAuthorityPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  let staker: Keypair;
+  let stakerTokenAccount: PublicKey;
+  let stakeAccountPda: PublicKey;
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    staker = await createAndFundUser(provider, program, mintPda, 5);
+    stakerTokenAccount = await getAssociatedTokenAddress(mintPda, staker.publicKey);
+    stakeAccountPda = deriveStakePda(staker.publicKey, 0, program);
+
+    await program.methods
+      .stake(new anchor.BN(50_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+      .accounts({
+        config: configPda,
+        counter: deriveCounterPda(staker.publicKey, program),
+        stakeAccount: stakeAccountPda,
+        userTokenAccount: stakerTokenAccount,
+        vault: vaultPda,
+        globalStats: globalStatsPda,
+        user: staker.publicKey,
+      })
+      .signers([staker])
+      .rpc();
+  });
+
+  it("should start with the stake not frozen", async () => {
+    const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+    expect(stakeAccount.frozen).to.be.false;
+    expect(stakeAccount.frozenAt.toNumber()).to.equal(0);
+    expect(stakeAccount.unfrozenAt.toNumber()).to.equal(0);
+  });
+
+  it("should reject freeze from a non-admin", async () => {
+    try {
+      await program.methods
+        .freezeStake(staker.publicKey, new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          stakeAccount: stakeAccountPda,
+          admin: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should let the admin freeze a stake", async () => {
+    await program.methods
+      .freezeStake(staker.publicKey, new anchor.BN(0))
+      .accounts({
+        config: configPda,
+        stakeAccount: stakeAccountPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+    expect(stakeAccount.frozen).to.be.true;
+    expect(stakeAccount.frozenAt.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("should reject unstake while the stake is frozen", async () => {
+    try {
+      await program.methods
+        .unstake(new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(staker.publicKey, program),
+          stakeAccount: stakeAccountPda,
+          userTokenAccount: stakerTokenAccount,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthorityPda,
+          globalStats: globalStatsPda,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+
+      expect.fail("Should have thrown StakeFrozen error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6019); // StakeFrozen
+    }
+  });
+
+  it("should let the admin unfreeze a stake", async () => {
+    await program.methods
+      .unfreezeStake(staker.publicKey, new anchor.BN(0))
+      .accounts({
+        config: configPda,
+        stakeAccount: stakeAccountPda,
+        admin: admin.publicKey,
+      })
+      .rpc();
+
+    const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+    expect(stakeAccount.frozen).to.be.false;
+    // Closes the interval excluded from rewards (payout math unit-tested in `unstake.rs`)
+    expect(stakeAccount.unfrozenAt.toNumber()).to.be.at.least(stakeAccount.frozenAt.toNumber());
+  });
+
+  it("should fall back to the lock check once unfrozen", async () => {
+    try {
+      await program.methods
+        .unstake(new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(staker.publicKey, program),
+          stakeAccount: stakeAccountPda,
+          userTokenAccount: stakerTokenAccount,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthorityPda,
+          globalStats: globalStatsPda,
+          user: staker.publicKey,
+        })
+        .signers([staker])
+        .rpc();
+
+      expect.fail("Should have thrown StillLocked error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6005); // StillLocked
+    }
+  });
+
+  it("should reject unfreezing a stake that is not frozen", async () => {
+    try {
+      await program.methods
+        .unfreezeStake(staker.publicKey, new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          stakeAccount: stakeAccountPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown StakeNotFrozen error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6020); // StakeNotFrozen
+    }
+  });
+
+  it("should not allow the same stake to be frozen a second time", async () => {
+    try {
+      await program.methods
+        .freezeStake(staker.publicKey, new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          stakeAccount: stakeAccountPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Should have thrown StakeAlreadyFrozen error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6021); // StakeAlreadyFrozen
+    }
+  });
+});
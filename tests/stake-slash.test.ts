@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  getTestContext,
+  deriveProgramPDAs,
+  deriveStakePda,
+  deriveCounterPda,
+  setupInitializedProgram,
+  createAndFundUser,
+  MIN_LOCK_DURATION,
+} from "./utils/test-helpers";
+
+describe("Stake Slashing Tests", () => {
+  const { program, provider, admin } = getTestContext();
+  const { configPda, mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  const STAKE_AMOUNT = 50_000_000_000; // 50 DEVR
+  const SLASH_AMOUNT = 20_000_000_000; // 20 DEVR
+  const REASON_CODE = 3;
+
+  let staker: Keypair;
+  let stakeAccountPda: PublicKey;
+
+  const slash = (amount: number, signer?: Keypair) => {
+    const builder = program.methods
+      .slashStake(staker.publicKey, new anchor.BN(0), new anchor.BN(amount), REASON_CODE)
+      .accounts({
+        config: configPda,
+        stakeAccount: stakeAccountPda,
+        globalStats: globalStatsPda,
+        admin: (signer ?? admin).publicKey,
+      });
+    return signer ? builder.signers([signer]) : builder;
+  };
+
+  before(async () => {
+    await setupInitializedProgram(program, configPda);
+    staker = await createAndFundUser(provider, program, mintPda, 5);
+    stakeAccountPda = deriveStakePda(staker.publicKey, 0, program);
+
+    await program.methods
+      .stake(new anchor.BN(STAKE_AMOUNT), new anchor.BN(MIN_LOCK_DURATION))
+      .accounts({
+        config: configPda,
+        counter: deriveCounterPda(staker.publicKey, program),
+        stakeAccount: stakeAccountPda,
+        userTokenAccount: await getAssociatedTokenAddress(mintPda, staker.publicKey),
+        vault: vaultPda,
+        globalStats: globalStatsPda,
+        user: staker.publicKey,
+      })
+      .signers([staker])
+      .rpc();
+  });
+
+  it("should reject a slash from a non-admin", async () => {
+    try {
+      await slash(SLASH_AMOUNT, staker).rpc();
+      expect.fail("Should have thrown Unauthorized error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+    }
+  });
+
+  it("should reject slashing more than the staked amount", async () => {
+    try {
+      await slash(STAKE_AMOUNT + 1).rpc();
+      expect.fail("Should have thrown InvalidSlashAmount error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6079); // InvalidSlashAmount
+    }
+  });
+
+  it("should reject a zero slash", async () => {
+    try {
+      await slash(0).rpc();
+      expect.fail("Should have thrown InvalidSlashAmount error");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.number).to.equal(6079); // InvalidSlashAmount
+    }
+  });
+
+  it("should emit SlashEvent with the slash details", async () => {
+    const { events } = await slash(SLASH_AMOUNT).simulate();
+    const event = events.find((e) => e.name === "slashEvent");
+
+    expect(event).to.exist;
+    expect(event!.data.user.toString()).to.equal(staker.publicKey.toString());
+    expect(event!.data.stakeIndex.toNumber()).to.equal(0);
+    expect(event!.data.slashedAmount.toNumber()).to.equal(SLASH_AMOUNT);
+    expect(event!.data.remainingPrincipal.toNumber()).to.equal(STAKE_AMOUNT - SLASH_AMOUNT);
+    expect(event!.data.reasonCode).to.equal(REASON_CODE);
+    expect(event!.data.admin.toString()).to.equal(admin.publicKey.toString());
+  });
+
+  it("should cut the principal and the staked total, keeping tokens in the vault", async () => {
+    const statsBefore = await program.account.globalStats.fetch(globalStatsPda);
+    const vaultBefore = await provider.connection.getTokenAccountBalance(vaultPda);
+
+    await slash(SLASH_AMOUNT).rpc();
+
+    const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+    expect(stakeAccount.stakedAmount.toNumber()).to.equal(STAKE_AMOUNT - SLASH_AMOUNT);
+
+    const statsAfter = await program.account.globalStats.fetch(globalStatsPda);
+    expect(statsBefore.totalStaked.sub(statsAfter.totalStaked).toNumber()).to.equal(SLASH_AMOUNT);
+    expect(
+      statsAfter.totalPenaltiesCollected.sub(statsBefore.totalPenaltiesCollected).toNumber()
+    ).to.equal(SLASH_AMOUNT);
+    // A smaller principal needs a smaller reward reservation
+    expect(statsAfter.totalRewardLiability.lt(statsBefore.totalRewardLiability)).to.be.true;
+
+    const vaultAfter = await provider.connection.getTokenAccountBalance(vaultPda);
+    expect(vaultAfter.value.amount).to.equal(vaultBefore.value.amount);
+  });
+});
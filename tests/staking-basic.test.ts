@@ -12,6 +12,10 @@ import {
   SECONDS_PER_DAY,
   MIN_LOCK_DURATION,
   MAX_LOCK_DURATION,
+  CURRENT_ACCOUNT_VERSION,
+  deriveUserClaimPda,
+  deriveUserStakeStatsPda,
+  deriveUserStatsPda,
 } from "./utils/test-helpers";
 
 describe("Basic Staking Tests (Day 18)", () => {
@@ -63,6 +67,9 @@ describe("Basic Staking Tests (Day 18)", () => {
       expect(stakeAccount.stakedAmount.toString()).to.equal(stakeAmount.toString());
       expect(stakeAccount.lockDuration.toString()).to.equal(lockDuration.toString());
       expect(stakeAccount.stakedAt.toNumber()).to.be.greaterThan(0);
+      expect(stakeAccount.maturityTimestamp.toNumber()).to.equal(
+        stakeAccount.stakedAt.toNumber() + lockDuration.toNumber()
+      );
       expect(stakeAccount.stakeIndex.toString()).to.equal("0");
     });
 
@@ -247,6 +254,31 @@ describe("Basic Staking Tests (Day 18)", () => {
         expect(error.error?.errorCode?.number).to.equal(6003);
       }
     });
+
+    it("should reject a stake into a token account other than the vault", async () => {
+      const newStaker = await createAndFundUser(provider, program, mintPda, 2);
+      const tokenAccount = await getAssociatedTokenAddress(mintPda, newStaker.publicKey);
+
+      try {
+        await program.methods
+          .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+          .accounts({
+            config: configPda,
+            counter: deriveCounterPda(newStaker.publicKey, program),
+            stakeAccount: deriveStakePda(newStaker.publicKey, 0, program),
+            userTokenAccount: tokenAccount,
+            vault: stakerTokenAccount, // A DEVR account, but not the program's vault
+            globalStats: globalStatsPda,
+            user: newStaker.publicKey,
+          })
+          .signers([newStaker])
+          .rpc();
+
+        expect.fail("Should have rejected the non-canonical vault");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(2006); // ConstraintSeeds
+      }
+    });
   });
 
   describe("Unstake Instruction", () => {
@@ -299,6 +331,57 @@ describe("Basic Staking Tests (Day 18)", () => {
       }
     });
 
+    it("should fail a partial unstake while tokens are still locked", async () => {
+      try {
+        await program.methods
+          .partialUnstake(new anchor.BN(0), new anchor.BN(10_000_000_000))
+          .accounts({
+            config: configPda,
+            counter: deriveCounterPda(unstaker.publicKey, program),
+            stakeAccount: unstakerStakeAccountPda,
+            userTokenAccount: unstakerTokenAccount,
+            vault: vaultPda,
+            vaultAuthority: vaultAuthorityPda,
+            globalStats: globalStatsPda,
+            user: unstaker.publicKey,
+          })
+          .signers([unstaker])
+          .rpc();
+
+        expect.fail("Should have thrown StillLocked error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6005);
+      }
+
+      // Nothing was taken out of the position
+      const stakeAccount = await program.account.stakeAccount.fetch(unstakerStakeAccountPda);
+      expect(stakeAccount.stakedAmount.toString()).to.equal("50000000000");
+    });
+
+    it("should reject an unstake from a token account other than the vault", async () => {
+      try {
+        await program.methods
+          .unstake(new anchor.BN(0))
+          .accounts({
+            config: configPda,
+            counter: deriveCounterPda(unstaker.publicKey, program),
+            stakeAccount: unstakerStakeAccountPda,
+            userTokenAccount: unstakerTokenAccount,
+            vault: stakerTokenAccount, // Not the vault config points at
+            vaultAuthority: vaultAuthorityPda,
+            globalStats: globalStatsPda,
+            user: unstaker.publicKey,
+          })
+          .signers([unstaker])
+          .rpc();
+
+        expect.fail("Should have rejected the non-canonical vault");
+      } catch (error: any) {
+        // Rejected at account validation, before the lock is even checked
+        expect(error.error?.errorCode?.number).to.equal(2006); // ConstraintSeeds
+      }
+    });
+
     it("should verify stake account state during lock period", async () => {
       const stakeAccount = await program.account.stakeAccount.fetch(unstakerStakeAccountPda);
 
@@ -326,6 +409,102 @@ describe("Basic Staking Tests (Day 18)", () => {
     });
   });
 
+  describe("Payout Destination", () => {
+    // Matured payouts need time travel; routing is checked before the lock, so a
+    // destination that passes fails with StillLocked instead
+    let payoutUser: Keypair;
+    let payoutUserTokenAccount: PublicKey;
+    let preferencesPda: PublicKey;
+
+    const unstakeInto = async (destination: PublicKey) =>
+      program.methods
+        .unstake(new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(payoutUser.publicKey, program),
+          stakeAccount: deriveStakePda(payoutUser.publicKey, 0, program),
+          userTokenAccount: destination,
+          vault: vaultPda,
+          vaultAuthority: vaultAuthorityPda,
+          globalStats: globalStatsPda,
+          user: payoutUser.publicKey,
+        })
+        .signers([payoutUser])
+        .rpc();
+
+    const expectUnstakeError = async (destination: PublicKey, code: number) => {
+      try {
+        await unstakeInto(destination);
+        expect.fail(`Should have thrown error ${code}`);
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(code);
+      }
+    };
+
+    before(async () => {
+      payoutUser = await createAndFundUser(provider, program, mintPda, 2);
+      payoutUserTokenAccount = await getAssociatedTokenAddress(mintPda, payoutUser.publicKey);
+      [preferencesPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user-preferences"), payoutUser.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(payoutUser.publicKey, program),
+          stakeAccount: deriveStakePda(payoutUser.publicKey, 0, program),
+          userTokenAccount: payoutUserTokenAccount,
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: payoutUser.publicKey,
+        })
+        .signers([payoutUser])
+        .rpc();
+    });
+
+    it("should pay into the user's own account while no preference is set", async () => {
+      await expectUnstakeError(payoutUserTokenAccount, 6005); // StillLocked - routing accepted
+      await expectUnstakeError(stakerTokenAccount, 6059); // PayoutDestinationMismatch
+    });
+
+    it("should route unstakes to the stored preference once set", async () => {
+      await program.methods
+        .setPayoutDestination(stakerTokenAccount)
+        .accounts({
+          config: configPda,
+          destinationTokenAccount: stakerTokenAccount,
+          user: payoutUser.publicKey,
+        })
+        .signers([payoutUser])
+        .rpc();
+
+      const preferences = await program.account.userPreferences.fetch(preferencesPda);
+      expect(preferences.payoutDestination.toString()).to.equal(stakerTokenAccount.toString());
+
+      await expectUnstakeError(stakerTokenAccount, 6005); // StillLocked - routing accepted
+      await expectUnstakeError(payoutUserTokenAccount, 6059); // PayoutDestinationMismatch
+    });
+
+    it("should reject a destination that isn't a DEVR token account", async () => {
+      try {
+        await program.methods
+          .setPayoutDestination(vaultAuthorityPda)
+          .accounts({
+            config: configPda,
+            destinationTokenAccount: vaultAuthorityPda,
+            user: payoutUser.publicKey,
+          })
+          .signers([payoutUser])
+          .rpc();
+        expect.fail("Should have rejected a non-token account");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("AccountNotInitialized");
+      }
+    });
+  });
+
   describe("Multiple Users Staking", () => {
     it("should handle multiple users staking independently", async () => {
       const user1 = await createAndFundUser(provider, program, mintPda, 2);
@@ -410,4 +589,231 @@ describe("Basic Staking Tests (Day 18)", () => {
       expect(vaultBalanceAfter.amount).to.equal(vaultBalanceBefore.amount + BigInt(stakeAmount.toString()));
     });
   });
+
+  describe("Reward Reserve", () => {
+    it("should reserve a new position's maximum rewards", async () => {
+      const newStaker = await createAndFundUser(provider, program, mintPda, 2);
+      const stakeAccountPda = deriveStakePda(newStaker.publicKey, 0, program);
+      const statsBefore = await program.account.globalStats.fetch(globalStatsPda);
+
+      await program.methods
+        .stake(new anchor.BN(50_000_000_000), new anchor.BN(30 * SECONDS_PER_DAY))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(newStaker.publicKey, program),
+          stakeAccount: stakeAccountPda,
+          userTokenAccount: await getAssociatedTokenAddress(mintPda, newStaker.publicKey),
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: newStaker.publicKey,
+        })
+        .signers([newStaker])
+        .rpc();
+
+      const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+      const statsAfter = await program.account.globalStats.fetch(globalStatsPda);
+      const vault = await getAccount(provider.connection, vaultPda);
+
+      expect(stakeAccount.rewardLiability.toNumber()).to.be.greaterThan(0);
+      expect(stakeAccount.isWithdrawn).to.be.false;
+      expect(statsAfter.totalRewardLiability.toString()).to.equal(
+        statsBefore.totalRewardLiability.add(stakeAccount.rewardLiability).toString()
+      );
+      // The vault backs every principal plus every reservation
+      expect(vault.amount >= BigInt(statsAfter.totalStaked.add(statsAfter.totalRewardLiability).toString())).to.be
+        .true;
+    });
+  });
+
+  describe("Account Versioning", () => {
+    it("should create user accounts at the current version", async () => {
+      const newStaker = await createAndFundUser(provider, program, mintPda, 2);
+      const stakeAccountPda = deriveStakePda(newStaker.publicKey, 0, program);
+      const counterPda = deriveCounterPda(newStaker.publicKey, program);
+
+      await program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: counterPda,
+          stakeAccount: stakeAccountPda,
+          userTokenAccount: await getAssociatedTokenAddress(mintPda, newStaker.publicKey),
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: newStaker.publicKey,
+        })
+        .signers([newStaker])
+        .rpc();
+
+      const stakeAccount = await program.account.stakeAccount.fetch(stakeAccountPda);
+      const counter = await program.account.stakeCounter.fetch(counterPda);
+      const userClaim = await program.account.userClaim.fetch(
+        deriveUserClaimPda(newStaker.publicKey, program)
+      );
+      const userStakeStats = await program.account.userStakeStats.fetch(
+        deriveUserStakeStatsPda(newStaker.publicKey, program)
+      );
+
+      expect(stakeAccount.version).to.equal(CURRENT_ACCOUNT_VERSION);
+      expect(counter.version).to.equal(CURRENT_ACCOUNT_VERSION);
+      expect(userClaim.version).to.equal(CURRENT_ACCOUNT_VERSION);
+      expect(userStakeStats.version).to.equal(CURRENT_ACCOUNT_VERSION);
+    });
+  });
+
+  describe("Active Stakes", () => {
+    // Normal unstakes need a matured lock; the full stake/unstake/restake cycle and
+    // MAX_STAKES_PER_USER are unit-tested in state.rs
+    let activeUser: Keypair;
+    let activeUserTokenAccount: PublicKey;
+
+    const stakeNext = async (index: number) =>
+      program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(activeUser.publicKey, program),
+          stakeAccount: deriveStakePda(activeUser.publicKey, index, program),
+          userTokenAccount: activeUserTokenAccount,
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: activeUser.publicKey,
+        })
+        .signers([activeUser])
+        .rpc();
+
+    const fetchCounter = () =>
+      program.account.stakeCounter.fetch(deriveCounterPda(activeUser.publicKey, program));
+
+    before(async () => {
+      activeUser = await createAndFundUser(provider, program, mintPda, 5);
+      activeUserTokenAccount = await getAssociatedTokenAddress(mintPda, activeUser.publicKey);
+    });
+
+    it("should count each open position", async () => {
+      await stakeNext(0);
+      await stakeNext(1);
+
+      const counter = await fetchCounter();
+      expect(counter.stakeCount.toNumber()).to.equal(2);
+      expect(counter.activeStakes.toNumber()).to.equal(2);
+    });
+
+    it("should drop the count when a position closes but keep the next index", async () => {
+      await program.methods
+        .emergencyUnstake(new anchor.BN(0))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(activeUser.publicKey, program),
+          stakeAccount: deriveStakePda(activeUser.publicKey, 0, program),
+          userTokenAccount: activeUserTokenAccount,
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: activeUser.publicKey,
+        })
+        .signers([activeUser])
+        .rpc();
+
+      let counter = await fetchCounter();
+      expect(counter.stakeCount.toNumber()).to.equal(2);
+      expect(counter.activeStakes.toNumber()).to.equal(1);
+
+      await stakeNext(2);
+      counter = await fetchCounter();
+      expect(counter.stakeCount.toNumber()).to.equal(3);
+      expect(counter.activeStakes.toNumber()).to.equal(2);
+    });
+  });
+
+  describe("Stake Index Collision", () => {
+    // Two stakes built from the same counter read: the second lands after the
+    // counter moved on. A position already sitting at the counter's index (a
+    // desynced counter) fails with StakeIndexCollision instead, unit-tested in state.rs.
+    let racer: Keypair;
+    let racerTokenAccount: PublicKey;
+
+    const stakeAt = (index: number) =>
+      program.methods
+        .stake(new anchor.BN(10_000_000_000), new anchor.BN(MIN_LOCK_DURATION))
+        .accounts({
+          config: configPda,
+          counter: deriveCounterPda(racer.publicKey, program),
+          stakeAccount: deriveStakePda(racer.publicKey, index, program),
+          userTokenAccount: racerTokenAccount,
+          vault: vaultPda,
+          globalStats: globalStatsPda,
+          user: racer.publicKey,
+        })
+        .signers([racer])
+        .rpc();
+
+    const nextIndex = async () => {
+      const counter = await program.account.stakeCounter.fetchNullable(
+        deriveCounterPda(racer.publicKey, program)
+      );
+      return counter ? counter.stakeCount.toNumber() : 0;
+    };
+
+    before(async () => {
+      racer = await createAndFundUser(provider, program, mintPda, 5);
+      racerTokenAccount = await getAssociatedTokenAddress(mintPda, racer.publicKey);
+    });
+
+    it("should reject the stale stake and succeed on a re-fetched retry", async () => {
+      const staleIndex = await nextIndex();
+      await stakeAt(staleIndex);
+
+      try {
+        await stakeAt(staleIndex);
+        expect.fail("Should have rejected a stake at a stale index");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("ConstraintSeeds");
+      }
+
+      // Recommended client retry: re-read the counter and rebuild the stake
+      const retryIndex = await nextIndex();
+      expect(retryIndex).to.equal(staleIndex + 1);
+      await stakeAt(retryIndex);
+
+      const position = await program.account.stakeAccount.fetch(
+        deriveStakePda(racer.publicKey, retryIndex, program)
+      );
+      expect(position.stakeIndex.toNumber()).to.equal(retryIndex);
+    });
+  });
+
+  describe("User Stats", () => {
+    // Unstakes need a matured lock; the closing side of the rollups is unit-tested in state.rs
+    it("should roll up every stake into the user's lifetime stats", async () => {
+      const newStaker = await createAndFundUser(provider, program, mintPda, 5);
+      const userTokenAccount = await getAssociatedTokenAddress(mintPda, newStaker.publicKey);
+      const amounts = [new anchor.BN(10_000_000_000), new anchor.BN(25_000_000_000)];
+
+      for (const [index, amount] of amounts.entries()) {
+        await program.methods
+          .stake(amount, new anchor.BN(MIN_LOCK_DURATION))
+          .accounts({
+            config: configPda,
+            counter: deriveCounterPda(newStaker.publicKey, program),
+            stakeAccount: deriveStakePda(newStaker.publicKey, index, program),
+            userTokenAccount,
+            vault: vaultPda,
+            globalStats: globalStatsPda,
+            user: newStaker.publicKey,
+          })
+          .signers([newStaker])
+          .rpc();
+      }
+
+      const userStats = await program.account.userStats.fetch(
+        deriveUserStatsPda(newStaker.publicKey, program)
+      );
+      expect(userStats.version).to.equal(CURRENT_ACCOUNT_VERSION);
+      expect(userStats.user.toBase58()).to.equal(newStaker.publicKey.toBase58());
+      expect(userStats.totalEverStaked.toString()).to.equal(amounts[0].add(amounts[1]).toString());
+      expect(userStats.stakesOpened.toNumber()).to.equal(2);
+      expect(userStats.stakesClosed.toNumber()).to.equal(0);
+      expect(userStats.totalRewardsEarned.toNumber()).to.equal(0);
+    });
+  });
 });
@@ -1,15 +1,23 @@
 import * as anchor from "@coral-xyz/anchor";
 import { PublicKey, Keypair } from "@solana/web3.js";
-import { getAssociatedTokenAddress, getAccount, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+import {
+  getAssociatedTokenAddress,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  createMint,
+  mintTo,
+} from "@solana/spl-token";
 import { expect } from "chai";
 import {
   getTestContext,
   deriveProgramPDAs,
+  getClaimNonce,
   setupInitializedProgram,
+  createAndFundUser,
 } from "./utils/test-helpers";
 
 describe("Token Transfers & Delegation Tests", () => {
-  const { program, provider } = getTestContext();
+  const { program, provider, admin } = getTestContext();
   const { configPda, mintPda } = deriveProgramPDAs(program);
 
   let alice: Keypair;
@@ -50,7 +58,7 @@ describe("Token Transfers & Delegation Tests", () => {
 
     // Alice claims 100 DEVR tokens
     await program.methods
-      .claimTokens()
+      .claimTokens(await getClaimNonce(program, alice.publicKey), [])
       .accounts({
         mint: mintPda,
         user: alice.publicKey,
@@ -77,7 +85,7 @@ describe("Token Transfers & Delegation Tests", () => {
       const transferAmount = 50_000_000_000; // 50 DEVR (with 9 decimals)
 
       await program.methods
-        .transfer(new anchor.BN(transferAmount))
+        .transfer(new anchor.BN(transferAmount), new anchor.BN(0))
         .accounts({
           fromTokenAccount: aliceTokenAccount,
           toTokenAccount: bobTokenAccount,
@@ -95,12 +103,34 @@ describe("Token Transfers & Delegation Tests", () => {
       expect(bobBalanceAfter.amount).to.equal(bobBalanceBefore.amount + BigInt(transferAmount));
     });
 
+    it("should emit a TransferEvent", async () => {
+      const transferAmount = 5_000_000_000; // 5 DEVR
+
+      const simulation = await program.methods
+        .transfer(new anchor.BN(transferAmount), new anchor.BN(0))
+        .accounts({
+          fromTokenAccount: aliceTokenAccount,
+          toTokenAccount: bobTokenAccount,
+          authority: alice.publicKey,
+        })
+        .signers([alice])
+        .simulate();
+
+      const event = simulation.events.find((e) => e.name === "transferEvent");
+      expect(event).to.not.be.undefined;
+      expect(event.data.from.toString()).to.equal(aliceTokenAccount.toString());
+      expect(event.data.to.toString()).to.equal(bobTokenAccount.toString());
+      expect(event.data.authority.toString()).to.equal(alice.publicKey.toString());
+      expect(event.data.amount.toNumber()).to.equal(transferAmount);
+      expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
+    });
+
     it("should fail if amount too small", async () => {
       const tooSmallAmount = 500_000_000; // 0.5 DEVR (less than MIN_TRANSFER of 1 DEVR)
 
       try {
         await program.methods
-          .transfer(new anchor.BN(tooSmallAmount))
+          .transfer(new anchor.BN(tooSmallAmount), new anchor.BN(0))
           .accounts({
             fromTokenAccount: aliceTokenAccount,
             toTokenAccount: bobTokenAccount,
@@ -121,7 +151,7 @@ describe("Token Transfers & Delegation Tests", () => {
 
       try {
         await program.methods
-          .transfer(new anchor.BN(excessiveAmount))
+          .transfer(new anchor.BN(excessiveAmount), new anchor.BN(0))
           .accounts({
             fromTokenAccount: bobTokenAccount,
             toTokenAccount: aliceTokenAccount,
@@ -141,7 +171,7 @@ describe("Token Transfers & Delegation Tests", () => {
 
       try {
         await program.methods
-          .transfer(new anchor.BN(tooLargeAmount))
+          .transfer(new anchor.BN(tooLargeAmount), new anchor.BN(0))
           .accounts({
             fromTokenAccount: aliceTokenAccount,
             toTokenAccount: bobTokenAccount,
@@ -155,6 +185,103 @@ describe("Token Transfers & Delegation Tests", () => {
         expect(error.error?.errorCode?.number).to.equal(6002); // AmountTooLarge
       }
     });
+
+    it("should fail if transfer would breach the requested reserve", async () => {
+      // Alice holds 50 DEVR; sending 10 with a 45 DEVR reserve would leave only 40
+      const transferAmount = 10_000_000_000;
+      const keepReserve = 45_000_000_000;
+
+      try {
+        await program.methods
+          .transfer(new anchor.BN(transferAmount), new anchor.BN(keepReserve))
+          .accounts({
+            fromTokenAccount: aliceTokenAccount,
+            toTokenAccount: bobTokenAccount,
+            authority: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown ReserveViolation error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6022); // ReserveViolation
+      }
+    });
+
+    it("should transfer when the requested reserve is respected", async () => {
+      const aliceBalanceBefore = await getAccount(provider.connection, aliceTokenAccount);
+
+      // Sending 1 DEVR leaves 49, above the 45 DEVR reserve
+      const transferAmount = 1_000_000_000;
+      const keepReserve = 45_000_000_000;
+
+      await program.methods
+        .transfer(new anchor.BN(transferAmount), new anchor.BN(keepReserve))
+        .accounts({
+          fromTokenAccount: aliceTokenAccount,
+          toTokenAccount: bobTokenAccount,
+          authority: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+
+      const aliceBalanceAfter = await getAccount(provider.connection, aliceTokenAccount);
+      expect(aliceBalanceAfter.amount).to.equal(aliceBalanceBefore.amount - BigInt(transferAmount));
+      expect(aliceBalanceAfter.amount >= BigInt(keepReserve)).to.be.true;
+    });
+
+    it("should reject a transfer to the source account itself", async () => {
+      const aliceBalanceBefore = await getAccount(provider.connection, aliceTokenAccount);
+
+      try {
+        await program.methods
+          .transfer(new anchor.BN(1_000_000_000), new anchor.BN(0))
+          .accounts({
+            fromTokenAccount: aliceTokenAccount,
+            toTokenAccount: aliceTokenAccount,
+            authority: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown SelfTransfer error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6089); // SelfTransfer
+      }
+
+      const aliceBalanceAfter = await getAccount(provider.connection, aliceTokenAccount);
+      expect(aliceBalanceAfter.amount).to.equal(aliceBalanceBefore.amount);
+    });
+
+    it("should fail when the token program rejects the transfer", async () => {
+      const aliceBalanceBefore = await getAccount(provider.connection, aliceTokenAccount);
+      const bobBalanceBefore = await getAccount(provider.connection, bobTokenAccount);
+
+      // Alice's balance covers the amount, so the program's own checks pass;
+      // the SPL transfer then fails because Bob doesn't own the source account
+      try {
+        await program.methods
+          .transfer(new anchor.BN(1_000_000_000), new anchor.BN(0))
+          .accounts({
+            fromTokenAccount: aliceTokenAccount,
+            toTokenAccount: bobTokenAccount,
+            authority: bob.publicKey,
+          })
+          .signers([bob])
+          .rpc();
+
+        expect.fail("Should have thrown a token program error");
+      } catch (error: any) {
+        expect(error.message).to.not.include("Should have thrown");
+        const logs: string[] = error.logs ?? [];
+        expect(logs.some((log) => log.includes("Transfer successful"))).to.be.false;
+      }
+
+      const aliceBalanceAfter = await getAccount(provider.connection, aliceTokenAccount);
+      const bobBalanceAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(aliceBalanceAfter.amount).to.equal(aliceBalanceBefore.amount);
+      expect(bobBalanceAfter.amount).to.equal(bobBalanceBefore.amount);
+    });
   });
 
   describe("Delegation Pattern", () => {
@@ -175,6 +302,28 @@ describe("Token Transfers & Delegation Tests", () => {
       });
     });
 
+    it("should emit DelegateApprovedEvent on approve", async () => {
+      const delegateAmount = 30_000_000_000; // 30 DEVR
+
+      const simulation = await program.methods
+        .approveDelegate(new anchor.BN(delegateAmount))
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          delegate: delegate.publicKey,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .simulate();
+
+      const event = simulation.events.find((e) => e.name === "delegateApprovedEvent");
+      expect(event).to.not.be.undefined;
+      expect(event.data.owner.toString()).to.equal(alice.publicKey.toString());
+      expect(event.data.delegate.toString()).to.equal(delegate.publicKey.toString());
+      expect(event.data.tokenAccount.toString()).to.equal(aliceTokenAccount.toString());
+      expect(event.data.amount.toNumber()).to.equal(delegateAmount);
+      expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
+    });
+
     it("should approve delegate successfully", async () => {
       const delegateAmount = 30_000_000_000; // 30 DEVR
 
@@ -226,6 +375,50 @@ describe("Token Transfers & Delegation Tests", () => {
       expect(aliceBalanceAfter.delegatedAmount).to.equal(delegatedAmountBefore - BigInt(transferAmount));
     });
 
+    it("should emit a DelegatedTransferEvent", async () => {
+      const transferAmount = 5_000_000_000; // 5 DEVR, within the remaining allowance
+
+      const simulation = await program.methods
+        .delegatedTransfer(new anchor.BN(transferAmount))
+        .accounts({
+          fromTokenAccount: aliceTokenAccount,
+          toTokenAccount: bobTokenAccount,
+          delegate: delegate.publicKey,
+        })
+        .signers([delegate])
+        .simulate();
+
+      const event = simulation.events.find((e) => e.name === "delegatedTransferEvent");
+      expect(event).to.not.be.undefined;
+      expect(event.data.from.toString()).to.equal(aliceTokenAccount.toString());
+      expect(event.data.to.toString()).to.equal(bobTokenAccount.toString());
+      expect(event.data.delegate.toString()).to.equal(delegate.publicKey.toString());
+      expect(event.data.amount.toNumber()).to.equal(transferAmount);
+      expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("should reject a delegated transfer back into the source account", async () => {
+      try {
+        await program.methods
+          .delegatedTransfer(new anchor.BN(1_000_000_000))
+          .accounts({
+            fromTokenAccount: aliceTokenAccount,
+            toTokenAccount: aliceTokenAccount,
+            delegate: delegate.publicKey,
+          })
+          .signers([delegate])
+          .rpc();
+
+        expect.fail("Should have thrown SelfTransfer error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6089); // SelfTransfer
+      }
+
+      // The allowance is untouched
+      const tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(10_000_000_000n);
+    });
+
     it("should fail if delegate exceeds approved amount", async () => {
       // Delegate has 10 DEVR remaining (30 approved - 20 used)
       // Try to transfer 50 DEVR
@@ -249,6 +442,23 @@ describe("Token Transfers & Delegation Tests", () => {
       }
     });
 
+    it("should emit DelegateRevokedEvent on revoke", async () => {
+      const simulation = await program.methods
+        .revokeDelegate()
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .simulate();
+
+      const event = simulation.events.find((e) => e.name === "delegateRevokedEvent");
+      expect(event).to.not.be.undefined;
+      expect(event.data.owner.toString()).to.equal(alice.publicKey.toString());
+      expect(event.data.tokenAccount.toString()).to.equal(aliceTokenAccount.toString());
+      expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
+    });
+
     it("should revoke delegation successfully", async () => {
       await program.methods
         .revokeDelegate()
@@ -287,4 +497,749 @@ describe("Token Transfers & Delegation Tests", () => {
       }
     });
   });
+
+  describe("Aggregate Delegation Cap", () => {
+    let capDelegate: Keypair;
+
+    before(async () => {
+      capDelegate = Keypair.generate();
+
+      // Cap delegated allowance at 50% of balance
+      await program.methods
+        .setDelegationCap(new anchor.BN(5_000))
+        .accounts({
+          config: configPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods
+        .setDelegationCap(new anchor.BN(10_000))
+        .accounts({
+          config: configPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+    });
+
+    it("should allow an allowance up to the aggregate cap", async () => {
+      const balance = await getAccount(provider.connection, aliceTokenAccount);
+      const maxAllowance = (balance.amount * 5_000n) / 10_000n;
+
+      await program.methods
+        .approveDelegate(new anchor.BN(maxAllowance.toString()))
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          delegate: capDelegate.publicKey,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+
+      const tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(maxAllowance);
+    });
+
+    it("should reject an allowance over the aggregate cap", async () => {
+      const balance = await getAccount(provider.connection, aliceTokenAccount);
+      const overCap = (balance.amount * 5_000n) / 10_000n + 1n;
+
+      try {
+        await program.methods
+          .approveDelegate(new anchor.BN(overCap.toString()))
+          .accounts({
+            tokenAccount: aliceTokenAccount,
+            delegate: capDelegate.publicKey,
+            owner: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown TotalDelegationExceeded error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6025); // TotalDelegationExceeded
+      }
+    });
+
+    it("should reject cap updates from a non-admin", async () => {
+      try {
+        await program.methods
+          .setDelegationCap(new anchor.BN(10_000))
+          .accounts({
+            config: configPda,
+            admin: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should reject a cap above 100%", async () => {
+      try {
+        await program.methods
+          .setDelegationCap(new anchor.BN(10_001))
+          .accounts({
+            config: configPda,
+            admin: admin.publicKey,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidDelegationCap error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6026); // InvalidDelegationCap
+      }
+    });
+  });
+
+  describe("Max Delegation Amount", () => {
+    const cap = 5_000_000_000n; // 5 DEVR
+    let cappedDelegate: Keypair;
+
+    const approve = (amount: bigint) =>
+      program.methods
+        .approveDelegate(new anchor.BN(amount.toString()))
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          delegate: cappedDelegate.publicKey,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+
+    const setMaxDelegationAmount = (amount: bigint) =>
+      program.methods
+        .setMaxDelegationAmount(new anchor.BN(amount.toString()))
+        .accounts({
+          config: configPda,
+          admin: admin.publicKey,
+        })
+        .rpc();
+
+    before(async () => {
+      cappedDelegate = Keypair.generate();
+      await setMaxDelegationAmount(cap);
+    });
+
+    after(async () => {
+      await setMaxDelegationAmount(0n);
+
+      await program.methods
+        .revokeDelegate()
+        .accounts({
+          tokenAccount: aliceTokenAccount,
+          owner: alice.publicKey,
+        })
+        .signers([alice])
+        .rpc();
+    });
+
+    it("should allow allowances below and at the cap", async () => {
+      await approve(cap - 1n);
+      let tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(cap - 1n);
+
+      await approve(cap);
+      tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(cap);
+    });
+
+    it("should reject an allowance above the cap", async () => {
+      try {
+        await approve(cap + 1n);
+        expect.fail("Should have thrown DelegationTooLarge error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6090); // DelegationTooLarge
+      }
+
+      // The earlier allowance stands
+      const tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(cap);
+    });
+
+    it("should allow any allowance once the cap is set to 0", async () => {
+      await setMaxDelegationAmount(0n);
+      await approve(cap + 1n);
+
+      const tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(cap + 1n);
+      const config = await program.account.tokenConfig.fetch(configPda);
+      expect(config.maxDelegationAmount.toNumber()).to.equal(0);
+
+      await setMaxDelegationAmount(cap);
+    });
+
+    it("should reject max delegation updates from a non-admin", async () => {
+      try {
+        await program.methods
+          .setMaxDelegationAmount(new anchor.BN(0))
+          .accounts({
+            config: configPda,
+            admin: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+  });
+
+  describe("Delegation Cooldown", () => {
+    const COOLDOWN_SECONDS = 3;
+    let cooldownOwner: Keypair;
+    let cooldownTokenAccount: PublicKey;
+    let cooldownDelegate: Keypair;
+
+    const setCooldown = async (seconds: number) =>
+      program.methods
+        .setDelegationCooldown(new anchor.BN(seconds))
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+    const approve = async () =>
+      program.methods
+        .approveDelegate(new anchor.BN(1_000_000_000))
+        .accounts({
+          tokenAccount: cooldownTokenAccount,
+          delegate: cooldownDelegate.publicKey,
+          owner: cooldownOwner.publicKey,
+        })
+        .signers([cooldownOwner])
+        .rpc();
+
+    before(async () => {
+      cooldownOwner = await createAndFundUser(provider, program, mintPda, 2);
+      cooldownTokenAccount = await getAssociatedTokenAddress(mintPda, cooldownOwner.publicKey);
+      cooldownDelegate = Keypair.generate();
+      await setCooldown(COOLDOWN_SECONDS);
+    });
+
+    after(async () => {
+      await setCooldown(0);
+    });
+
+    it("should allow a first approval on an account never revoked", async () => {
+      await approve();
+      const tokenAccountData = await getAccount(provider.connection, cooldownTokenAccount);
+      expect(tokenAccountData.delegatedAmount).to.equal(1_000_000_000n);
+    });
+
+    it("should block re-approval within the cooldown after a revoke", async () => {
+      await program.methods
+        .revokeDelegate()
+        .accounts({ tokenAccount: cooldownTokenAccount, owner: cooldownOwner.publicKey })
+        .signers([cooldownOwner])
+        .rpc();
+
+      try {
+        await approve();
+        expect.fail("Should have thrown DelegationCooldown error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6056); // DelegationCooldown
+      }
+    });
+
+    it("should allow re-approval once the cooldown has passed", async () => {
+      await new Promise((resolve) => setTimeout(resolve, (COOLDOWN_SECONDS + 2) * 1000));
+
+      await approve();
+      const tokenAccountData = await getAccount(provider.connection, cooldownTokenAccount);
+      expect(tokenAccountData.delegate?.toBase58()).to.equal(cooldownDelegate.publicKey.toBase58());
+    });
+
+    it("should reject a cooldown over 7 days or from a non-admin", async () => {
+      try {
+        await setCooldown(7 * 86_400 + 1);
+        expect.fail("Should have thrown InvalidDelegationCooldown error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6057); // InvalidDelegationCooldown
+      }
+
+      try {
+        await program.methods
+          .setDelegationCooldown(new anchor.BN(60))
+          .accounts({ config: configPda, admin: cooldownOwner.publicKey })
+          .signers([cooldownOwner])
+          .rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+  });
+
+  describe("Close Token Account", () => {
+    it("should close an empty token account and return rent", async () => {
+      const closer = Keypair.generate();
+      const airdrop = await provider.connection.requestAirdrop(
+        closer.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction({
+        signature: airdrop,
+        ...(await provider.connection.getLatestBlockhash()),
+      });
+
+      const closerTokenAccount = await getAssociatedTokenAddress(mintPda, closer.publicKey);
+
+      // Claim 100 DEVR, then send the full balance to Bob
+      await program.methods
+        .claimTokens(await getClaimNonce(program, closer.publicKey), [])
+        .accounts({
+          mint: mintPda,
+          user: closer.publicKey,
+        })
+        .signers([closer])
+        .rpc();
+
+      const balance = await getAccount(provider.connection, closerTokenAccount);
+
+      await program.methods
+        .transfer(new anchor.BN(balance.amount.toString()), new anchor.BN(0))
+        .accounts({
+          fromTokenAccount: closerTokenAccount,
+          toTokenAccount: bobTokenAccount,
+          authority: closer.publicKey,
+        })
+        .signers([closer])
+        .rpc();
+
+      const rent = await provider.connection.getBalance(closerTokenAccount);
+      const lamportsBefore = await provider.connection.getBalance(closer.publicKey);
+
+      await program.methods
+        .closeTokenAccount()
+        .accounts({
+          config: configPda,
+          tokenAccount: closerTokenAccount,
+          owner: closer.publicKey,
+        })
+        .signers([closer])
+        .rpc();
+
+      const closedAccount = await provider.connection.getAccountInfo(closerTokenAccount);
+      expect(closedAccount).to.be.null;
+
+      // Owner got the rent back (minus the transaction fee)
+      const lamportsAfter = await provider.connection.getBalance(closer.publicKey);
+      expect(lamportsAfter).to.be.greaterThan(lamportsBefore + rent - 10_000);
+    });
+
+    it("should fail to close a token account that still holds tokens", async () => {
+      try {
+        await program.methods
+          .closeTokenAccount()
+          .accounts({
+            config: configPda,
+            tokenAccount: aliceTokenAccount,
+            owner: alice.publicKey,
+          })
+          .signers([alice])
+          .rpc();
+
+        expect.fail("Should have thrown TokenAccountNotEmpty error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6017); // TokenAccountNotEmpty
+      }
+
+      const tokenAccountData = await getAccount(provider.connection, aliceTokenAccount);
+      expect(Number(tokenAccountData.amount)).to.be.greaterThan(0);
+    });
+  });
+
+  describe("Batch Transfer", () => {
+    let sender: Keypair;
+    let senderTokenAccount: PublicKey;
+
+    const recipients = (accounts: PublicKey[]) =>
+      accounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }));
+
+    before(async () => {
+      sender = await createAndFundUser(provider, program, mintPda, 2);
+      senderTokenAccount = await getAssociatedTokenAddress(mintPda, sender.publicKey);
+    });
+
+    it("should allow a duplicate recipient when reject_duplicates is off", async () => {
+      const bobBefore = await getAccount(provider.connection, bobTokenAccount);
+
+      await program.methods
+        .batchTransfer([new anchor.BN(1_000_000_000), new anchor.BN(2_000_000_000)], false)
+        .accounts({
+          fromTokenAccount: senderTokenAccount,
+          authority: sender.publicKey,
+        })
+        .remainingAccounts(recipients([bobTokenAccount, bobTokenAccount]))
+        .signers([sender])
+        .rpc();
+
+      const bobAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(Number(bobAfter.amount) - Number(bobBefore.amount)).to.equal(3_000_000_000);
+    });
+
+    it("should reject a duplicate recipient when reject_duplicates is on", async () => {
+      try {
+        await program.methods
+          .batchTransfer(
+            [new anchor.BN(1_000_000_000), new anchor.BN(1_000_000_000), new anchor.BN(1_000_000_000)],
+            true
+          )
+          .accounts({
+            fromTokenAccount: senderTokenAccount,
+            authority: sender.publicKey,
+          })
+          .remainingAccounts(recipients([bobTokenAccount, aliceTokenAccount, bobTokenAccount]))
+          .signers([sender])
+          .rpc();
+
+        expect.fail("Should have thrown DuplicateRecipient error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6034); // DuplicateRecipient
+      }
+    });
+
+    it("should send to distinct recipients with reject_duplicates on", async () => {
+      const aliceBefore = await getAccount(provider.connection, aliceTokenAccount);
+      const bobBefore = await getAccount(provider.connection, bobTokenAccount);
+
+      await program.methods
+        .batchTransfer([new anchor.BN(1_000_000_000), new anchor.BN(1_000_000_000)], true)
+        .accounts({
+          fromTokenAccount: senderTokenAccount,
+          authority: sender.publicKey,
+        })
+        .remainingAccounts(recipients([aliceTokenAccount, bobTokenAccount]))
+        .signers([sender])
+        .rpc();
+
+      const aliceAfter = await getAccount(provider.connection, aliceTokenAccount);
+      const bobAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(Number(aliceAfter.amount) - Number(aliceBefore.amount)).to.equal(1_000_000_000);
+      expect(Number(bobAfter.amount) - Number(bobBefore.amount)).to.equal(1_000_000_000);
+    });
+
+    it("should reject a batch with mismatched amounts and recipients", async () => {
+      try {
+        await program.methods
+          .batchTransfer([new anchor.BN(1_000_000_000)], false)
+          .accounts({
+            fromTokenAccount: senderTokenAccount,
+            authority: sender.publicKey,
+          })
+          .remainingAccounts(recipients([aliceTokenAccount, bobTokenAccount]))
+          .signers([sender])
+          .rpc();
+
+        expect.fail("Should have thrown InvalidBatchTransfer error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6033); // InvalidBatchTransfer
+      }
+    });
+  });
+
+  describe("Recipient Blocklist", () => {
+    // Only transfers through this program are gated; direct SPL transfers aren't
+    let sender: Keypair;
+    let senderTokenAccount: PublicKey;
+    let blocked: Keypair;
+    let blockedTokenAccount: PublicKey;
+
+    const block = (address: PublicKey, signer?: Keypair) => {
+      const builder = program.methods
+        .blockAddress(address)
+        .accounts({ config: configPda, admin: (signer ?? admin).publicKey });
+      return signer ? builder.signers([signer]).rpc() : builder.rpc();
+    };
+
+    const transferTo = (toTokenAccount: PublicKey) =>
+      program.methods
+        .transfer(new anchor.BN(1_000_000_000), new anchor.BN(0))
+        .accounts({
+          fromTokenAccount: senderTokenAccount,
+          toTokenAccount,
+          authority: sender.publicKey,
+        })
+        .signers([sender])
+        .rpc();
+
+    before(async () => {
+      sender = await createAndFundUser(provider, program, mintPda, 2);
+      senderTokenAccount = await getAssociatedTokenAddress(mintPda, sender.publicKey);
+      blocked = Keypair.generate();
+      blockedTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, sender, mintPda, blocked.publicKey)
+      ).address;
+    });
+
+    it("should reject blocking from non-admin", async () => {
+      try {
+        await block(blocked.publicKey, sender);
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should reject a transfer to a blocked owner", async () => {
+      await block(blocked.publicKey);
+
+      try {
+        await transferTo(blockedTokenAccount);
+        expect.fail("Should have thrown RecipientBlocked error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6052); // RecipientBlocked
+      }
+    });
+
+    it("should reject a delegated transfer to a blocked owner", async () => {
+      const delegate = Keypair.generate();
+      await program.methods
+        .approveDelegate(new anchor.BN(1_000_000_000))
+        .accounts({
+          tokenAccount: senderTokenAccount,
+          delegate: delegate.publicKey,
+          owner: sender.publicKey,
+        })
+        .signers([sender])
+        .rpc();
+
+      try {
+        await program.methods
+          .delegatedTransfer(new anchor.BN(1_000_000_000))
+          .accounts({
+            fromTokenAccount: senderTokenAccount,
+            toTokenAccount: blockedTokenAccount,
+            delegate: delegate.publicKey,
+          })
+          .signers([delegate])
+          .rpc();
+        expect.fail("Should have thrown RecipientBlocked error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6052); // RecipientBlocked
+      }
+    });
+
+    it("should still transfer to a normal owner", async () => {
+      const bobBefore = await getAccount(provider.connection, bobTokenAccount);
+      await transferTo(bobTokenAccount);
+      const bobAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(Number(bobAfter.amount) - Number(bobBefore.amount)).to.equal(1_000_000_000);
+    });
+
+    it("should restore transfers after unblocking", async () => {
+      await program.methods
+        .unblockAddress(blocked.publicKey)
+        .accounts({ config: configPda, admin: admin.publicKey })
+        .rpc();
+
+      await transferTo(blockedTokenAccount);
+      const blockedAccount = await getAccount(provider.connection, blockedTokenAccount);
+      expect(Number(blockedAccount.amount)).to.equal(1_000_000_000);
+    });
+  });
+
+  describe("DEVR-only Transfer", () => {
+    // `transfer_devr` pins both accounts to the platform mint; `transfer` only checks they match
+    let holder: Keypair;
+    let foreignFrom: PublicKey;
+    let foreignTo: PublicKey;
+
+    const transferDevr = (fromTokenAccount: PublicKey, toTokenAccount: PublicKey) =>
+      program.methods
+        .transferDevr(new anchor.BN(1_000_000_000), new anchor.BN(0))
+        .accounts({
+          fromTokenAccount,
+          toTokenAccount,
+          authority: holder.publicKey,
+        })
+        .signers([holder])
+        .rpc();
+
+    before(async () => {
+      holder = await createAndFundUser(provider, program, mintPda, 2);
+
+      const foreignMint = await createMint(provider.connection, holder, holder.publicKey, null, 9);
+      foreignFrom = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, holder, foreignMint, holder.publicKey)
+      ).address;
+      foreignTo = (
+        await getOrCreateAssociatedTokenAccount(provider.connection, holder, foreignMint, bob.publicKey)
+      ).address;
+      await mintTo(provider.connection, holder, foreignMint, foreignFrom, holder, 10_000_000_000);
+    });
+
+    it("should transfer DEVR between DEVR accounts", async () => {
+      const holderTokenAccount = await getAssociatedTokenAddress(mintPda, holder.publicKey);
+      const bobBefore = await getAccount(provider.connection, bobTokenAccount);
+
+      await transferDevr(holderTokenAccount, bobTokenAccount);
+
+      const bobAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(Number(bobAfter.amount) - Number(bobBefore.amount)).to.equal(1_000_000_000);
+    });
+
+    it("should reject a foreign-mint transfer with MintMismatch", async () => {
+      try {
+        await transferDevr(foreignFrom, foreignTo);
+        expect.fail("Should have thrown MintMismatch error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6004); // MintMismatch
+      }
+
+      const destination = await getAccount(provider.connection, foreignTo);
+      expect(Number(destination.amount)).to.equal(0);
+    });
+  });
+
+  describe("Admin Transfer Limits", () => {
+    // transfer, delegated_transfer and batch_transfer all read TokenConfig.min_transfer / max_transfer
+    const DEVR = 1_000_000_000;
+    const DEFAULT_MIN_TRANSFER = DEVR;
+    const DEFAULT_MAX_TRANSFER = 10_000 * DEVR;
+
+    let holder: Keypair;
+    let holderTokenAccount: PublicKey;
+    let delegate: Keypair;
+
+    const setTransferLimits = (minTransfer: number, maxTransfer: number) =>
+      program.methods.setTransferLimits(new anchor.BN(minTransfer), new anchor.BN(maxTransfer));
+
+    before(async () => {
+      holder = await createAndFundUser(provider, program, mintPda, 2);
+      holderTokenAccount = await getAssociatedTokenAddress(mintPda, holder.publicKey);
+
+      delegate = Keypair.generate();
+      await program.methods
+        .approveDelegate(new anchor.BN(10 * DEVR))
+        .accounts({
+          tokenAccount: holderTokenAccount,
+          delegate: delegate.publicKey,
+          owner: holder.publicKey,
+        })
+        .signers([holder])
+        .rpc();
+    });
+
+    after(async () => {
+      // Other suites assume the compiled-in defaults
+      await setTransferLimits(DEFAULT_MIN_TRANSFER, DEFAULT_MAX_TRANSFER).rpc();
+    });
+
+    it("should reject transfer limits from a non-admin", async () => {
+      try {
+        await setTransferLimits(2 * DEVR, 5 * DEVR)
+          .accounts({ admin: holder.publicKey })
+          .signers([holder])
+          .rpc();
+        expect.fail("Should have thrown Unauthorized error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6018); // Unauthorized
+      }
+    });
+
+    it("should reject a minimum above the maximum", async () => {
+      try {
+        await setTransferLimits(5 * DEVR, 2 * DEVR).rpc();
+        expect.fail("Should have thrown InvalidTransferLimits error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6086); // InvalidTransferLimits
+      }
+    });
+
+    it("should update the limits and emit a TransferLimitsUpdatedEvent", async () => {
+      const { events } = await setTransferLimits(2 * DEVR, 5 * DEVR).simulate();
+      const updated = events.find((e) => e.name === "transferLimitsUpdatedEvent")?.data as any;
+      expect(updated.minTransfer.toNumber()).to.equal(2 * DEVR);
+      expect(updated.maxTransfer.toNumber()).to.equal(5 * DEVR);
+
+      await setTransferLimits(2 * DEVR, 5 * DEVR).rpc();
+
+      const config = await program.account.tokenConfig.fetch(configPda);
+      expect(config.minTransfer.toNumber()).to.equal(2 * DEVR);
+      expect(config.maxTransfer.toNumber()).to.equal(5 * DEVR);
+    });
+
+    it("should hold transfer to the configured limits", async () => {
+      try {
+        await program.methods
+          .transfer(new anchor.BN(DEVR), new anchor.BN(0))
+          .accounts({
+            fromTokenAccount: holderTokenAccount,
+            toTokenAccount: bobTokenAccount,
+            authority: holder.publicKey,
+          })
+          .signers([holder])
+          .rpc();
+        expect.fail("Should have thrown AmountTooSmall error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6001); // AmountTooSmall
+      }
+
+      const bobBefore = await getAccount(provider.connection, bobTokenAccount);
+      await program.methods
+        .transfer(new anchor.BN(3 * DEVR), new anchor.BN(0))
+        .accounts({
+          fromTokenAccount: holderTokenAccount,
+          toTokenAccount: bobTokenAccount,
+          authority: holder.publicKey,
+        })
+        .signers([holder])
+        .rpc();
+      const bobAfter = await getAccount(provider.connection, bobTokenAccount);
+      expect(Number(bobAfter.amount) - Number(bobBefore.amount)).to.equal(3 * DEVR);
+    });
+
+    it("should hold delegated_transfer to the configured limits", async () => {
+      try {
+        await program.methods
+          .delegatedTransfer(new anchor.BN(6 * DEVR))
+          .accounts({
+            fromTokenAccount: holderTokenAccount,
+            toTokenAccount: bobTokenAccount,
+            delegate: delegate.publicKey,
+          })
+          .signers([delegate])
+          .rpc();
+        expect.fail("Should have thrown AmountTooLarge error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6002); // AmountTooLarge
+      }
+    });
+
+    it("should hold every batch_transfer leg to the configured limits", async () => {
+      try {
+        await program.methods
+          .batchTransfer([new anchor.BN(3 * DEVR), new anchor.BN(DEVR)], false)
+          .accounts({
+            fromTokenAccount: holderTokenAccount,
+            authority: holder.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: bobTokenAccount, isWritable: true, isSigner: false },
+            { pubkey: aliceTokenAccount, isWritable: true, isSigner: false },
+          ])
+          .signers([holder])
+          .rpc();
+        expect.fail("Should have thrown AmountTooSmall error");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.number).to.equal(6001); // AmountTooSmall
+      }
+    });
+  });
 });
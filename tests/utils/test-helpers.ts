@@ -2,10 +2,12 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { DevrewardsPlatform } from "../../target/types/devrewards_platform";
 import { PublicKey, Keypair } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 
 export const SECONDS_PER_DAY = 86400;
 export const MIN_LOCK_DURATION = 7 * SECONDS_PER_DAY; // 7 days
 export const MAX_LOCK_DURATION = 10 * 365 * SECONDS_PER_DAY; // 10 years
+export const CURRENT_ACCOUNT_VERSION = 1;
 
 /**
  * Gets the program, provider, and admin wallet
@@ -110,6 +112,56 @@ export function deriveUserClaimPda(
   return pda;
 }
 
+/**
+ * Current claim nonce for a user (0 if they have never claimed)
+ */
+export async function getClaimNonce(
+  program: Program<DevrewardsPlatform>,
+  user: PublicKey
+): Promise<anchor.BN> {
+  const userClaim = await program.account.userClaim.fetchNullable(
+    deriveUserClaimPda(user, program)
+  );
+  return userClaim ? userClaim.claimNonce : new anchor.BN(0);
+}
+
+/**
+ * Derives user stake stats PDA
+ */
+export function deriveUserStakeStatsPda(
+  user: PublicKey,
+  program: Program<DevrewardsPlatform>
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user-stake-stats"), user.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+/**
+ * Derives user lifetime stats PDA
+ */
+export function deriveUserStatsPda(
+  user: PublicKey,
+  program: Program<DevrewardsPlatform>
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user-stats"), user.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+/**
+ * Returns the validator's current unix timestamp
+ */
+export async function getChainTime(provider: anchor.AnchorProvider): Promise<number> {
+  const slot = await provider.connection.getSlot();
+  const blockTime = await provider.connection.getBlockTime(slot);
+  return blockTime ?? Math.floor(Date.now() / 1000);
+}
+
 /**
  * Ensures the program is initialized (safe to call multiple times)
  */
@@ -124,6 +176,52 @@ export async function setupInitializedProgram(
     // Not initialized yet, initialize now
     await program.methods.initialize().rpc();
   }
+
+  await ensureRewardReserve(program);
+}
+
+const DEVR = 1_000_000_000;
+const REWARD_RESERVE_FLOOR = 1_000 * DEVR; // Top up once spare liquidity drops below this
+const REWARD_RESERVE_TOP_UP = 10_000 * DEVR; // The faucet maximum, claimed in one go
+const DEFAULT_DAILY_CLAIM_AMOUNT = 100 * DEVR;
+
+/**
+ * `stake` reserves each position's maximum rewards, so the vault needs liquidity
+ * beyond staked principal. Funds it from a throwaway wallet whenever it runs low.
+ */
+export async function ensureRewardReserve(program: Program<DevrewardsPlatform>) {
+  const provider = program.provider as anchor.AnchorProvider;
+  const { mintPda, vaultPda, globalStatsPda } = deriveProgramPDAs(program);
+
+  const stats = await program.account.globalStats.fetch(globalStatsPda);
+  const vault = await provider.connection.getTokenAccountBalance(vaultPda);
+  const spare =
+    BigInt(vault.value.amount) -
+    BigInt(stats.totalStaked.toString()) -
+    BigInt(stats.totalRewardLiability.toString());
+  if (spare >= BigInt(REWARD_RESERVE_FLOOR)) {
+    return;
+  }
+
+  const funder = await createAndFundUserWithoutTokens(provider);
+
+  // Raise the faucet for a single claim, then put the default back
+  await program.methods.updateConfig(new anchor.BN(REWARD_RESERVE_TOP_UP)).rpc();
+  await program.methods
+    .claimTokens(new anchor.BN(0), [])
+    .accounts({ mint: mintPda, user: funder.publicKey })
+    .signers([funder])
+    .rpc();
+  await program.methods.updateConfig(new anchor.BN(DEFAULT_DAILY_CLAIM_AMOUNT)).rpc();
+
+  await program.methods
+    .fundVault(new anchor.BN(REWARD_RESERVE_TOP_UP))
+    .accounts({
+      funderTokenAccount: getAssociatedTokenAddressSync(mintPda, funder.publicKey),
+      funder: funder.publicKey,
+    })
+    .signers([funder])
+    .rpc();
 }
 
 /**
@@ -147,9 +245,9 @@ export async function createAndFundUser(
     ...(await provider.connection.getLatestBlockhash()),
   });
 
-  // Claim tokens
+  // Claim tokens (a brand-new user is at nonce 0)
   await program.methods
-    .claimTokens()
+    .claimTokens(new anchor.BN(0), [])
     .accounts({
       mint: mintPda,
       user: user.publicKey,